@@ -0,0 +1,182 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  derive_pda2, executable, get_time_i64, instructions::check_signer, net_received, none_zero_u64,
+  parse_u64, rent_exempt_tokacct_iface, tok_acct_amount, writable, Auction, Bid, Ee, TransferIface,
+  PROG_ADDR,
+};
+
+/// Bid on an Auction. The bid is locked in the bidder's Bid PDA. An outbid bid stays in its Bid PDA until its bidder takes it back with AuctionBidWithdraw, or bids again by adding amount to it
+pub struct AuctionBid<'a> {
+  pub bidder: &'a AccountView, //signer
+  pub bidder_ata_y: &'a AccountView,
+  pub bid_pda: &'a AccountView,
+  pub bid_ata_y: &'a AccountView,
+  pub mint_y: &'a AccountView,
+  pub auction_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub amount: u64, //added to the bidder's Bid PDA
+}
+impl<'a> AuctionBid<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &52;
+
+  pub fn process(self) -> ProgramResult {
+    let AuctionBid {
+      bidder,
+      bidder_ata_y,
+      bid_pda,
+      bid_ata_y,
+      mint_y,
+      auction_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      amount,
+    } = self;
+    log!("AuctionBid process()");
+    let auction = Auction::from_account_view(auction_pda)?;
+    if get_time_i64()? >= auction.end_time() {
+      return Ee::AuctionEnded.e();
+    }
+    if auction.mint_y().ne(mint_y.address()) {
+      return Ee::EscrowMintY.e();
+    }
+    //the highest bidder cannot outbid itself
+    if auction.high_bidder().eq(bidder.address()) {
+      return Ee::AuctionBid.e();
+    }
+    let (expected_bid, bump) =
+      derive_pda2(auction_pda.address(), bidder.address().as_ref(), Bid::SEED)?;
+    if expected_bid.ne(bid_pda.address()) {
+      return Ee::BidPDA.e();
+    }
+    //an outbid bidder bids again on top of what its Bid PDA still holds
+    let prior = if bid_pda.lamports() > 0 {
+      Bid::from_account_view(bid_pda)?.amount()
+    } else {
+      log!("Make Bid PDA");
+      let signer_seeds = [
+        Seed::from(Bid::SEED),
+        Seed::from(auction_pda.address().as_ref()),
+        Seed::from(bidder.address().as_ref()),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+      pinocchio_system::instructions::CreateAccount {
+        from: bidder,
+        to: bid_pda,
+        lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Bid::LEN)?,
+        space: Bid::LEN as u64,
+        owner: &PROG_ADDR,
+      }
+      .invoke_signed(&[seed_signer])?;
+      0
+    };
+    let total = prior
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    auction.outbid(bidder.address(), total)?;
+    log!("high bid: {}", total);
+
+    if bid_ata_y.is_data_empty() {
+      log!("Make bid_ata_y");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: bidder,
+        account: bid_ata_y,
+        wallet: bid_pda,
+        mint: mint_y,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("bid_ata_y has data");
+      check_ata_iface(bid_ata_y, bid_pda, mint_y, token_program)?;
+      rent_exempt_tokacct_iface(bid_ata_y, rent_sysvar, token_program)?;
+    }
+    let balc_before = tok_acct_amount(bid_ata_y)?;
+    TransferIface {
+      from: bidder_ata_y,
+      mint: mint_y,
+      to: bid_ata_y,
+      authority: bidder,
+      amount,
+      decimals: auction.decimal_y(),
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke()?;
+
+    let bid = Bid::from_account_view(bid_pda)?;
+    bid.set_auction(auction_pda.address());
+    bid.set_bidder(bidder.address());
+    //the maker receives what the Bid ATA holds, which can be less than amount after transfer fees
+    let received = net_received(bid_ata_y, balc_before)?;
+    bid.set_amount(
+      prior
+        .checked_add(received)
+        .ok_or(ProgramError::ArithmeticOverflow)?,
+    )?;
+    bid.set_bump(bump);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AuctionBid<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AuctionBid try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //u64 takes 8 bytes
+    check_data_len(data, 8)?;
+
+    let [bidder, bidder_ata_y, bid_pda, bid_ata_y, mint_y, auction_pda, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(bidder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(bidder_ata_y)?;
+    writable(bid_pda)?;
+    writable(bid_ata_y)?;
+    writable(auction_pda)?;
+    check_ata_iface(bidder_ata_y, bidder, mint_y, token_program)?;
+
+    let amount = parse_u64(data)?;
+    log!("amount: {}", amount);
+    none_zero_u64(amount)?;
+    if tok_acct_amount(bidder_ata_y)? < amount {
+      return Err(Ee::TakerInsuffTokenY.into());
+    }
+    Ok(Self {
+      bidder,
+      bidder_ata_y,
+      bid_pda,
+      bid_ata_y,
+      mint_y,
+      auction_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      amount,
+    })
+  }
+}
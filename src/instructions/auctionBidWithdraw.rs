@@ -0,0 +1,145 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface, check_sysprog,
+  close_pda, derive_ata, executable, instructions::check_signer, tok_acct_amount, writable,
+  Auction, Bid, CloseAccountIface, Ee, TransferIface,
+};
+
+/// Outbid bidder takes its bid back from its Bid PDA, and the Bid PDA is closed. The highest bid stays until AuctionSettle pays it to the maker. Once the Auction is settled and closed, every Bid PDA left is an outbid one
+pub struct AuctionBidWithdraw<'a> {
+  pub bidder: &'a AccountView, //signer
+  pub bidder_ata_y: &'a AccountView,
+  pub bid_pda: &'a AccountView,
+  pub bid_ata_y: &'a AccountView,
+  pub mint_y: &'a AccountView,
+  pub auction_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub decimals: u8,
+}
+impl<'a> AuctionBidWithdraw<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &71;
+
+  pub fn process(self) -> ProgramResult {
+    let AuctionBidWithdraw {
+      bidder,
+      bidder_ata_y,
+      bid_pda,
+      bid_ata_y,
+      mint_y,
+      auction_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      decimals,
+    } = self;
+    log!("AuctionBidWithdraw process()");
+    if auction_pda.lamports() > 0
+      && Auction::from_account_view(auction_pda)?
+        .high_bidder()
+        .eq(bidder.address())
+    {
+      return Ee::AuctionBid.e();
+    }
+
+    if bidder_ata_y.is_data_empty() {
+      log!("Make bidder_ata_y");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: bidder,
+        account: bidder_ata_y,
+        wallet: bidder,
+        mint: mint_y,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(bidder_ata_y, bidder, mint_y, token_program)?;
+    }
+    let bump = Bid::from_account_view(bid_pda)?.bump();
+    let signer_seeds = [
+      Seed::from(Bid::SEED),
+      Seed::from(auction_pda.address().as_ref()),
+      Seed::from(bidder.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    let amount = tok_acct_amount(bid_ata_y)?;
+    log!("refund: {}", amount);
+    TransferIface {
+      from: bid_ata_y,
+      mint: mint_y,
+      to: bidder_ata_y,
+      authority: bid_pda,
+      amount,
+      decimals,
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    CloseAccountIface {
+      account: bid_ata_y,
+      authority: bid_pda,
+      destination: bidder,
+      token_program,
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    close_pda(bid_pda, bidder)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AuctionBidWithdraw<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AuctionBidWithdraw try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //u8 takes 1 byte
+    check_data_len(data, 1)?;
+
+    let [bidder, bidder_ata_y, bid_pda, bid_ata_y, mint_y, auction_pda, token_program, system_program, atoken_program] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(bidder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    writable(bidder_ata_y)?;
+    writable(bid_pda)?;
+    writable(bid_ata_y)?;
+
+    let bid = Bid::from_account_view(bid_pda)?;
+    if bid.auction().ne(auction_pda.address()) || bid.bidder().ne(bidder.address()) {
+      return Err(Ee::BidPDA.into());
+    }
+    if bid_ata_y.address()
+      != &derive_ata(bid_pda.address(), mint_y.address(), token_program.address())?
+    {
+      return Err(Ee::AtaOrOwner.into());
+    }
+    let decimals = data[0];
+    check_decimals_iface(mint_y, token_program, decimals)?;
+    Ok(Self {
+      bidder,
+      bidder_ata_y,
+      bid_pda,
+      bid_ata_y,
+      mint_y,
+      auction_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      decimals,
+    })
+  }
+}
@@ -0,0 +1,216 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_escrow_mints, check_mint_iface, check_rent_sysvar, check_sysprog, executable, get_time_i64,
+  instructions::check_signer, make_maker_index, net_received, none_zero_u64, parse_i64, parse_u64,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Auction, Config, Ee, MakerIndex,
+  TransferIface, ID, PROG_ADDR,
+};
+
+/// Make an English Auction: the maker locks amount_x X, and bids in Y from reserve_y are taken until end_time. id 0 takes the next id from the maker's MakerIndex. TransferHook mints are not supported
+pub struct AuctionMake<'a> {
+  pub maker: &'a AccountView, //signer
+  pub maker_ata_x: &'a AccountView,
+  pub auction_ata_x: &'a AccountView,
+  pub mint_x: &'a AccountView,
+  pub mint_y: &'a AccountView,
+  pub auction_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub decimal_x: u8,
+  pub amount_x: u64,
+  pub decimal_y: u8,
+  pub reserve_y: u64,
+  pub end_time: i64,
+  pub id: u64,
+}
+impl<'a> AuctionMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &51;
+
+  pub fn process(self) -> ProgramResult {
+    let AuctionMake {
+      maker,
+      maker_ata_x,
+      auction_ata_x,
+      mint_x,
+      mint_y,
+      auction_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      decimal_x,
+      amount_x,
+      decimal_y,
+      reserve_y,
+      end_time,
+      id,
+    } = self;
+    log!("AuctionMake process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    if end_time <= get_time_i64()? {
+      return Ee::AuctionEnded.e();
+    }
+    make_maker_index(maker, maker_index, rent_sysvar)?;
+    let index = MakerIndex::from_account_view(maker_index)?;
+    let id = index.allocate(id)?;
+    log!("allocated id: {}, open offers: {}", id, index.open_count());
+
+    let seed = [Auction::SEED, maker.address().as_array(), &id.to_le_bytes()];
+    let (expected_auction, bump) = Address::find_program_address(&seed[..], &ID.into());
+    if expected_auction.ne(auction_pda.address()) || auction_pda.lamports() > 0 {
+      return Ee::AuctionPDA.e();
+    }
+    log!("Make Auction PDA");
+    let id_bytes = &id.to_le_bytes();
+    let seeds = [
+      Seed::from(Auction::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: maker,
+      to: auction_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Auction::LEN)?,
+      space: Auction::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    if auction_ata_x.is_data_empty() {
+      log!("Make auction_ata_x");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: maker,
+        account: auction_ata_x,
+        wallet: auction_pda,
+        mint: mint_x,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("auction_ata_x has data");
+      check_ata_iface(auction_ata_x, auction_pda, mint_x, token_program)?;
+      rent_exempt_tokacct_iface(auction_ata_x, rent_sysvar, token_program)?;
+    }
+
+    let balc_before = tok_acct_amount(auction_ata_x)?;
+    TransferIface {
+      from: maker_ata_x,
+      mint: mint_x,
+      to: auction_ata_x,
+      authority: maker,
+      amount: amount_x,
+      decimals: decimal_x,
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke()?;
+
+    let auction = Auction::from_account_view(auction_pda)?;
+    auction.set_maker(maker.address());
+    auction.set_config(config_pda.address());
+    auction.set_mint_x(mint_x.address());
+    auction.set_mint_y(mint_y.address());
+    //the auction holds what it received, which can be less than amount_x after transfer fees
+    auction.set_amount_x(net_received(auction_ata_x, balc_before)?)?;
+    auction.set_reserve_y(reserve_y)?;
+    auction.set_end_time(end_time);
+    auction.set_id(id);
+    auction.set_decimal_x(decimal_x);
+    auction.set_decimal_y(decimal_y);
+    auction.set_bump(bump);
+    config.add_open_escrow()?;
+    log!("open escrows: {}", config.open_escrows());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AuctionMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AuctionMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //1+8+1+8+8+8: 2x u8 take 2, 3x u64 and 1x i64 take 32 bytes
+    check_data_len(data, 34)?;
+
+    let [maker, maker_ata_x, auction_ata_x, mint_x, mint_y, auction_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(maker_ata_x)?;
+    writable(auction_ata_x)?;
+    writable(auction_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+
+    let decimal_x = data[0];
+    let amount_x = parse_u64(&data[1..9])?;
+    let decimal_y = data[9];
+    let reserve_y = parse_u64(&data[10..18])?;
+    let end_time = parse_i64(&data[18..26])?;
+    let id = parse_u64(&data[26..34])?;
+    log!(
+      "amount_x: {}, reserve_y: {}, end_time: {}, id: {}",
+      amount_x,
+      reserve_y,
+      end_time,
+      id
+    );
+    none_zero_u64(amount_x)?;
+    none_zero_u64(reserve_y)?;
+
+    check_escrow_mints(mint_x, mint_y)?;
+    check_decimals_iface(mint_x, token_program, decimal_x)?;
+    check_decimals_iface(mint_y, token_program, decimal_y)?;
+    check_mint_iface(mint_x, token_program)?;
+    check_mint_iface(mint_y, token_program)?;
+    check_ata_iface(maker_ata_x, maker, mint_x, token_program)?;
+    ata_balc_iface(maker_ata_x, amount_x, token_program)?;
+    Ok(Self {
+      maker,
+      maker_ata_x,
+      auction_ata_x,
+      mint_x,
+      mint_y,
+      auction_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      decimal_x,
+      amount_x,
+      decimal_y,
+      reserve_y,
+      end_time,
+      id,
+    })
+  }
+}
@@ -0,0 +1,255 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog, close_pda,
+  derive_ata, executable, get_time_i64, instructions::check_signer, rent_exempt_tokacct_iface,
+  tok_acct_amount, writable, Auction, Bid, CloseAccountIface, Config, Ee, MakerIndex,
+  TransferIface,
+};
+
+/// Settle an Auction after end_time. Anyone can pay for it. The highest bid goes to the maker and X to the winner; with no bid, the winner is the maker and X goes back. bid_pda, bid_ata_y and maker_ata_y are not read when there is no bid
+pub struct AuctionSettle<'a> {
+  pub payer: &'a AccountView, //signer
+  pub maker: &'a AccountView,
+  pub maker_ata_y: &'a AccountView,
+  pub winner: &'a AccountView,
+  pub winner_ata_x: &'a AccountView,
+  pub auction_ata_x: &'a AccountView,
+  pub bid_pda: &'a AccountView,
+  pub bid_ata_y: &'a AccountView,
+  pub mint_x: &'a AccountView,
+  pub mint_y: &'a AccountView,
+  pub auction_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+}
+impl<'a> AuctionSettle<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &53;
+
+  pub fn process(self) -> ProgramResult {
+    let AuctionSettle {
+      payer,
+      maker,
+      maker_ata_y,
+      winner,
+      winner_ata_x,
+      auction_ata_x,
+      bid_pda,
+      bid_ata_y,
+      mint_x,
+      mint_y,
+      auction_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+    } = self;
+    log!("AuctionSettle process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    let auction = Auction::from_account_view(auction_pda)?;
+    if get_time_i64()? < auction.end_time() {
+      return Ee::AuctionNotEnded.e();
+    }
+    if maker.address().ne(auction.maker()) {
+      return Ee::OnlyMaker.e();
+    }
+    //only the Config that counted this auction at make gets it uncounted
+    if config_pda.address().ne(auction.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    if auction.mint_x().ne(mint_x.address()) {
+      return Ee::EscrowMintX.e();
+    }
+    if auction.mint_y().ne(mint_y.address()) {
+      return Ee::EscrowMintY.e();
+    }
+    let has_bid = auction.has_bid();
+    let expected_winner = if has_bid {
+      auction.high_bidder()
+    } else {
+      auction.maker()
+    };
+    if winner.address().ne(expected_winner) {
+      return Ee::AuctionBid.e();
+    }
+    //an empty decoy must not leave X stranded in the real Auction ATA
+    if auction_ata_x.address()
+      != &derive_ata(
+        auction_pda.address(),
+        mint_x.address(),
+        token_program.address(),
+      )?
+    {
+      return Ee::AtaOrOwner.e();
+    }
+
+    log!("Deliver X to the winner");
+    if winner_ata_x.is_data_empty() {
+      log!("Make winner_ata_x");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: payer,
+        account: winner_ata_x,
+        wallet: winner,
+        mint: mint_x,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(winner_ata_x, winner, mint_x, token_program)?;
+    }
+    rent_exempt_tokacct_iface(winner_ata_x, rent_sysvar, token_program)?;
+    let id_bytes = &auction.id().to_le_bytes();
+    let bump = auction.bump();
+    let signer_seeds = [
+      Seed::from(Auction::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    TransferIface {
+      from: auction_ata_x,
+      mint: mint_x,
+      to: winner_ata_x,
+      authority: auction_pda,
+      amount: tok_acct_amount(auction_ata_x)?,
+      decimals: auction.decimal_x(),
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    CloseAccountIface {
+      account: auction_ata_x,
+      authority: auction_pda,
+      destination: maker,
+      token_program,
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    if has_bid {
+      log!("Pay the highest bid to the maker");
+      let bid = Bid::from_account_view(bid_pda)?;
+      if bid.auction().ne(auction_pda.address()) || bid.bidder().ne(winner.address()) {
+        return Ee::BidPDA.e();
+      }
+      if bid_ata_y.address()
+        != &derive_ata(bid_pda.address(), mint_y.address(), token_program.address())?
+      {
+        return Ee::AtaOrOwner.e();
+      }
+      if maker_ata_y.is_data_empty() {
+        log!("Make maker_ata_y");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: payer,
+          account: maker_ata_y,
+          wallet: maker,
+          mint: mint_y,
+          system_program,
+          token_program,
+        }
+        .invoke()?;
+      } else {
+        check_ata_iface(maker_ata_y, maker, mint_y, token_program)?;
+      }
+      let bump = bid.bump();
+      let signer_seeds = [
+        Seed::from(Bid::SEED),
+        Seed::from(auction_pda.address().as_ref()),
+        Seed::from(winner.address().as_ref()),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+      TransferIface {
+        from: bid_ata_y,
+        mint: mint_y,
+        to: maker_ata_y,
+        authority: bid_pda,
+        amount: tok_acct_amount(bid_ata_y)?,
+        decimals: auction.decimal_y(),
+        token_program,
+        hook_accounts: &[],
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+      CloseAccountIface {
+        account: bid_ata_y,
+        authority: bid_pda,
+        destination: winner,
+        token_program,
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+      close_pda(bid_pda, winner)?;
+    }
+
+    config.sub_open_escrow();
+    MakerIndex::from_account_view(maker_index)?.close_offer();
+    log!("open escrows: {}", config.open_escrows());
+
+    log!("Close Auction PDA");
+    close_pda(auction_pda, maker)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AuctionSettle<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AuctionSettle try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [payer, maker, maker_ata_y, winner, winner_ata_x, auction_ata_x, bid_pda, bid_ata_y, mint_x, mint_y, auction_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(maker)?;
+    writable(winner_ata_x)?;
+    writable(auction_ata_x)?;
+    writable(auction_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+    if MakerIndex::from_account_view(maker_index)?.maker() != maker.address() {
+      return Err(Ee::MakerIndexPDA.into());
+    }
+    Ok(Self {
+      payer,
+      maker,
+      maker_ata_y,
+      winner,
+      winner_ata_x,
+      auction_ata_x,
+      bid_pda,
+      bid_ata_y,
+      mint_x,
+      mint_y,
+      auction_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+    })
+  }
+}
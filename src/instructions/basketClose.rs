@@ -0,0 +1,178 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog, close_pda,
+  derive_ata, executable, instructions::check_signer, rent_exempt_tokacct_iface, tok_acct_amount,
+  writable, Basket, CloseAccountIface, Config, Ee, MakerIndex, TransferIface,
+};
+
+/// Maker closes a Basket Offer, as a cancel before a take or a withdraw after it. Every Basket ATA is emptied into the maker ATA of its mint and closed
+pub struct BasketClose<'a> {
+  pub maker: &'a AccountView, //signer
+  pub basket_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub leg_accounts: &'a [AccountView], //per leg, X legs first: [mint, basket_ata, maker_ata]
+}
+impl<'a> BasketClose<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &50;
+
+  pub fn process(self) -> ProgramResult {
+    let BasketClose {
+      maker,
+      basket_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      leg_accounts,
+    } = self;
+    log!("BasketClose process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    let basket = Basket::from_account_view(basket_pda)?;
+    if maker.address().ne(basket.maker()) {
+      return Ee::OnlyMaker.e();
+    }
+    //only the Config that counted this offer at make gets it uncounted
+    if config_pda.address().ne(basket.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    let legs = Basket::legs(basket_pda)?;
+    if leg_accounts.len() != legs.len() * 3 {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let id_bytes = &basket.id().to_le_bytes();
+    let bump = basket.bump();
+    let signer_seeds = [
+      Seed::from(Basket::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    for (leg, accts) in legs.iter().zip(leg_accounts.chunks_exact(3)) {
+      let [mint, basket_ata, maker_ata] = accts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if leg.mint() != mint.address() {
+        return Ee::BasketLegs.e();
+      }
+      //an empty decoy must not leave tokens stranded in the real Basket ATA
+      if basket_ata.address()
+        != &derive_ata(
+          basket_pda.address(),
+          mint.address(),
+          token_program.address(),
+        )?
+      {
+        return Ee::AtaOrOwner.e();
+      }
+      if basket_ata.is_data_empty() {
+        continue;
+      }
+      let amount = tok_acct_amount(basket_ata)?;
+      log!("Basket ATA amount: {}", amount);
+      if amount > 0 {
+        if maker_ata.is_data_empty() {
+          log!("Make maker_ata");
+          pinocchio_associated_token_account::instructions::Create {
+            funding_account: maker,
+            account: maker_ata,
+            wallet: maker,
+            mint,
+            system_program,
+            token_program,
+          }
+          .invoke()?;
+        } else {
+          check_ata_iface(maker_ata, maker, mint, token_program)?;
+          rent_exempt_tokacct_iface(maker_ata, rent_sysvar, token_program)?;
+        }
+        TransferIface {
+          from: basket_ata,
+          mint,
+          to: maker_ata,
+          authority: basket_pda,
+          amount,
+          decimals: leg.decimals(),
+          token_program,
+          hook_accounts: &[],
+        }
+        .invoke_signed(core::slice::from_ref(&seed_signer))?;
+      }
+      CloseAccountIface {
+        account: basket_ata,
+        authority: basket_pda,
+        destination: maker,
+        token_program,
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    }
+
+    config.sub_open_escrow();
+    MakerIndex::from_account_view(maker_index)?.close_offer();
+    log!("open escrows: {}", config.open_escrows());
+
+    log!("Close Basket PDA");
+    close_pda(basket_pda, maker)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for BasketClose<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("BasketClose try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [maker, basket_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, leg_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(basket_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+    if MakerIndex::from_account_view(maker_index)?.maker() != maker.address() {
+      return Err(Ee::MakerIndexPDA.into());
+    }
+    for leg in leg_accounts.chunks(3) {
+      for acct in leg.iter().skip(1) {
+        writable(acct)?;
+      }
+    }
+    Ok(Self {
+      maker,
+      basket_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      leg_accounts,
+    })
+  }
+}
@@ -0,0 +1,239 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_rent_sysvar, check_sysprog, executable, instructions::check_signer,
+  make_maker_index, net_received, parse_u64, rent_exempt_tokacct_iface, tok_acct_amount, writable,
+  Basket, Config, Ee, MakerIndex, TransferIface, ID, PROG_ADDR,
+};
+
+/// Make a Basket Offer: up to MAX_BASKET_LEGS offered mints for up to MAX_BASKET_LEGS wanted mints, all distinct and under token_program. id 0 takes the next id from the maker's MakerIndex. TransferHook mints are not supported
+pub struct BasketMake<'a> {
+  pub maker: &'a AccountView, //signer
+  pub basket_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub leg_accounts: &'a [AccountView], //X legs: [mint, maker_ata, basket_ata], then Y legs: [mint, basket_ata]
+  pub leg_data: &'a [u8],              //per leg: amount u64, decimals u8
+  pub leg_count_x: u8,
+  pub leg_count_y: u8,
+  pub id: u64,
+}
+impl<'a> BasketMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &48;
+
+  pub fn process(self) -> ProgramResult {
+    let BasketMake {
+      maker,
+      basket_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      leg_accounts,
+      leg_data,
+      leg_count_x,
+      leg_count_y,
+      id,
+    } = self;
+    log!("BasketMake process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    make_maker_index(maker, maker_index, rent_sysvar)?;
+    let index = MakerIndex::from_account_view(maker_index)?;
+    let id = index.allocate(id)?;
+    log!("allocated id: {}, open offers: {}", id, index.open_count());
+
+    let seed = [Basket::SEED, maker.address().as_array(), &id.to_le_bytes()];
+    let (expected_basket, bump) = Address::find_program_address(&seed[..], &ID.into());
+    if expected_basket.ne(basket_pda.address()) || basket_pda.lamports() > 0 {
+      return Ee::BasketPDA.e();
+    }
+    log!("Make Basket PDA");
+    let space = Basket::space(leg_count_x, leg_count_y)?;
+    let id_bytes = &id.to_le_bytes();
+    let seeds = [
+      Seed::from(Basket::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: maker,
+      to: basket_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(space)?,
+      space: space as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+    Basket::init(
+      basket_pda,
+      maker.address(),
+      config_pda.address(),
+      id,
+      leg_count_x,
+      leg_count_y,
+      bump,
+    )?;
+    let legs = Basket::legs(basket_pda)?;
+
+    let count_x = leg_count_x as usize;
+    let (x_accounts, y_accounts) = leg_accounts.split_at(count_x * 3);
+    for i in 0..legs.len() {
+      let amount = parse_u64(&leg_data[i * 9..i * 9 + 8])?;
+      let decimals = leg_data[i * 9 + 8];
+      let (mint, basket_ata) = if i < count_x {
+        (&x_accounts[i * 3], &x_accounts[i * 3 + 2])
+      } else {
+        let j = i - count_x;
+        (&y_accounts[j * 2], &y_accounts[j * 2 + 1])
+      };
+      log!("leg {}: amount: {}, decimals: {}", i, amount, decimals);
+      if legs[..i].iter().any(|leg| leg.mint() == mint.address()) {
+        return Ee::BasketLegs.e();
+      }
+      check_decimals_iface(mint, token_program, decimals)?;
+      check_mint_iface(mint, token_program)?;
+      make_basket_ata(
+        maker,
+        basket_ata,
+        basket_pda,
+        mint,
+        system_program,
+        token_program,
+        rent_sysvar,
+      )?;
+
+      let leg = &mut legs[i];
+      leg.set_mint(mint.address());
+      leg.set_decimals(decimals);
+      if i < count_x {
+        let maker_ata = &x_accounts[i * 3 + 1];
+        check_ata_iface(maker_ata, maker, mint, token_program)?;
+        ata_balc_iface(maker_ata, amount, token_program)?;
+        let balc_before = tok_acct_amount(basket_ata)?;
+        TransferIface {
+          from: maker_ata,
+          mint,
+          to: basket_ata,
+          authority: maker,
+          amount,
+          decimals,
+          token_program,
+          hook_accounts: &[],
+        }
+        .invoke()?;
+        //the basket holds what it received, which can be less than amount after transfer fees
+        leg.set_amount(net_received(basket_ata, balc_before)?)?;
+      } else {
+        leg.set_amount(amount)?;
+      }
+    }
+    config.add_open_escrow()?;
+    log!("open escrows: {}", config.open_escrows());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for BasketMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("BasketMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [maker, basket_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, leg_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(basket_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+
+    //1+1+8: 2x u8 take 2, u64 takes 8, then 8+1 per leg
+    if data.len() < 10 {
+      return Err(ProgramError::InvalidInstructionData);
+    }
+    let leg_count_x = data[0];
+    let leg_count_y = data[1];
+    Basket::space(leg_count_x, leg_count_y)?;
+    let leg_count = leg_count_x as usize + leg_count_y as usize;
+    check_data_len(data, 10 + leg_count * 9)?;
+    let id = parse_u64(&data[2..10])?;
+    log!(
+      "leg_count_x: {}, leg_count_y: {}, id: {}",
+      leg_count_x,
+      leg_count_y,
+      id
+    );
+    if leg_accounts.len() != leg_count_x as usize * 3 + leg_count_y as usize * 2 {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    Ok(Self {
+      maker,
+      basket_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      leg_accounts,
+      leg_data: &data[10..],
+      leg_count_x,
+      leg_count_y,
+      id,
+    })
+  }
+}
+
+/// Make the Basket ATA of a leg, or verify one made beforehand
+pub fn make_basket_ata(
+  payer: &AccountView,
+  basket_ata: &AccountView,
+  basket_pda: &AccountView,
+  mint: &AccountView,
+  system_program: &AccountView,
+  token_program: &AccountView,
+  rent_sysvar: &AccountView,
+) -> ProgramResult {
+  writable(basket_ata)?;
+  if basket_ata.is_data_empty() {
+    log!("Make basket_ata");
+    pinocchio_associated_token_account::instructions::Create {
+      funding_account: payer,
+      account: basket_ata,
+      wallet: basket_pda,
+      mint,
+      system_program,
+      token_program,
+    }
+    .invoke()?;
+  } else {
+    log!("basket_ata has data");
+    check_ata_iface(basket_ata, basket_pda, mint, token_program)?;
+    rent_exempt_tokacct_iface(basket_ata, rent_sysvar, token_program)?;
+  }
+  Ok(())
+}
@@ -0,0 +1,175 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  executable, instructions::check_signer, net_received, rent_exempt_tokacct_iface, tok_acct_amount,
+  writable, Basket, Config, Ee, TransferIface,
+};
+
+/// Take a Basket Offer. The taker pays every Y leg into its Basket ATA and receives every X leg, all in one instruction
+pub struct BasketTake<'a> {
+  pub taker: &'a AccountView, //signer
+  pub basket_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub leg_accounts: &'a [AccountView], //per leg, X legs first: [mint, basket_ata, taker_ata]
+}
+impl<'a> BasketTake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &49;
+
+  pub fn process(self) -> ProgramResult {
+    let BasketTake {
+      taker,
+      basket_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      leg_accounts,
+    } = self;
+    log!("BasketTake process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    let basket = Basket::from_account_view(basket_pda)?;
+    if basket.taken() {
+      return Ee::BasketTaken.e();
+    }
+    let legs = Basket::legs(basket_pda)?;
+    if leg_accounts.len() != legs.len() * 3 {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let count_x = basket.leg_count_x() as usize;
+    for (leg, accts) in legs.iter().zip(leg_accounts.chunks_exact(3)) {
+      let [mint, basket_ata, _taker_ata] = accts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if leg.mint() != mint.address() {
+        return Ee::BasketLegs.e();
+      }
+      check_ata_iface(basket_ata, basket_pda, mint, token_program)?;
+    }
+
+    log!("Pay Y legs");
+    for (leg, accts) in legs[count_x..]
+      .iter_mut()
+      .zip(leg_accounts[count_x * 3..].chunks_exact(3))
+    {
+      let [mint, basket_ata, taker_ata] = accts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      check_ata_iface(taker_ata, taker, mint, token_program)?;
+      if tok_acct_amount(taker_ata)? < leg.amount() {
+        return Ee::TakerInsuffTokenY.e();
+      }
+      let balc_before = tok_acct_amount(basket_ata)?;
+      TransferIface {
+        from: taker_ata,
+        mint,
+        to: basket_ata,
+        authority: taker,
+        amount: leg.amount(),
+        decimals: leg.decimals(),
+        token_program,
+        hook_accounts: &[],
+      }
+      .invoke()?;
+      //the maker withdraws what the basket received, which can be less after transfer fees
+      leg.set_amount(net_received(basket_ata, balc_before)?)?;
+    }
+
+    log!("Release X legs");
+    let id_bytes = &basket.id().to_le_bytes();
+    let bump = basket.bump();
+    let signer_seeds = [
+      Seed::from(Basket::SEED),
+      Seed::from(basket.maker().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    for (leg, accts) in legs[..count_x].iter().zip(leg_accounts.chunks_exact(3)) {
+      let [mint, basket_ata, taker_ata] = accts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if tok_acct_amount(basket_ata)? < leg.amount() {
+        return Ee::EscrowInsuffTokenX.e();
+      }
+      if taker_ata.is_data_empty() {
+        log!("Make taker_ata");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: taker,
+          account: taker_ata,
+          wallet: taker,
+          mint,
+          system_program,
+          token_program,
+        }
+        .invoke()?;
+      } else {
+        check_ata_iface(taker_ata, taker, mint, token_program)?;
+      }
+      rent_exempt_tokacct_iface(taker_ata, rent_sysvar, token_program)?;
+      TransferIface {
+        from: basket_ata,
+        mint,
+        to: taker_ata,
+        authority: basket_pda,
+        amount: leg.amount(),
+        decimals: leg.decimals(),
+        token_program,
+        hook_accounts: &[],
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    }
+    basket.set_taken(true);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for BasketTake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("BasketTake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [taker, basket_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, leg_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(taker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(basket_pda)?;
+    for leg in leg_accounts.chunks(3) {
+      for acct in leg.iter().skip(1) {
+        writable(acct)?;
+      }
+    }
+    Ok(Self {
+      taker,
+      basket_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      leg_accounts,
+    })
+  }
+}
@@ -1,6 +1,6 @@
 use crate::{
-  check_data_len, check_pda, instructions::check_signer, parse_u32, parse_u64, writable, Config2,
-  Ee,
+  check_data_len, check_pda, instructions::check_signer, parse_u32, parse_u64, writable, Config,
+  Config2, Ee, Multisig, Timelock,
 };
 use core::convert::TryFrom;
 use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
@@ -104,6 +104,15 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Config2Update<'a> {
     let u64s = [u64a, u64b, u64c, u64d];*/
     log!("new_u64: {}", new_u64);
 
+    // the admin cannot write a config governed by its Timelock or Multisig
+    let config: &Config = Config::from_account_view(config_pda)?;
+    if Timelock::governs(config_pda, config)? {
+      return Err(Ee::Timelocked.into());
+    }
+    if Multisig::governs(config_pda, config)? {
+      return Err(Ee::MultisigGoverned.into());
+    }
+
     config_pda.check_borrow_mut()?;
     let config2: &mut Config2 = Config2::from_account_view(&config_pda)?;
 
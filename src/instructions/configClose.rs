@@ -2,9 +2,12 @@ use core::convert::TryFrom;
 use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
-use crate::{check_data_len, check_pda, close_pda, instructions::check_signer, writable, Config};
+use crate::{
+  check_data_len, check_pda, close_pda, derive_ata, instructions::check_signer, tok_acct_amount,
+  u8_to_bool, writable, Config, Ee, Multisig, Timelock,
+};
 
-/// Close PDA
+/// Close PDA. Refused while the vault holds tokens or escrows are open, unless the prog_owner forces it
 pub struct CloseConfigPda<'a> {
   pub authority: &'a AccountView,
   pub config_pda: &'a AccountView,
@@ -31,13 +34,15 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CloseConfigPda<'a> {
     log!("CloseConfigPda try_from");
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
-    check_data_len(data, 0)?;
+    //1: force flag as u8
+    check_data_len(data, 1)?;
 
-    let [authority, config_pda, dest] = accounts else {
+    let [authority, config_pda, dest, mint_atas @ ..] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(authority)?;
     writable(config_pda)?;
+    writable(dest)?;
     check_pda(config_pda)?;
     log!("TryFrom 1");
 
@@ -48,6 +53,26 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CloseConfigPda<'a> {
     if config.admin().ne(authority.address()) && config.prog_owner().ne(authority.address()) {
       return Err(ProgramError::IncorrectAuthority);
     }
+    // closing goes through the Timelock queue
+    if Timelock::governs(config_pda, config)? {
+      return Err(Ee::Timelocked.into());
+    }
+    // a Multisig owned config hands prog_owner back by proposal before closing
+    if Multisig::governs(config_pda, config)? {
+      return Err(Ee::MultisigGoverned.into());
+    }
+    if config.prog_owner().ne(dest.address()) {
+      return Err(Ee::CloseDest.into());
+    }
+    let force = u8_to_bool(data[0])?;
+    log!("force: {}", force as u8);
+    if force {
+      if config.prog_owner().ne(authority.address()) {
+        return Err(Ee::OnlyProgOwner.into());
+      }
+    } else {
+      check_config_empty(config, mint_atas)?;
+    }
     Ok(Self {
       authority,
       config_pda,
@@ -55,3 +80,37 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CloseConfigPda<'a> {
     })
   }
 }
+
+/// No open escrows, and every vault ATA of the config mints is empty. mint_atas are [mint, vault_ata] pairs in config mint order
+pub fn check_config_empty(config: &Config, mint_atas: &[AccountView]) -> ProgramResult {
+  log!("open escrows: {}", config.open_escrows());
+  if config.open_escrows() > 0 {
+    return Ee::OpenEscrows.e();
+  }
+  if mint_atas.len() != 8 {
+    return Err(ProgramError::NotEnoughAccountKeys);
+  }
+  for (pair, config_mint) in mint_atas.chunks_exact(2).zip(config.mints()) {
+    let [mint, vault_ata] = pair else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if mint.address() != config_mint {
+      return Ee::MintNotAccepted.e();
+    }
+    let token_program = if mint.owned_by(&pinocchio_token::ID) {
+      pinocchio_token::ID
+    } else if mint.owned_by(&pinocchio_token_2022::ID) {
+      pinocchio_token_2022::ID
+    } else {
+      log!("mint not made. No vault ATA");
+      continue;
+    };
+    if vault_ata.address() != &derive_ata(config.vault(), mint.address(), &token_program)? {
+      return Ee::VaultAta.e();
+    }
+    if vault_ata.data_len() > 0 && tok_acct_amount(vault_ata)? > 0 {
+      return Ee::VaultAtaNotEmpty.e();
+    }
+  }
+  Ok(())
+}
@@ -97,6 +97,7 @@ impl<'a> InitConfig<'a> {
     config.set_status(status);
     config.set_vault_bump(vault_bump);
     config.set_bump(bump);
+    config.set_creator(prog_owner);
     Ok(())
   }
 }
@@ -0,0 +1,96 @@
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda1, get_rent_exempt,
+  instructions::check_signer, writable, Config, Config2, Ee,
+};
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+/// Grow a Config or Config2 PDA made before open_escrows and creator to the current layout. Config2's own fields move behind the new Config fields, and creator is set from the address that seeds the Config PDA
+pub struct ConfigMigrate<'a> {
+  pub payer: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub creator: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub new_len: usize,
+}
+impl<'a> ConfigMigrate<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &69;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigMigrate {
+      payer,
+      config_pda,
+      creator,
+      system_program: _,
+      rent_sysvar,
+      new_len,
+    } = self;
+    log!("ConfigMigrate process()");
+    let old_len = config_pda.data_len();
+    config_pda.resize(new_len)?;
+
+    let min_lamport = get_rent_exempt(config_pda, rent_sysvar, new_len)?;
+    let prev_lamport = config_pda.lamports();
+    if min_lamport > prev_lamport {
+      log!("deposit lamports");
+      SystemTransfer {
+        from: payer,
+        to: config_pda,
+        lamports: min_lamport - prev_lamport,
+      }
+      .invoke()?;
+    }
+
+    if old_len == Config2::LEGACY_LEN {
+      log!("move Config2 fields");
+      let mut data = config_pda.try_borrow_mut()?;
+      data.copy_within(Config::LEGACY_LEN..old_len, Config::INIT_LEN);
+      data[Config::LEGACY_LEN..Config::INIT_LEN].fill(0);
+    }
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.set_creator(creator.address());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigMigrate<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigMigrate try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [payer, config_pda, creator, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    writable(payer)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    let (expected_config_pda, _bump) = derive_pda1(creator.address(), Config::SEED)?;
+    if expected_config_pda != *config_pda.address() {
+      return Err(Ee::ConfigPDA.into());
+    }
+    let new_len = match config_pda.data_len() {
+      Config::LEGACY_LEN => Config::INIT_LEN,
+      Config2::LEGACY_LEN => Config2::INIT_LEN,
+      _ => return Err(Ee::ConfigDataLengh.into()),
+    };
+    log!("new_len: {}", new_len);
+    Ok(Self {
+      payer,
+      config_pda,
+      creator,
+      system_program,
+      rent_sysvar,
+      new_len,
+    })
+  }
+}
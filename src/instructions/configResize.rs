@@ -1,6 +1,6 @@
 use crate::{
   check_data_len, check_pda, check_rent_sysvar, check_sysprog, get_rent_exempt,
-  instructions::check_signer, none_zero_u64, parse_u64, writable, Config,
+  instructions::check_signer, none_zero_u64, parse_u64, writable, Config, Ee, Multisig, Timelock,
 };
 use core::convert::TryFrom;
 use pinocchio::{error::ProgramError, AccountView, ProgramResult};
@@ -76,6 +76,13 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigResize<'a> {
     if config.admin().ne(authority.address()) && config.prog_owner().ne(authority.address()) {
       return Err(ProgramError::IncorrectAuthority);
     }
+    // the admin cannot resize a config governed by its Timelock or Multisig
+    if Timelock::governs(config_pda, config)? {
+      return Err(Ee::Timelocked.into());
+    }
+    if Multisig::governs(config_pda, config)? {
+      return Err(Ee::MultisigGoverned.into());
+    }
     let new_len = parse_u64(&data[0..8])?;
     //let bump = config.bump(); //data[9];
     log!("new_len: {}", new_len);
@@ -4,7 +4,7 @@ use pinocchio_log::log;
 
 use crate::{
   check_data_len, check_pda, get_time, instructions::check_signer, parse_u32, parse_u64, writable,
-  Config, Ee,
+  Config, Ee, Multisig, Timelock,
 };
 
 /// Update Config PDA
@@ -131,6 +131,14 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigUpdate<'a> {
     if config.admin().ne(signer.address()) && config.prog_owner().ne(signer.address()) {
       return Err(ProgramError::IncorrectAuthority);
     }
+    // fee and admin changes go through the Timelock queue
+    if Timelock::governs(config_pda, config)? {
+      return Err(Ee::Timelocked.into());
+    }
+    // and through proposals when a Multisig owns the config
+    if Multisig::governs(config_pda, config)? {
+      return Err(Ee::MultisigGoverned.into());
+    }
     // cannot use self in "0 => Self.process(),
     Ok(Self {
       signer,
@@ -0,0 +1,41 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{close_pda, instructions::check_signer, writable, Ee, VaultDelegate};
+
+/// User revokes a vault delegate and gets its rent back
+pub struct DelegateRevoke<'a> {
+  pub user: &'a AccountView, //signer
+  pub delegate_pda: &'a AccountView,
+}
+impl<'a> DelegateRevoke<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &33;
+
+  pub fn process(self) -> ProgramResult {
+    let DelegateRevoke { user, delegate_pda } = self;
+    log!("DelegateRevoke process()");
+    close_pda(delegate_pda, user)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DelegateRevoke<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DelegateRevoke try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [user, delegate_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(delegate_pda)?;
+
+    let state = VaultDelegate::from_account_view(delegate_pda)?;
+    if state.user() != user.address() {
+      return Err(Ee::DelegatePDA.into());
+    }
+    Ok(Self { user, delegate_pda })
+  }
+}
@@ -0,0 +1,114 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_rent_sysvar, check_sysprog, derive_pda2, get_time,
+  instructions::check_signer, none_zero_u32, none_zero_u64, parse_u32, parse_u64, to32bytes,
+  writable, Ee, VaultDelegate, PROG_ADDR,
+};
+
+/// User registers or replaces a delegate allowed to withdraw up to cap per period from the user's vault. Zero mint for SOL
+pub struct DelegateSet<'a> {
+  pub user: &'a AccountView, //signer
+  pub delegate_pda: &'a AccountView,
+  pub delegate: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub mint: &'a [u8; 32],
+  pub cap: u64,
+  pub period: u32,
+}
+impl<'a> DelegateSet<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &32;
+
+  pub fn process(self) -> ProgramResult {
+    let DelegateSet {
+      user,
+      delegate_pda,
+      delegate,
+      system_program: _,
+      rent_sysvar,
+      mint,
+      cap,
+      period,
+    } = self;
+    log!("DelegateSet process()");
+    let (expected_pda, bump) = derive_pda2(user.address(), mint, VaultDelegate::SEED)?;
+    if delegate_pda.address() != &expected_pda {
+      return Ee::DelegatePDA.e();
+    }
+    if delegate_pda.lamports() == 0 {
+      log!("Make VaultDelegate");
+      let signer_seeds = [
+        Seed::from(VaultDelegate::SEED),
+        Seed::from(user.address().as_ref()),
+        Seed::from(mint.as_ref()),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+
+      let rent = Rent::from_account_view(rent_sysvar)?;
+      pinocchio_system::instructions::CreateAccount {
+        from: user,
+        to: delegate_pda,
+        lamports: rent.try_minimum_balance(VaultDelegate::LEN)?,
+        space: VaultDelegate::LEN as u64,
+        owner: &PROG_ADDR,
+      }
+      .invoke_signed(&[seed_signer])?;
+    }
+    let state = VaultDelegate::from_account_view(delegate_pda)?;
+    state.set_user(user.address());
+    state.set_delegate(delegate.address());
+    state.set_mint(&Address::new_from_array(*mint));
+    state.set_cap(cap);
+    state.set_period(period);
+    state.set_period_start(get_time()?);
+    state.set_spent(0);
+    state.set_bump(bump);
+    log!("delegate cap: {} per {} seconds", cap, period);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DelegateSet<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DelegateSet try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [user, delegate_pda, delegate, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(delegate_pda)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    //32+8+4: mint takes 32, u64 takes 8, u32 takes 4 bytes
+    check_data_len(data, 44)?;
+    let mint = to32bytes(&data[0..32])?;
+    let cap = parse_u64(&data[32..40])?;
+    let period = parse_u32(&data[40..44])?;
+    none_zero_u64(cap)?;
+    none_zero_u32(period)?;
+
+    Ok(Self {
+      user,
+      delegate_pda,
+      delegate,
+      system_program,
+      rent_sysvar,
+      mint,
+      cap,
+      period,
+    })
+  }
+}
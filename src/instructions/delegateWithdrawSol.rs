@@ -0,0 +1,115 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_rent_sysvar, check_vault_lock, derive_pda1, get_rent_exempt, get_time,
+  instructions::check_signer, migrate_legacy_vault, none_zero_u64, parse_u64, sync_vault_yield,
+  writable, Ee, Vault, VaultDelegate, VaultLock, VAULT_SEED, VAULT_SIZE,
+};
+
+/// Delegate withdraws SOL from a user's vault within the delegate's cap per period. Locked SOL stays in the vault
+pub struct DelegateWithdrawSol<'a> {
+  pub delegate: &'a AccountView, //signer
+  pub user: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub delegate_pda: &'a AccountView,
+  pub to: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub lock: &'a AccountView,
+  pub yield_accounts: &'a [AccountView], //optional YieldPool and Config PDA
+  pub amount: u64,
+  pub bump: u8,
+}
+impl<'a> DelegateWithdrawSol<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &34;
+
+  pub fn process(self) -> ProgramResult {
+    let DelegateWithdrawSol {
+      delegate: _,
+      user,
+      vault,
+      delegate_pda,
+      to,
+      rent_sysvar,
+      lock,
+      yield_accounts,
+      amount,
+      bump,
+    } = self;
+    log!("DelegateWithdrawSol process()");
+    VaultDelegate::from_account_view(delegate_pda)?.spend(amount, get_time()?)?;
+
+    migrate_legacy_vault(vault, rent_sysvar, None, bump)?;
+    let vault_min_balc = get_rent_exempt(vault, rent_sysvar, VAULT_SIZE)?;
+    let available = vault.lamports().saturating_sub(vault_min_balc);
+    if amount > available {
+      return Ee::PdaToBeBelowRentExempt.e();
+    }
+    check_vault_lock(
+      user.address(),
+      &VaultLock::SOL_MINT,
+      lock,
+      available - amount,
+    )?;
+    let state = Vault::from_account_view(vault)?;
+    sync_vault_yield(state, yield_accounts)?;
+
+    vault.set_lamports(vault.lamports() - amount);
+    let sum_lam = to
+      .lamports()
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    to.set_lamports(sum_lam);
+    state.set_principal(state.principal().saturating_sub(amount));
+    log!("{} lamports withdrawn by delegate", amount);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DelegateWithdrawSol<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DelegateWithdrawSol try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [delegate, user, vault, delegate_pda, to, rent_sysvar, lock, yield_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(delegate)?;
+    writable(vault)?;
+    writable(delegate_pda)?;
+    writable(to)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    let amount = parse_u64(data)?;
+    none_zero_u64(amount)?;
+
+    let state = VaultDelegate::from_account_view(delegate_pda)?;
+    if state.user() != user.address() || state.delegate() != delegate.address() {
+      return Err(Ee::DelegatePDA.into());
+    }
+    if state.mint().as_array() != &[0u8; 32] {
+      return Err(Ee::DelegatePDA.into());
+    }
+    let (expected_vault, bump) = derive_pda1(user.address(), VAULT_SEED)?;
+    if vault.address() != &expected_vault {
+      return Err(Ee::VaultPDA.into());
+    }
+    Ok(Self {
+      delegate,
+      user,
+      vault,
+      delegate_pda,
+      to,
+      rent_sysvar,
+      lock,
+      yield_accounts,
+      amount,
+      bump,
+    })
+  }
+}
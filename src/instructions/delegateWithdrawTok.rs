@@ -0,0 +1,147 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_data_len, check_decimals_iface, check_mint_iface,
+  check_pda, check_vault_lock, derive_pda1, executable, get_time, instructions::check_signer,
+  none_zero_u64, parse_u64, tok_acct_amount, writable, Config, Ee, TransferIface, VaultDelegate,
+  VAULT_SEED,
+};
+
+/// Delegate withdraws tokens from a user's vault within the delegate's cap per period. Locked tokens stay in the vault. Serves both Token and Token2022 via token_program
+pub struct DelegateWithdrawTok<'a> {
+  pub delegate: &'a AccountView, //signer
+  pub user: &'a AccountView,
+  pub from_ata: &'a AccountView,
+  pub to_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub delegate_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub lock: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub vault_bump: u8,
+  pub decimals: u8,
+  pub amount: u64,
+}
+impl<'a> DelegateWithdrawTok<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &35;
+
+  pub fn process(self) -> ProgramResult {
+    let DelegateWithdrawTok {
+      delegate: _,
+      user,
+      from_ata,
+      to_ata,
+      vault,
+      delegate_pda,
+      mint,
+      config_pda,
+      lock: _,
+      token_program,
+      hook_accounts,
+      vault_bump,
+      decimals,
+      amount,
+    } = self;
+    log!("DelegateWithdrawTok process()");
+    VaultDelegate::from_account_view(delegate_pda)?.spend(amount, get_time()?)?;
+
+    let signer_seeds = [
+      Seed::from(VAULT_SEED),
+      Seed::from(user.address().as_ref()),
+      Seed::from(core::slice::from_ref(&vault_bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    TransferIface {
+      from: from_ata,
+      mint,
+      to: to_ata,
+      authority: vault,
+      amount,
+      decimals,
+      token_program,
+      hook_accounts,
+    }
+    .invoke_signed(&[seed_signer])?;
+    Config::from_account_view(config_pda)?.sub_token_balance(mint.address(), amount);
+    log!("{} tokens withdrawn by delegate", amount);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DelegateWithdrawTok<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DelegateWithdrawTok try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [delegate, user, from_ata, to_ata, vault, delegate_pda, mint, config_pda, lock, token_program, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(delegate)?;
+    executable(token_program)?;
+    writable(from_ata)?;
+    check_ata_iface(from_ata, vault, mint, token_program)?;
+    writable(to_ata)?;
+    writable(delegate_pda)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    Config::from_account_view(config_pda)?.check_address(config_pda)?;
+
+    //1+8: u8 takes 1, u64 takes 8 bytes
+    check_data_len(data, 9)?;
+    let decimals = data[0];
+    let amount = parse_u64(&data[1..])?;
+    log!("decimals: {}, amount: {}", decimals, amount);
+    none_zero_u64(amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
+
+    let state = VaultDelegate::from_account_view(delegate_pda)?;
+    if state.user() != user.address() || state.delegate() != delegate.address() {
+      return Err(Ee::DelegatePDA.into());
+    }
+    if state.mint() != mint.address() {
+      return Err(Ee::DelegatePDA.into());
+    }
+    let (expected_vault, vault_bump) = derive_pda1(user.address(), VAULT_SEED)?;
+    if vault.address() != &expected_vault {
+      return Err(Ee::VaultPDA.into());
+    }
+    check_vault_lock(
+      user.address(),
+      mint.address().as_array(),
+      lock,
+      tok_acct_amount(from_ata)? - amount,
+    )?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+
+    Ok(Self {
+      delegate,
+      user,
+      from_ata,
+      to_ata,
+      vault,
+      delegate_pda,
+      mint,
+      config_pda,
+      lock,
+      token_program,
+      hook_accounts,
+      vault_bump,
+      decimals,
+      amount,
+    })
+  }
+}
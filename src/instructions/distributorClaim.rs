@@ -0,0 +1,178 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_rent_sysvar, check_sysprog, check_vault, claim_leaf,
+  executable, get_time_i64, instructions::check_signer, none_zero_u64, parse_u32, parse_u64,
+  rent_exempt_tokacct_iface, verify_proof, writable, Config, Distributor, Ee, TransferIface,
+  VAULT_SEED,
+};
+
+/// Claimant proves the (index, claimant, amount) leaf under a Distributor root, and gets amount from the vault ATA. Each index is claimed once, before the deadline
+pub struct DistributorClaim<'a> {
+  pub claimant: &'a AccountView, //signer
+  pub claimant_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub distributor_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub index: u32,
+  pub amount: u64,
+  pub proof: &'a [u8],
+}
+impl<'a> DistributorClaim<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &65;
+
+  pub fn process(self) -> ProgramResult {
+    let DistributorClaim {
+      claimant,
+      claimant_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+      index,
+      amount,
+      proof,
+    } = self;
+    log!("DistributorClaim process()");
+    let distributor = Distributor::from_account_view(distributor_pda)?;
+    if distributor.mint().ne(mint.address()) {
+      return Ee::DistributorPDA.e();
+    }
+    //only the Config that funded the Distributor pays it from its vault
+    if config_pda.address().ne(distributor.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    if get_time_i64()? >= distributor.deadline() {
+      return Ee::DistributorDeadline.e();
+    }
+    let leaf = claim_leaf(index, claimant.address(), amount);
+    if !verify_proof(proof, distributor.root(), leaf) {
+      return Ee::DistributorProof.e();
+    }
+    distributor.claim(index, amount)?;
+    log!(
+      "claimed: {} of {}",
+      distributor.claimed(),
+      distributor.total()
+    );
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    check_vault(vault, config.vault())?;
+    check_ata_iface(vault_ata, vault, mint, token_program)?;
+
+    if claimant_ata.is_data_empty() {
+      log!("Make claimant_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: claimant,
+        account: claimant_ata,
+        wallet: claimant,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(claimant_ata, claimant, mint, token_program)?;
+      rent_exempt_tokacct_iface(claimant_ata, rent_sysvar, token_program)?;
+    }
+
+    let vault_bump = config.vault_bump();
+    let signer_seeds = [
+      Seed::from(VAULT_SEED),
+      Seed::from(config.creator().as_ref()),
+      Seed::from(core::slice::from_ref(&vault_bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    TransferIface {
+      from: vault_ata,
+      mint,
+      to: claimant_ata,
+      authority: vault,
+      amount,
+      decimals: distributor.decimals(),
+      token_program,
+      hook_accounts,
+    }
+    .invoke_signed(&[seed_signer])?;
+    config.sub_token_balance(mint.address(), amount);
+    config.release(mint.address(), amount);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DistributorClaim<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DistributorClaim try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [claimant, claimant_ata, vault_ata, vault, mint, distributor_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(claimant)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(claimant)?;
+    writable(claimant_ata)?;
+    writable(vault_ata)?;
+    writable(distributor_pda)?;
+    writable(config_pda)?;
+
+    //4+8: u32 takes 4, u64 takes 8 bytes, then 32 bytes per proof hash
+    if data.len() < 12 || !(data.len() - 12).is_multiple_of(32) {
+      return Err(Ee::InputDataLen.into());
+    }
+    let index = parse_u32(&data[0..4])?;
+    let amount = parse_u64(&data[4..12])?;
+    let proof = &data[12..];
+    log!(
+      "index: {}, amount: {}, proof hashes: {}",
+      index,
+      amount,
+      proof.len() / 32
+    );
+    none_zero_u64(amount)?;
+    Ok(Self {
+      claimant,
+      claimant_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+      index,
+      amount,
+      proof,
+    })
+  }
+}
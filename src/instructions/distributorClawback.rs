@@ -0,0 +1,153 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  check_vault, close_pda, executable, get_time_i64, instructions::check_signer,
+  rent_exempt_tokacct_iface, writable, Config, Distributor, Ee, TransferIface, VAULT_SEED,
+};
+
+/// Funder claws back what a Distributor has not paid out after its deadline, from the vault ATA to the funder ATA, and closes the Distributor PDA
+pub struct DistributorClawback<'a> {
+  pub funder: &'a AccountView, //signer
+  pub funder_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub distributor_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> DistributorClawback<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &66;
+
+  pub fn process(self) -> ProgramResult {
+    let DistributorClawback {
+      funder,
+      funder_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("DistributorClawback process()");
+    let distributor = Distributor::from_account_view(distributor_pda)?;
+    if distributor.funder().ne(funder.address()) {
+      return Ee::OnlyAdmin.e();
+    }
+    if distributor.mint().ne(mint.address()) {
+      return Ee::DistributorPDA.e();
+    }
+    //only the Config that funded the Distributor pays it from its vault
+    if config_pda.address().ne(distributor.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    if get_time_i64()? < distributor.deadline() {
+      return Ee::DistributorDeadline.e();
+    }
+    let amount = distributor.total().saturating_sub(distributor.claimed());
+    log!("unclaimed: {}", amount);
+
+    if amount > 0 {
+      let config: &mut Config = Config::from_account_view(config_pda)?;
+      config.check_address(config_pda)?;
+      check_vault(vault, config.vault())?;
+      check_ata_iface(vault_ata, vault, mint, token_program)?;
+
+      if funder_ata.is_data_empty() {
+        log!("Make funder_ata");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: funder,
+          account: funder_ata,
+          wallet: funder,
+          mint,
+          system_program,
+          token_program,
+        }
+        .invoke()?;
+      } else {
+        check_ata_iface(funder_ata, funder, mint, token_program)?;
+        rent_exempt_tokacct_iface(funder_ata, rent_sysvar, token_program)?;
+      }
+
+      let vault_bump = config.vault_bump();
+      let signer_seeds = [
+        Seed::from(VAULT_SEED),
+        Seed::from(config.creator().as_ref()),
+        Seed::from(core::slice::from_ref(&vault_bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+      TransferIface {
+        from: vault_ata,
+        mint,
+        to: funder_ata,
+        authority: vault,
+        amount,
+        decimals: distributor.decimals(),
+        token_program,
+        hook_accounts,
+      }
+      .invoke_signed(&[seed_signer])?;
+      config.sub_token_balance(mint.address(), amount);
+      config.release(mint.address(), amount);
+    }
+    log!("Close Distributor PDA");
+    close_pda(distributor_pda, funder)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DistributorClawback<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DistributorClawback try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [funder, funder_ata, vault_ata, vault, mint, distributor_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(funder)?;
+    writable(funder_ata)?;
+    writable(vault_ata)?;
+    writable(distributor_pda)?;
+    writable(config_pda)?;
+    Ok(Self {
+      funder,
+      funder_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
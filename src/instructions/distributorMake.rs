@@ -0,0 +1,214 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_decimals_iface, check_mint_iface,
+  check_rent_sysvar, check_sysprog, check_vault, derive_pda2, executable, get_time_i64,
+  instructions::check_signer, net_received, none_zero_u64, parse_i64, parse_u32, parse_u64,
+  rent_exempt_tokacct_iface, to32bytes, tok_acct_amount, writable, Config, Distributor, Ee,
+  TransferIface, PROG_ADDR,
+};
+
+/// Config admin or prog_owner funds a merkle airdrop: total moves from the funder ATA into the vault ATA, claimable by the leaves under root until deadline. What the vault received stays committed until claimed or clawed back
+pub struct DistributorMake<'a> {
+  pub funder: &'a AccountView, //signer
+  pub funder_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub distributor_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub decimals: u8,
+  pub id: u64,
+  pub total: u64,
+  pub deadline: i64,
+  pub count: u32,
+  pub root: [u8; 32],
+}
+impl<'a> DistributorMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &64;
+
+  pub fn process(self) -> ProgramResult {
+    let DistributorMake {
+      funder,
+      funder_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+      decimals,
+      id,
+      total,
+      deadline,
+      count,
+      root,
+    } = self;
+    log!("DistributorMake process()");
+    let id_bytes = &id.to_le_bytes();
+    let (expected_pda, bump) = derive_pda2(funder.address(), id_bytes, Distributor::SEED)?;
+    if distributor_pda.address() != &expected_pda || distributor_pda.lamports() > 0 {
+      return Ee::DistributorPDA.e();
+    }
+    log!("Make Distributor PDA");
+    let signer_seeds = [
+      Seed::from(Distributor::SEED),
+      Seed::from(funder.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: funder,
+      to: distributor_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Distributor::LEN)?,
+      space: Distributor::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: funder,
+        account: vault_ata,
+        wallet: vault,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(vault_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(vault_ata, rent_sysvar, token_program)?;
+    }
+    let balc_before = tok_acct_amount(vault_ata)?;
+    TransferIface {
+      from: funder_ata,
+      mint,
+      to: vault_ata,
+      authority: funder,
+      amount: total,
+      decimals,
+      token_program,
+      hook_accounts,
+    }
+    .invoke()?;
+    //claims are paid from what the vault received, which can be less than total after transfer fees
+    let received = net_received(vault_ata, balc_before)?;
+    log!("received: {}", received);
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.add_token_balance(mint.address(), received)?;
+    config.commit(mint.address(), received, tok_acct_amount(vault_ata)?)?;
+
+    let distributor = Distributor::from_account_view(distributor_pda)?;
+    distributor.set_funder(funder.address());
+    distributor.set_config(config_pda.address());
+    distributor.set_mint(mint.address());
+    distributor.set_root(root);
+    distributor.set_total(received)?;
+    distributor.set_deadline(deadline);
+    distributor.set_id(id);
+    distributor.set_count(count)?;
+    distributor.set_decimals(decimals);
+    distributor.set_bump(bump);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DistributorMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("DistributorMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [funder, funder_ata, vault_ata, vault, mint, distributor_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(funder)?;
+    writable(funder_ata)?;
+    writable(vault_ata)?;
+    writable(distributor_pda)?;
+    writable(config_pda)?;
+
+    //1+8+8+8+4+32: u8 takes 1, u64, u64 and i64 take 24, u32 takes 4, the root takes 32 bytes
+    if data.len() != 61 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let decimals = data[0];
+    let id = parse_u64(&data[1..9])?;
+    let total = parse_u64(&data[9..17])?;
+    let deadline = parse_i64(&data[17..25])?;
+    let count = parse_u32(&data[25..29])?;
+    let root = *to32bytes(&data[29..61])?;
+    log!(
+      "id: {}, total: {}, deadline: {}, count: {}",
+      id,
+      total,
+      deadline,
+      count
+    );
+    none_zero_u64(total)?;
+    if deadline <= get_time_i64()? {
+      return Err(Ee::DistributorDeadline.into());
+    }
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    if config.admin().ne(funder.address()) && config.prog_owner().ne(funder.address()) {
+      return Err(Ee::OnlyAdmin.into());
+    }
+    if !config.mints().contains(&mint.address()) {
+      return Err(Ee::MintNotAccepted.into());
+    }
+    check_vault(vault, config.vault())?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    check_ata_iface(funder_ata, funder, mint, token_program)?;
+    ata_balc_iface(funder_ata, total, token_program)?;
+    Ok(Self {
+      funder,
+      funder_ata,
+      vault_ata,
+      vault,
+      mint,
+      distributor_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+      decimals,
+      id,
+      total,
+      deadline,
+      count,
+      root,
+    })
+  }
+}
@@ -0,0 +1,117 @@
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda2, get_rent_exempt,
+  instructions::check_signer, make_maker_index, parse_u64, writable, Config, Ee, Escrow,
+  MakerIndex, PROG_ADDR,
+};
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+/// Grow an Escrow PDA made before seq, Dutch, trigger and received_y fields to the current layout, so EscrowTokWithdraw and EscrowTokCancel can read it. The offer is counted in the maker's MakerIndex and in Config open_escrows
+pub struct EscrowMigrate<'a> {
+  pub maker: &'a AccountView, //signer
+  pub escrow_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+}
+impl<'a> EscrowMigrate<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &70;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowMigrate {
+      maker,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      system_program: _,
+      rent_sysvar,
+    } = self;
+    log!("EscrowMigrate process()");
+    //the legacy layout ends with id, decimal_x, decimal_y and bump
+    let tail = Escrow::LEGACY_LEN - 3;
+    let (id, decimal_x, decimal_y, bump) = {
+      let data = escrow_pda.try_borrow()?;
+      if data[..32] != *maker.address().as_ref() {
+        return Ee::OnlyMaker.e();
+      }
+      let id = parse_u64(&data[tail - 8..tail])?;
+      (id, data[tail], data[tail + 1], data[tail + 2])
+    };
+    let (expected_pda, expected_bump) =
+      derive_pda2(maker.address(), &id.to_le_bytes(), Escrow::SEED)?;
+    if escrow_pda.address() != &expected_pda || bump != expected_bump {
+      return Ee::EscrowPDA.e();
+    }
+
+    escrow_pda.resize(Escrow::LEN)?;
+    let min_lamport = get_rent_exempt(escrow_pda, rent_sysvar, Escrow::LEN)?;
+    let prev_lamport = escrow_pda.lamports();
+    if min_lamport > prev_lamport {
+      log!("deposit lamports");
+      SystemTransfer {
+        from: maker,
+        to: escrow_pda,
+        lamports: min_lamport - prev_lamport,
+      }
+      .invoke()?;
+    }
+    {
+      let mut data = escrow_pda.try_borrow_mut()?;
+      data[tail..].fill(0);
+    }
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    escrow.set_decimal_x(decimal_x);
+    escrow.set_decimal_y(decimal_y);
+    escrow.set_config(config_pda.address());
+    escrow.set_bump(bump);
+
+    make_maker_index(maker, maker_index, rent_sysvar)?;
+    MakerIndex::from_account_view(maker_index)?.adopt(id)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    config.add_open_escrow()?;
+    log!("id: {}, open escrows: {}", id, config.open_escrows());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowMigrate<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowMigrate try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [maker, escrow_pda, config_pda, maker_index, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    writable(maker)?;
+    writable(escrow_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+    check_pda(escrow_pda)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    unsafe {
+      if escrow_pda.owner().ne(&PROG_ADDR) {
+        return Err(Ee::EscrowIsForeign.into());
+      }
+    }
+    if escrow_pda.data_len() != Escrow::LEGACY_LEN {
+      return Err(Ee::EscrowDataLengh.into());
+    }
+    Ok(Self {
+      maker,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      system_program,
+      rent_sysvar,
+    })
+  }
+}
@@ -0,0 +1,188 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface, check_mint_iface,
+  check_rent_sysvar, check_sysprog, close_pda, derive_ata, executable, instructions::check_signer,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, CloseAccountIface, Config, Ee, Escrow,
+  MakerIndex, TransferIface,
+};
+
+/// Maker closes an Escrow Offer with one SOL leg, as a cancel before a take or a withdraw after it. The Escrow ATA tokens go to the maker ATA, and the Escrow PDA lamports, SOL leg included, go to the maker
+pub struct EscrowSolClose<'a> {
+  pub maker: &'a AccountView, //signer
+  pub maker_ata: &'a AccountView,
+  pub escrow_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub escrow_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> EscrowSolClose<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &47;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowSolClose {
+      maker,
+      maker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("EscrowSolClose process()");
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    escrow_pda.check_borrow_mut()?;
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    if maker.address().ne(escrow.maker()) {
+      return Ee::OnlyMaker.e();
+    }
+    //only the Config that counted this offer at make gets it uncounted
+    if config_pda.address().ne(escrow.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    let decimals = if escrow.sol_x(mint.address())? {
+      escrow.decimal_y()
+    } else {
+      escrow.decimal_x()
+    };
+    check_decimals_iface(mint, token_program, decimals)?;
+
+    if escrow_ata.data_len() > 0 {
+      let id_bytes = &escrow.id().to_le_bytes();
+      let bump = escrow.bump();
+      let signer_seeds = [
+        Seed::from(Escrow::SEED),
+        Seed::from(maker.address().as_ref()),
+        Seed::from(id_bytes),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+
+      let amount = tok_acct_amount(escrow_ata)?;
+      log!("Escrow ATA amount: {}", amount);
+      if amount > 0 {
+        if maker_ata.is_data_empty() {
+          log!("Make maker_ata");
+          pinocchio_associated_token_account::instructions::Create {
+            funding_account: maker,
+            account: maker_ata,
+            wallet: maker,
+            mint,
+            system_program,
+            token_program,
+          }
+          .invoke()?;
+        } else {
+          log!("maker_ata has data");
+          check_ata_iface(maker_ata, maker, mint, token_program)?;
+          rent_exempt_tokacct_iface(maker_ata, rent_sysvar, token_program)?;
+        }
+        TransferIface {
+          from: escrow_ata,
+          mint,
+          to: maker_ata,
+          authority: escrow_pda,
+          amount,
+          decimals,
+          token_program,
+          hook_accounts,
+        }
+        .invoke_signed(core::slice::from_ref(&seed_signer))?;
+      }
+      log!("Close Escrow ATA");
+      CloseAccountIface {
+        account: escrow_ata,
+        authority: escrow_pda,
+        destination: maker,
+        token_program,
+      }
+      .invoke_signed(&[seed_signer])?;
+    }
+
+    config.sub_open_escrow();
+    MakerIndex::from_account_view(maker_index)?.close_offer();
+    log!("open escrows: {}", config.open_escrows());
+
+    log!("Close EscrowPDA");
+    close_pda(escrow_pda, maker)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowSolClose<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowSolClose try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [maker, maker_ata, escrow_ata, mint, escrow_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(maker_ata)?;
+    writable(escrow_ata)?;
+    //an empty decoy must not leave tokens stranded in the real Escrow ATA
+    if escrow_ata.address()
+      != &derive_ata(
+        escrow_pda.address(),
+        mint.address(),
+        token_program.address(),
+      )?
+    {
+      return Err(Ee::AtaOrOwner.into());
+    }
+    writable(escrow_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+    if MakerIndex::from_account_view(maker_index)?.maker() != maker.address() {
+      return Err(Ee::MakerIndexPDA.into());
+    }
+    if escrow_pda.is_data_empty() {
+      return Err(Ee::EscrowDataEmpty.into());
+    }
+    check_mint_iface(mint, token_program)?;
+
+    Ok(Self {
+      maker,
+      maker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
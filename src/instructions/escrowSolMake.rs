@@ -0,0 +1,234 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_rent_sysvar, check_sysprog, executable, instructions::check_signer,
+  make_maker_index, net_received, none_zero_u64, parse_u64, rent_exempt_mint_iface,
+  rent_exempt_tokacct_iface, sol_balc, tok_acct_amount, u8_to_bool, writable, Config, Ee, Escrow,
+  MakerIndex, TransferIface, ID, PROG_ADDR,
+};
+
+/// Make Escrow Offer with one leg in native SOL, held as lamports in the Escrow PDA. sol_x picks the SOL leg; mint is the token leg. id 0 takes the next id from the maker's MakerIndex
+pub struct EscrowSolMake<'a> {
+  pub maker: &'a AccountView, //signer
+  pub maker_ata: &'a AccountView,
+  pub escrow_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub escrow_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub sol_x: bool,
+  pub decimals: u8,
+  pub amount_x: u64,
+  pub amount_y: u64,
+  pub id: u64,
+}
+impl<'a> EscrowSolMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &45;
+  pub const SOL_DECIMALS: u8 = 9;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowSolMake {
+      maker,
+      maker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+      sol_x,
+      decimals,
+      amount_x,
+      amount_y,
+      id,
+    } = self;
+    log!("EscrowSolMake process()");
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    make_maker_index(maker, maker_index, rent_sysvar)?;
+    let index = MakerIndex::from_account_view(maker_index)?;
+    let id = index.allocate(id)?;
+    log!("allocated id: {}, open offers: {}", id, index.open_count());
+
+    let seed = [Escrow::SEED, maker.address().as_array(), &id.to_le_bytes()];
+    let (expected_escrow, bump) = Address::find_program_address(&seed[..], &ID.into());
+    if expected_escrow.ne(escrow_pda.address()) {
+      return Ee::EscrowPDA.e();
+    }
+    if !escrow_pda.is_data_empty() {
+      return Ee::EscrowExists.e();
+    }
+    log!("Make Escrow PDA");
+    //a SOL X leg goes into the Escrow PDA on top of its rent
+    let rent = Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Escrow::LEN)?;
+    let lamports = if sol_x {
+      rent
+        .checked_add(amount_x)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+    } else {
+      rent
+    };
+    let id_bytes = &id.to_le_bytes();
+    let seeds = [
+      Seed::from(Escrow::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: maker,
+      to: escrow_pda,
+      lamports,
+      space: Escrow::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    if escrow_ata.is_data_empty() {
+      log!("Make escrow_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: maker,
+        account: escrow_ata,
+        wallet: escrow_pda,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("escrow_ata has data");
+      check_ata_iface(escrow_ata, escrow_pda, mint, token_program)?;
+      rent_exempt_tokacct_iface(escrow_ata, rent_sysvar, token_program)?;
+    }
+
+    let sol = Address::new_from_array(Escrow::SOL_MINT);
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    if sol_x {
+      log!("{} lamports escrowed", amount_x);
+      escrow.set_mint_x(&sol);
+      escrow.set_mint_y(mint.address());
+      escrow.set_amount_x(amount_x)?;
+      escrow.set_decimal_x(Self::SOL_DECIMALS);
+      escrow.set_decimal_y(decimals);
+    } else {
+      let balc_before = tok_acct_amount(escrow_ata)?;
+      TransferIface {
+        from: maker_ata,
+        mint,
+        to: escrow_ata,
+        authority: maker,
+        amount: amount_x,
+        decimals,
+        token_program,
+        hook_accounts,
+      }
+      .invoke()?;
+      log!("tokens sent from maker_ata");
+      escrow.set_mint_x(mint.address());
+      escrow.set_mint_y(&sol);
+      escrow.set_amount_x(net_received(escrow_ata, balc_before)?)?;
+      escrow.set_decimal_x(decimals);
+      escrow.set_decimal_y(Self::SOL_DECIMALS);
+    }
+    escrow.set_maker(maker.address());
+    escrow.set_config(config_pda.address());
+    escrow.set_id(id)?;
+    escrow.set_amount_y(amount_y)?;
+    escrow.set_bump(bump);
+    config.add_open_escrow()?;
+    log!("open escrows: {}", config.open_escrows());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowSolMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowSolMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //1+1+8+8+8: 2x u8 take 2, 3x u64 take 24 bytes
+    check_data_len(data, 26)?;
+
+    let [maker, maker_ata, escrow_ata, mint, escrow_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(escrow_ata)?;
+    writable(escrow_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+
+    let sol_x = u8_to_bool(data[0])?;
+    let decimals = data[1];
+    let amount_x = parse_u64(&data[2..10])?;
+    let amount_y = parse_u64(&data[10..18])?;
+    let id = parse_u64(&data[18..26])?;
+    log!(
+      "sol_x: {}, decimals: {}, amount_x: {}, amount_y: {}, id: {}",
+      sol_x as u8,
+      decimals,
+      amount_x,
+      amount_y,
+      id
+    );
+    none_zero_u64(amount_x)?;
+    none_zero_u64(amount_y)?;
+
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    if sol_x {
+      sol_balc(maker, amount_x)?;
+    } else {
+      writable(maker_ata)?;
+      check_ata_iface(maker_ata, maker, mint, token_program)?;
+      ata_balc_iface(maker_ata, amount_x, token_program)?;
+    }
+    Ok(Self {
+      maker,
+      maker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+      sol_x,
+      decimals,
+      amount_x,
+      amount_y,
+      id,
+    })
+  }
+}
@@ -0,0 +1,231 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface, check_mint_iface,
+  check_rent_sysvar, check_sysprog, executable, instructions::check_signer, net_received,
+  none_zero_u64, parse_u32, parse_u64, rent_exempt_tokacct_iface, tok_acct_amount, writable,
+  Config, Ee, Escrow, TransferIface,
+};
+
+/// Take Escrow Offer with one SOL leg. The taker's min_x, max_y and seq guard against an offer that changed
+pub struct EscrowSolTake<'a> {
+  pub taker: &'a AccountView, //signer
+  pub taker_ata: &'a AccountView,
+  pub escrow_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub escrow_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub min_x: u64,
+  pub max_y: u64,
+  pub seq: u32,
+}
+impl<'a> EscrowSolTake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &46;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowSolTake {
+      taker,
+      taker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+      min_x,
+      max_y,
+      seq,
+    } = self;
+    log!("EscrowSolTake process()");
+    config_pda.check_borrow_mut()?;
+    let _config: &mut Config = Config::from_account_view(config_pda)?;
+
+    escrow_pda.check_borrow_mut()?;
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    let sol_x = escrow.sol_x(mint.address())?;
+    let amount_x = escrow.amount_x();
+    let amount_y = escrow.amount_y();
+    log!(
+      "sol_x: {}, amount_x: {}, amount_y: {}, seq: {}",
+      sol_x as u8,
+      amount_x,
+      amount_y,
+      escrow.seq()
+    );
+    if escrow.seq() != seq {
+      return Ee::EscrowSeq.e();
+    }
+    if amount_x < min_x {
+      return Ee::TakeMinX.e();
+    }
+    if amount_y > max_y {
+      return Ee::TakeMaxY.e();
+    }
+
+    if sol_x {
+      let decimal_y = escrow.decimal_y();
+      check_decimals_iface(mint, token_program, decimal_y)?;
+      let rent = Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Escrow::LEN)?;
+      if escrow_pda.lamports().saturating_sub(rent) < amount_x {
+        return Ee::EscrowInsuffTokenX.e();
+      }
+      check_ata_iface(taker_ata, taker, mint, token_program)?;
+      if tok_acct_amount(taker_ata)? < amount_y {
+        return Ee::TakerInsuffTokenY.e();
+      }
+
+      log!("Transfer Token Y to Escrow ATA");
+      let balc_before = tok_acct_amount(escrow_ata)?;
+      TransferIface {
+        from: taker_ata,
+        mint,
+        to: escrow_ata,
+        authority: taker,
+        amount: amount_y,
+        decimals: decimal_y,
+        token_program,
+        hook_accounts,
+      }
+      .invoke()?;
+      //the maker withdraws what the escrow received, which can be less than amount_y after transfer fees
+      let received_y = net_received(escrow_ata, balc_before)?;
+
+      log!("Transfer SOL X to Taker");
+      escrow_pda.set_lamports(escrow_pda.lamports() - amount_x);
+      let sum_lam = taker
+        .lamports()
+        .checked_add(amount_x)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+      taker.set_lamports(sum_lam);
+      escrow.set_received_y(received_y)?;
+    } else {
+      let decimal_x = escrow.decimal_x();
+      check_decimals_iface(mint, token_program, decimal_x)?;
+      if tok_acct_amount(escrow_ata)? < amount_x {
+        return Ee::EscrowInsuffTokenX.e();
+      }
+      if taker.lamports() < amount_y {
+        return Ee::TakerInsuffTokenY.e();
+      }
+
+      log!("Check Taker ATA");
+      if taker_ata.is_data_empty() {
+        log!("Make taker_ata");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: taker,
+          account: taker_ata,
+          wallet: taker,
+          mint,
+          system_program,
+          token_program,
+        }
+        .invoke()?;
+      } else {
+        log!("taker_ata has data");
+        check_ata_iface(taker_ata, taker, mint, token_program)?;
+      }
+      rent_exempt_tokacct_iface(taker_ata, rent_sysvar, token_program)?;
+
+      log!("Transfer SOL Y to Escrow PDA");
+      SystemTransfer {
+        from: taker,
+        to: escrow_pda,
+        lamports: amount_y,
+      }
+      .invoke()?;
+
+      log!("Transfer Token X to Taker ATA");
+      let id_bytes = &escrow.id().to_le_bytes();
+      let bump = escrow.bump();
+      let signer_seeds = [
+        Seed::from(Escrow::SEED),
+        Seed::from(escrow.maker().as_ref()),
+        Seed::from(id_bytes),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+      TransferIface {
+        from: escrow_ata,
+        mint,
+        to: taker_ata,
+        authority: escrow_pda,
+        amount: amount_x,
+        decimals: decimal_x,
+        token_program,
+        hook_accounts,
+      }
+      .invoke_signed(&[seed_signer])?;
+    }
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowSolTake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowSolTake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //8+8+4: 2x u64 take 16, u32 takes 4 bytes
+    check_data_len(data, 20)?;
+
+    let [taker, taker_ata, escrow_ata, mint, escrow_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(taker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(taker_ata)?;
+    writable(escrow_ata)?;
+    check_ata_iface(escrow_ata, escrow_pda, mint, token_program)?;
+    writable(escrow_pda)?;
+    writable(config_pda)?;
+    if escrow_pda.is_data_empty() {
+      return Err(Ee::EscrowDataEmpty.into());
+    }
+    check_mint_iface(mint, token_program)?;
+
+    let min_x = parse_u64(&data[0..8])?;
+    let max_y = parse_u64(&data[8..16])?;
+    let seq = parse_u32(&data[16..20])?;
+    log!("min_x: {}, max_y: {}, seq: {}", min_x, max_y, seq);
+    none_zero_u64(max_y)?;
+
+    Ok(Self {
+      taker,
+      taker_ata,
+      escrow_ata,
+      mint,
+      escrow_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+      min_x,
+      max_y,
+      seq,
+    })
+  }
+}
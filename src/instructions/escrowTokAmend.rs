@@ -0,0 +1,186 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_data_len, check_decimals_iface, executable,
+  instructions::check_signer, net_received, none_zero_u64, parse_u64, tok_acct_amount, writable,
+  Ee, Escrow, TransferIface,
+};
+
+/// Maker changes the price and tops up or withdraws part of X while the offer stays open. Each amendment bumps the Escrow seq
+pub struct EscrowTokAmend<'a> {
+  pub maker: &'a AccountView, //signer
+  pub maker_ata_x: &'a AccountView,
+  pub escrow_ata_x: &'a AccountView,
+  pub mint_x: &'a AccountView,
+  pub escrow_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub amount_y: u64,
+  pub top_up_x: u64,
+  pub withdraw_x: u64,
+}
+impl<'a> EscrowTokAmend<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &44;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowTokAmend {
+      maker,
+      maker_ata_x,
+      escrow_ata_x,
+      mint_x,
+      escrow_pda,
+      token_program,
+      hook_accounts,
+      amount_y,
+      top_up_x,
+      withdraw_x,
+    } = self;
+    log!("EscrowTokAmend process()");
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    let decimal_x = escrow.decimal_x();
+    let mut amount_x = escrow.amount_x();
+
+    if top_up_x > 0 {
+      log!("Top up X");
+      let balc_before = tok_acct_amount(escrow_ata_x)?;
+      TransferIface {
+        from: maker_ata_x,
+        mint: mint_x,
+        to: escrow_ata_x,
+        authority: maker,
+        amount: top_up_x,
+        decimals: decimal_x,
+        token_program,
+        hook_accounts,
+      }
+      .invoke()?;
+      amount_x = amount_x
+        .checked_add(net_received(escrow_ata_x, balc_before)?)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    if withdraw_x > 0 {
+      log!("Withdraw X");
+      //part of X only. Cancel the offer to take all of it back
+      if withdraw_x >= amount_x {
+        return Ee::EscrowInsuffTokenX.e();
+      }
+      let id_bytes = &escrow.id().to_le_bytes();
+      let bump = escrow.bump();
+      let signer_seeds = [
+        Seed::from(Escrow::SEED),
+        Seed::from(maker.address().as_ref()),
+        Seed::from(id_bytes),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+      TransferIface {
+        from: escrow_ata_x,
+        mint: mint_x,
+        to: maker_ata_x,
+        authority: escrow_pda,
+        amount: withdraw_x,
+        decimals: decimal_x,
+        token_program,
+        hook_accounts,
+      }
+      .invoke_signed(&[seed_signer])?;
+      amount_x -= withdraw_x;
+    }
+    //an NFT leg stays at exactly 1
+    if (escrow.mode() & Escrow::NFT_X != 0 && amount_x != 1)
+      || (escrow.mode() & Escrow::COLLECTION_Y != 0 && amount_y != 1)
+    {
+      return Ee::EscrowNft.e();
+    }
+    //the new start price must stay above the Dutch end price
+    if escrow.mode() & Escrow::DUTCH_Y != 0 && amount_y <= escrow.end_amount_y() {
+      return Ee::EscrowDutch.e();
+    }
+    escrow.set_amount_x(amount_x)?;
+    escrow.set_amount_y(amount_y)?;
+    let seq = escrow
+      .seq()
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    escrow.set_seq(seq);
+    log!(
+      "amount_x: {}, amount_y: {}, seq: {}",
+      amount_x,
+      amount_y,
+      seq
+    );
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokAmend<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowTokAmend try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [maker, maker_ata_x, escrow_ata_x, mint_x, escrow_pda, token_program, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(maker)?;
+    executable(token_program)?;
+    writable(maker_ata_x)?;
+    writable(escrow_ata_x)?;
+    writable(escrow_pda)?;
+
+    //3x u64 takes 24 bytes
+    check_data_len(data, 24)?;
+    let amount_y = parse_u64(&data[0..8])?;
+    let top_up_x = parse_u64(&data[8..16])?;
+    let withdraw_x = parse_u64(&data[16..24])?;
+    log!(
+      "amount_y: {}, top_up_x: {}, withdraw_x: {}",
+      amount_y,
+      top_up_x,
+      withdraw_x
+    );
+    none_zero_u64(amount_y)?;
+    if top_up_x > 0 && withdraw_x > 0 {
+      return Err(Ee::EscrowAmend.into());
+    }
+
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    if maker.address().ne(escrow.maker()) {
+      return Err(Ee::OnlyMaker.into());
+    }
+    if escrow.mint_x().ne(mint_x.address()) {
+      return Err(Ee::EscrowMintX.into());
+    }
+    check_decimals_iface(mint_x, token_program, escrow.decimal_x())?;
+    check_ata_iface(maker_ata_x, maker, mint_x, token_program)?;
+    check_ata_iface(escrow_ata_x, escrow_pda, mint_x, token_program)?;
+    //a taken offer has no X left. The maker withdraws Y instead
+    if tok_acct_amount(escrow_ata_x)? == 0 {
+      return Err(Ee::MakerToWithdrawTokenY.into());
+    }
+    if top_up_x > 0 {
+      ata_balc_iface(maker_ata_x, top_up_x, token_program)?;
+    }
+    Ok(Self {
+      maker,
+      maker_ata_x,
+      escrow_ata_x,
+      mint_x,
+      escrow_pda,
+      token_program,
+      hook_accounts,
+      amount_y,
+      top_up_x,
+      withdraw_x,
+    })
+  }
+}
@@ -8,13 +8,15 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_ata_escrow, check_atoken_gpvbd, check_data_len, check_decimals,
-  check_escrow_mints, check_mint0a, check_rent_sysvar, check_sysprog, executable,
-  instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct,
-  writable, Config, Ee, Escrow, ID, PROG_ADDR,
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_escrow_mints, check_mint_iface, check_nft_iface, check_oracle_feed, check_rent_sysvar,
+  check_sysprog, derive_pda1, executable, instructions::check_signer, is_token22, net_received,
+  none_zero_u64, parse_i64, parse_u64, rent_exempt_mint_iface, rent_exempt_tokacct_iface,
+  to32bytes, tok_acct_amount, u8_to_bool, writable, Config, Ee, Escrow, MakerIndex, TransferIface,
+  ID, PROG_ADDR,
 };
 
-/// Make Escrow Token Offer
+/// Make Escrow Token Offer. Serves both Token and Token2022 via token_program. id 0 takes the next id from the maker's MakerIndex
 pub struct EscrowTokMake<'a> {
   pub maker: &'a AccountView, //signer
   pub maker_ata_x: &'a AccountView,
@@ -23,15 +25,20 @@ pub struct EscrowTokMake<'a> {
   pub mint_y: &'a AccountView,
   pub escrow_pda: &'a AccountView,
   pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
   pub amount_x: u64,
   pub amount_y: u64,
   pub id: u64,
   pub decimal_x: u8,
   pub decimal_y: u8,
+  pub mode: u8,
+  pub dutch: Option<(u64, i64, i64)>, //DUTCH_Y: end_amount_y, start_time, end_time
+  pub trigger: Option<(u8, bool, u64, [u8; 32])>, //TRIGGER: oracle_vendor, trigger_above, trigger_price, feed_id
 }
 impl<'a> EscrowTokMake<'a> {
   pub const DISCRIMINATOR: &'a u8 = &15;
@@ -45,19 +52,30 @@ impl<'a> EscrowTokMake<'a> {
       mint_y,
       escrow_pda,
       config_pda,
+      maker_index,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
       decimal_x,
       decimal_y,
       amount_x,
       amount_y,
       id,
+      mode,
+      dutch,
+      trigger,
     } = self;
     log!("---------== process()");
     config_pda.check_borrow_mut()?;
-    let _config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(&config_pda)?;
+    config.check_address(config_pda)?;
+
+    make_maker_index(maker, maker_index, rent_sysvar)?;
+    let index = MakerIndex::from_account_view(maker_index)?;
+    let id = index.allocate(id)?;
+    log!("allocated id: {}, open offers: {}", id, index.open_count());
 
     /*let bump = unsafe { *(data.as_ptr() as *const u8) }.to_le_bytes();
     if bump.len() != 1 { return Err(..);  };   bump.as_ref()*/
@@ -116,32 +134,47 @@ impl<'a> EscrowTokMake<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("escrow_ata_x has data");
-      check_ata_escrow(escrow_ata_x, escrow_pda, mint_x)?;
-      rent_exempt_tokacct(escrow_ata_x, rent_sysvar)?;
+      check_ata_iface(escrow_ata_x, escrow_pda, mint_x, token_program)?;
+      rent_exempt_tokacct_iface(escrow_ata_x, rent_sysvar, token_program)?;
     }
     log!("Vault ATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let balc_before = tok_acct_amount(escrow_ata_x)?;
+    TransferIface {
       from: maker_ata_x,
       mint: mint_x,
       to: escrow_ata_x,
       authority: maker,
       amount: amount_x, // *(data.as_ptr().add(1 + 8) as *const u64)
       decimals: decimal_x,
+      token_program,
+      hook_accounts,
     }
     .invoke()?;
     log!("tokens sent from maker_ata_x");
+    //escrow holds what it received, which can be less than amount_x after transfer fees
+    let received_x = net_received(escrow_ata_x, balc_before)?;
 
     let escrow: &mut Escrow = Escrow::from_account_view(&escrow_pda)?;
     escrow.set_maker(maker.address());
     escrow.set_mint_x(mint_x.address());
     escrow.set_mint_y(mint_y.address());
+    escrow.set_config(config_pda.address());
     escrow.set_id(id)?;
-    escrow.set_amount_x(amount_x)?;
+    escrow.set_amount_x(received_x)?;
     escrow.set_amount_y(amount_y)?; // unsafe { *(data.as_ptr().add(1) as *const u64) };
     escrow.set_decimal_x(decimal_x);
     escrow.set_decimal_y(decimal_y);
+    escrow.set_mode(mode)?;
+    if let Some((end_amount_y, start_time, end_time)) = dutch {
+      escrow.set_dutch(end_amount_y, start_time, end_time)?;
+    }
+    if let Some((oracle_vendor, trigger_above, trigger_price, feed_id)) = trigger {
+      escrow.set_trigger(oracle_vendor, trigger_above, trigger_price, feed_id)?;
+    }
     escrow.set_bump(bump); // unsafe { *data.as_ptr() };
+    config.add_open_escrow()?;
+    log!("open escrows: {}", config.open_escrows());
 
     Ok(())
   }
@@ -153,11 +186,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
     log!("EscrowTokMake try_from");
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
-    let data_len = 26;
-    //2x u8 takes 2 + 2x u64 takes 16 bytes
-    check_data_len(data, data_len)?;
+    //2x u8 takes 2 + 3x u64 takes 24 bytes + mode u8 takes 1, then DUTCH_Y: u64 + 2x i64 take 24 bytes, then TRIGGER: 2x u8 + u64 + feed_id take 42 bytes
+    if data.len() < 27 {
+      return Err(Ee::InputDataLen.into());
+    }
 
-    let [maker, maker_ata_x, escrow_ata_x, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar] =
+    let [maker, maker_ata_x, escrow_ata_x, mint_x, mint_y, escrow_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -170,37 +204,94 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
     log!("EscrowTokMake try_from 3");
 
     writable(maker_ata_x)?;
-    check_ata(maker_ata_x, maker, mint_x)?;
+    check_ata_iface(maker_ata_x, maker, mint_x, token_program)?;
     writable(escrow_ata_x)?;
     writable(escrow_pda)?;
     writable(config_pda)?;
+    writable(maker_index)?;
     log!("EscrowTokMake try_from 4");
 
     let decimal_x = data[0];
     let amount_x = parse_u64(&data[1..9])?;
     log!("decimal_x: {}, amount_x: {}", decimal_x, amount_x);
     none_zero_u64(amount_x)?;
-    ata_balc(maker_ata_x, amount_x)?;
+    ata_balc_iface(maker_ata_x, amount_x, token_program)?;
 
     let decimal_y = data[9];
     let amount_y = parse_u64(&data[10..18])?;
     log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
     none_zero_u64(amount_y)?;
 
-    let id = parse_u64(&data[18..data_len])?;
-    log!("id: {}", id);
+    let id = parse_u64(&data[18..26])?;
+    let mode = data[26];
+    log!("id: {}, mode: {}", id, mode);
+    let dutch_len = if mode & Escrow::DUTCH_Y != 0 { 24 } else { 0 };
+    let trigger_len = if mode & Escrow::TRIGGER != 0 { 42 } else { 0 };
+    check_data_len(data, 27 + dutch_len + trigger_len)?;
+    let dutch = if dutch_len > 0 {
+      let end_amount_y = parse_u64(&data[27..35])?;
+      let start_time = parse_i64(&data[35..43])?;
+      let end_time = parse_i64(&data[43..51])?;
+      log!(
+        "end_amount_y: {}, start_time: {}, end_time: {}",
+        end_amount_y,
+        start_time,
+        end_time
+      );
+      if mode & Escrow::COLLECTION_Y != 0 {
+        return Err(Ee::EscrowDutch.into());
+      }
+      Some((end_amount_y, start_time, end_time))
+    } else {
+      None
+    };
+    let trigger = if trigger_len > 0 {
+      let i = 27 + dutch_len;
+      let oracle_vendor = data[i];
+      let trigger_above = u8_to_bool(data[i + 1])?;
+      let trigger_price = parse_u64(&data[i + 2..i + 10])?;
+      let feed_id = *to32bytes(&data[i + 10..i + 42])?;
+      log!(
+        "oracle_vendor: {}, trigger_above: {}, trigger_price: {}",
+        oracle_vendor,
+        trigger_above as u8,
+        trigger_price
+      );
+      check_oracle_feed(oracle_vendor, &feed_id)?;
+      Some((oracle_vendor, trigger_above, trigger_price, feed_id))
+    } else {
+      None
+    };
 
     log!("EscrowTokMake try_from 5");
     check_escrow_mints(mint_x, mint_y)?;
-    rent_exempt_mint(mint_x, rent_sysvar, 0)?;
-    rent_exempt_mint(mint_y, rent_sysvar, 1)?;
+    rent_exempt_mint_iface(mint_x, rent_sysvar, token_program, 0)?;
+    rent_exempt_mint_iface(mint_y, rent_sysvar, token_program, 1)?;
     //TODO: fee is part of exchange amount
 
     log!("EscrowTokMake try_from 6");
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_decimals_iface(mint_x, token_program, decimal_x)?;
+    check_decimals_iface(mint_y, token_program, decimal_y)?;
+    check_mint_iface(mint_x, token_program)?;
+    check_mint_iface(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+
+    if mode & Escrow::NFT_X != 0 {
+      log!("NFT X");
+      check_nft_iface(mint_x, token_program)?;
+      if amount_x != 1 {
+        return Err(Ee::EscrowNft.into());
+      }
+    }
+    if mode & Escrow::COLLECTION_Y != 0 {
+      log!("Collection Y");
+      //mint_y is the collection(group) mint. Members are Token2022 NFTs
+      if !is_token22(token_program)? {
+        return Err(Ee::EscrowCollection.into());
+      }
+      if amount_y != 1 || decimal_y != 0 {
+        return Err(Ee::EscrowNft.into());
+      }
+    }
 
     Ok(Self {
       maker,
@@ -210,15 +301,62 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
       mint_y,
       escrow_pda,
       config_pda,
+      maker_index,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
       amount_x,
       amount_y,
       id,
       decimal_x,
       decimal_y,
+      mode,
+      dutch,
+      trigger,
     })
   }
 }
+
+/// Make the maker's MakerIndex on the first offer. Ids start at 1
+pub fn make_maker_index(
+  maker: &AccountView,
+  maker_index: &AccountView,
+  rent_sysvar: &AccountView,
+) -> ProgramResult {
+  if maker_index.lamports() > 0 {
+    let index = MakerIndex::from_account_view(maker_index)?;
+    if index.maker() != maker.address() {
+      return Ee::MakerIndexPDA.e();
+    }
+    return Ok(());
+  }
+  let (expected_index, bump) = derive_pda1(maker.address(), MakerIndex::SEED)?;
+  if maker_index.address() != &expected_index {
+    return Ee::MakerIndexPDA.e();
+  }
+  log!("Make MakerIndex");
+  let signer_seeds = [
+    Seed::from(MakerIndex::SEED),
+    Seed::from(maker.address().as_ref()),
+    Seed::from(core::slice::from_ref(&bump)),
+  ];
+  let seed_signer = Signer::from(&signer_seeds);
+
+  let rent = Rent::from_account_view(rent_sysvar)?;
+  pinocchio_system::instructions::CreateAccount {
+    from: maker,
+    to: maker_index,
+    lamports: rent.try_minimum_balance(MakerIndex::LEN)?,
+    space: MakerIndex::LEN as u64,
+    owner: &PROG_ADDR,
+  }
+  .invoke_signed(&[seed_signer])?;
+
+  let index = MakerIndex::from_account_view(maker_index)?;
+  index.set_maker(maker.address());
+  index.set_next_id(1);
+  index.set_bump(bump);
+  Ok(())
+}
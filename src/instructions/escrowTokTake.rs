@@ -5,16 +5,15 @@ use pinocchio::{
   AccountView, ProgramResult,
 };
 use pinocchio_log::log;
-use pinocchio_token::state::TokenAccount;
 
 use crate::{
-  check_ata, check_ata_escrow, check_atoken_gpvbd, check_data_len, check_decimals,
-  check_escrow_mints, check_mint0a, check_rent_sysvar, check_sysprog, executable,
-  instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct,
-  writable, Config, Ee, Escrow,
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface, check_escrow_mints,
+  check_mint_iface, check_nft_iface, check_rent_sysvar, check_sysprog, executable, get_time_i64,
+  group_of_member, instructions::check_signer, net_received, none_zero_u64, parse_u32, parse_u64,
+  read_oracle_pda, rent_exempt_mint_iface, rent_exempt_tokacct_iface, tok_acct_amount, writable,
+  Config, Ee, Escrow, TransferIface,
 };
-//TODO: add Token2022 interface
-/// Take Escrow Token Offer
+/// Take Escrow Token Offer. Serves both Token and Token2022 via token_program. The taker's min_x, max_y and seq guard against an offer that changed
 pub struct EscrowTokTake<'a> {
   pub taker: &'a AccountView, //signer
   pub taker_ata_x: &'a AccountView,
@@ -29,9 +28,10 @@ pub struct EscrowTokTake<'a> {
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
-  pub amount_x: u64,
-  pub amount_y: u64,
-  pub id: u64,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub min_x: u64,
+  pub max_y: u64,
+  pub seq: u32,
   pub decimal_x: u8,
   pub decimal_y: u8,
 }
@@ -53,9 +53,10 @@ impl<'a> EscrowTokTake<'a> {
       system_program,
       atoken_program: _,
       rent_sysvar,
-      amount_x,
-      amount_y,
-      id,
+      hook_accounts,
+      min_x,
+      max_y,
+      seq,
       decimal_x,
       decimal_y,
     } = self;
@@ -73,17 +74,51 @@ impl<'a> EscrowTokTake<'a> {
     if escrow.mint_x().ne(mint_x.address()) {
       return Ee::EscrowMintX.e();
     }
-    if escrow.mint_y().ne(mint_y.address()) {
+    let collection_y = escrow.mode() & Escrow::COLLECTION_Y != 0;
+    if collection_y {
+      //mint_y is a member NFT of the collection in the offer
+      if group_of_member(mint_y)?.as_ref() != Some(escrow.mint_y()) {
+        return Ee::EscrowCollection.e();
+      }
+      check_nft_iface(mint_y, token_program)?;
+    } else if escrow.mint_y().ne(mint_y.address()) {
       return Ee::EscrowMintY.e();
     }
-    if escrow.amount_x() != amount_x {
-      return Ee::InputAmountX.e();
+    let amount_x = escrow.amount_x();
+    //a Dutch auction asks less Y as time passes
+    let amount_y = escrow.price_y(get_time_i64()?)?;
+    let id = escrow.id();
+    log!(
+      "amount_x: {}, amount_y: {}, seq: {}",
+      amount_x,
+      amount_y,
+      escrow.seq()
+    );
+    if escrow.seq() != seq {
+      return Ee::EscrowSeq.e();
+    }
+    let mut hook_accounts = hook_accounts;
+    if escrow.mode() & Escrow::TRIGGER != 0 {
+      //the oracle PDA comes first in the remaining accounts, before TransferHook accounts
+      let [oracle_pda, rest @ ..] = hook_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      //stale prices are rejected by read_oracle_pda
+      let price = read_oracle_pda(escrow.oracle_vendor(), oracle_pda, escrow.feed_id())?;
+      escrow.check_trigger(price)?;
+      hook_accounts = rest;
+    }
+    if amount_x < min_x {
+      return Ee::TakeMinX.e();
     }
-    if escrow.amount_y() != amount_y {
-      return Ee::InputAmountY.e();
+    if amount_y > max_y {
+      return Ee::TakeMaxY.e();
     }
-    if escrow.id() != id {
-      return Ee::EscrowId.e();
+    if tok_acct_amount(escrow_ata_x)? < amount_x {
+      return Ee::EscrowInsuffTokenX.e();
+    } //TODO: unknown token received by Escrow
+    if tok_acct_amount(taker_ata_y)? < amount_y {
+      return Ee::TakerInsuffTokenY.e();
     }
 
     log!("Check Escrow ATA Y");
@@ -101,8 +136,8 @@ impl<'a> EscrowTokTake<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("escrow_ata_y has data");
-      check_ata_escrow(escrow_ata_y, escrow_pda, mint_y)?;
-      rent_exempt_tokacct(escrow_ata_y, rent_sysvar)?;
+      check_ata_iface(escrow_ata_y, escrow_pda, mint_y, token_program)?;
+      rent_exempt_tokacct_iface(escrow_ata_y, rent_sysvar, token_program)?;
     }
 
     log!("Check Taker ATA X");
@@ -120,21 +155,26 @@ impl<'a> EscrowTokTake<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("taker_ata_x has data");
-      check_ata(taker_ata_x, taker, mint_x)?;
+      check_ata_iface(taker_ata_x, taker, mint_x, token_program)?;
     }
     writable(taker_ata_x)?;
-    rent_exempt_tokacct(taker_ata_x, rent_sysvar)?;
+    rent_exempt_tokacct_iface(taker_ata_x, rent_sysvar, token_program)?;
 
     log!("Transfer Token Y to Escrow ATA Y");
-    pinocchio_token::instructions::TransferChecked {
+    let balc_before = tok_acct_amount(escrow_ata_y)?;
+    TransferIface {
       from: taker_ata_y,
       mint: mint_y,
       to: escrow_ata_y,
       authority: taker,
       amount: amount_y,
       decimals: decimal_y,
+      token_program,
+      hook_accounts,
     }
     .invoke()?;
+    //the maker withdraws what the escrow received, which can be less than amount_y after transfer fees
+    let received_y = net_received(escrow_ata_y, balc_before)?;
 
     log!("Make Seed Signer");
     let id_bytes = &id.to_le_bytes();
@@ -147,15 +187,23 @@ impl<'a> EscrowTokTake<'a> {
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("Transfer Token X to Taker ATA X");
-    pinocchio_token::instructions::TransferChecked {
+    TransferIface {
       from: escrow_ata_x,
       mint: mint_x,
       to: taker_ata_x,
       authority: escrow_pda,
       amount: amount_x,
       decimals: decimal_x,
+      token_program,
+      hook_accounts,
     }
     .invoke_signed(&[seed_signer])?;
+
+    escrow.set_received_y(received_y)?;
+    if collection_y {
+      //the maker withdraws the delivered member
+      escrow.set_mint_y(mint_y.address());
+    }
     Ok(())
   }
 }
@@ -167,7 +215,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [taker, taker_ata_x, taker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar] =
+    let [taker, taker_ata_x, taker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -181,12 +229,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
 
     writable(taker_ata_x)?;
     writable(taker_ata_y)?;
-    check_ata(taker_ata_y, taker, mint_y)?;
+    check_ata_iface(taker_ata_y, taker, mint_y, token_program)?;
     log!("EscrowTokTake try_from 2");
 
     writable(escrow_ata_x)?;
     writable(escrow_ata_y)?;
-    check_ata(escrow_ata_x, escrow_pda, mint_x)?;
+    check_ata_iface(escrow_ata_x, escrow_pda, mint_x, token_program)?;
     log!("EscrowTokTake try_from 3");
 
     writable(escrow_pda)?;
@@ -196,42 +244,31 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
     }
     log!("EscrowTokTake try_from 4");
 
-    //2x u8 takes 2 + 2x u64 takes 16 bytes
-    check_data_len(data, 26)?;
+    //1+8+1+8+4: 2x u8 take 2, 2x u64 take 16, u32 takes 4 bytes
+    check_data_len(data, 22)?;
     let decimal_x = data[0];
-    let amount_x = parse_u64(&data[1..9])?;
-    log!("decimal_x: {}, amount_x: {}", decimal_x, amount_x);
-    none_zero_u64(amount_x)?;
-
-    let escrow_ata_x_info = TokenAccount::from_account_view(escrow_ata_x)?;
-    if escrow_ata_x_info.amount() < amount_x {
-      return Err(Ee::EscrowInsuffTokenX.into());
-    } //ata_balc(escrow_ata_x, amount_x)?;
-      //TODO: unknown token received by Escrow
+    let min_x = parse_u64(&data[1..9])?;
+    log!("decimal_x: {}, min_x: {}", decimal_x, min_x);
 
     let decimal_y = data[9];
-    let amount_y = parse_u64(&data[10..18])?;
-    log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
-    none_zero_u64(amount_y)?;
-    let taker_ata_y_info = TokenAccount::from_account_view(taker_ata_y)?;
-    if taker_ata_y_info.amount() < amount_y {
-      return Err(Ee::TakerInsuffTokenY.into());
-    } //ata_balc(taker_ata_y, amount_y)?;
+    let max_y = parse_u64(&data[10..18])?;
+    log!("decimal_y: {}, max_y: {}", decimal_y, max_y);
+    none_zero_u64(max_y)?;
 
-    let id = parse_u64(&data[18..26])?;
-    log!("id: {}", id);
+    let seq = parse_u32(&data[18..22])?;
+    log!("seq: {}", seq);
 
     log!("EscrowTokTake try_from 5");
     check_escrow_mints(mint_x, mint_y)?;
-    rent_exempt_mint(mint_x, rent_sysvar, 0)?;
-    rent_exempt_mint(mint_y, rent_sysvar, 1)?;
+    rent_exempt_mint_iface(mint_x, rent_sysvar, token_program, 0)?;
+    rent_exempt_mint_iface(mint_y, rent_sysvar, token_program, 1)?;
     //TODO: fee is part of exchange amount
 
     log!("EscrowTokTake try_from 6");
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_decimals_iface(mint_x, token_program, decimal_x)?;
+    check_decimals_iface(mint_y, token_program, decimal_y)?;
+    check_mint_iface(mint_x, token_program)?;
+    check_mint_iface(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
 
     Ok(Self {
       taker,
@@ -247,9 +284,10 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
       system_program,
       atoken_program,
       rent_sysvar,
-      amount_x,
-      amount_y,
-      id,
+      hook_accounts,
+      min_x,
+      max_y,
+      seq,
       decimal_x,
       decimal_y,
     })
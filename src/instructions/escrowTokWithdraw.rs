@@ -5,15 +5,14 @@ use pinocchio::{
   AccountView, ProgramResult,
 };
 use pinocchio_log::log;
-use pinocchio_token::state::TokenAccount;
 
 use crate::{
-  check_ata, check_atoken_gpvbd, check_data_len, check_decimals, check_mint0a, check_rent_sysvar,
-  check_sysprog, executable, instructions::check_signer, none_zero_u64, rent_exempt_mint,
-  rent_exempt_tokacct, writable, Config, Ee, Escrow,
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface, check_mint_iface,
+  check_rent_sysvar, check_sysprog, executable, instructions::check_signer, none_zero_u64,
+  rent_exempt_mint_iface, rent_exempt_tokacct_iface, tok_acct_amount, writable, CloseAccountIface,
+  Config, Ee, Escrow, MakerIndex, TransferIface,
 };
-//TODO: add Token2022 interface
-/// Make Withdraw Escrow Token Y
+/// Make Withdraw Escrow Token Y. Serves both Token and Token2022 via token_program
 pub struct EscrowTokWithdraw<'a> {
   pub maker: &'a AccountView, //signer
   pub maker_ata_x: &'a AccountView,
@@ -24,10 +23,12 @@ pub struct EscrowTokWithdraw<'a> {
   pub mint_y: &'a AccountView,
   pub escrow_pda: &'a AccountView,
   pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
 }
 impl<'a> EscrowTokWithdraw<'a> {
   pub const DISCRIMINATOR: &'a u8 = &17;
@@ -43,14 +44,17 @@ impl<'a> EscrowTokWithdraw<'a> {
       mint_y,
       escrow_pda,
       config_pda,
+      maker_index,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
     } = self;
     log!("---------== process()");
     config_pda.check_borrow_mut()?;
-    let _config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(&config_pda)?;
+    config.check_address(config_pda)?;
 
     escrow_pda.check_borrow_mut()?;
     let escrow: &mut Escrow = Escrow::from_account_view(&escrow_pda)?;
@@ -61,6 +65,10 @@ impl<'a> EscrowTokWithdraw<'a> {
     if maker.address().ne(escrow.maker()) {
       return Ee::OnlyMaker.e();
     }
+    //only the Config that counted this offer at make gets it uncounted
+    if config_pda.address().ne(escrow.config()) {
+      return Ee::ConfigPDA.e();
+    }
     if escrow.mint_x().ne(mint_x.address()) {
       return Ee::EscrowMintX.e();
     }
@@ -70,18 +78,16 @@ impl<'a> EscrowTokWithdraw<'a> {
 
     let decimal_x = escrow.decimal_x();
     log!("decimal_: {}", decimal_x);
-    let amount_y = escrow.amount_y();
+    let amount_y = escrow.taken_y();
     let decimal_y = escrow.decimal_y();
     log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
     none_zero_u64(amount_y)?;
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
+    check_decimals_iface(mint_x, token_program, decimal_x)?;
+    check_decimals_iface(mint_y, token_program, decimal_y)?;
 
-    let escrow_ata_y_info = TokenAccount::from_account_view(escrow_ata_y)?;
-    if escrow_ata_y_info.amount() < amount_y {
+    if tok_acct_amount(escrow_ata_y)? < amount_y {
       return Ee::EscrowInsuffTokenY.e();
     } //ata_balc(escrow_ata_y, amount_y)?;
-    drop(escrow_ata_y_info);
 
     log!("Check Maker ATA Y");
     if maker_ata_y.is_data_empty() {
@@ -98,8 +104,8 @@ impl<'a> EscrowTokWithdraw<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("maker_ata_y has data");
-      check_ata(maker_ata_y, maker, mint_y)?;
-      rent_exempt_tokacct(maker_ata_y, rent_sysvar)?;
+      check_ata_iface(maker_ata_y, maker, mint_y, token_program)?;
+      rent_exempt_tokacct_iface(maker_ata_y, rent_sysvar, token_program)?;
     }
 
     log!("Make Seed Signer");
@@ -115,20 +121,20 @@ impl<'a> EscrowTokWithdraw<'a> {
     log!("Transfer Token Y to Maker ATA Y");
     //escrow_pda.check_borrow_mut()?;
     //escrow_ata_y.check_borrow_mut()?;
-    pinocchio_token::instructions::TransferChecked {
+    TransferIface {
       from: escrow_ata_y,
       mint: mint_y,
       to: maker_ata_y,
       authority: escrow_pda,
       amount: amount_y,
       decimals: decimal_y,
+      token_program,
+      hook_accounts,
     }
     .invoke_signed(&[seed_signer.clone()])?;
 
     log!("Check Unknown token in Escrow ATA X");
-    let escrow_ata_x_info = TokenAccount::from_account_view(escrow_ata_x)?;
-    let unknown_amt_x = escrow_ata_x_info.amount();
-    drop(escrow_ata_x_info);
+    let unknown_amt_x = tok_acct_amount(escrow_ata_x)?;
     if unknown_amt_x > 0 {
       log!("Found unknown token in Escrow ATA X");
       if maker_ata_x.is_data_empty() {
@@ -145,18 +151,20 @@ impl<'a> EscrowTokWithdraw<'a> {
         //Please upgrade to SPL Token 2022 for immutable owner support
       } else {
         log!("maker_ata_x has data");
-        check_ata(maker_ata_x, maker, mint_x)?;
-        rent_exempt_tokacct(maker_ata_x, rent_sysvar)?;
+        check_ata_iface(maker_ata_x, maker, mint_x, token_program)?;
+        rent_exempt_tokacct_iface(maker_ata_x, rent_sysvar, token_program)?;
       }
 
       log!("Send token x to maker_ata_x");
-      pinocchio_token::instructions::TransferChecked {
+      TransferIface {
         from: escrow_ata_x,
         mint: mint_x,
         to: maker_ata_x,
         authority: escrow_pda,
         amount: unknown_amt_x,
         decimals: decimal_x,
+        token_program,
+        hook_accounts,
       }
       .invoke_signed(&[seed_signer.clone()])?;
     } else {
@@ -166,23 +174,29 @@ impl<'a> EscrowTokWithdraw<'a> {
     log!("Close Escrow ATA Y");
     //escrow_ata_y.check_borrow_mut()?;
     //escrow_pda.check_borrow_mut()?;
-    pinocchio_token::instructions::CloseAccount {
+    CloseAccountIface {
       account: escrow_ata_y,
       authority: escrow_pda,
       destination: maker,
+      token_program,
     }
     .invoke_signed(&[seed_signer.clone()])?;
 
     log!("Close Escrow ATA X");
     //escrow_pda.check_borrow_mut()?;
     //escrow_ata_x.check_borrow_mut()?;
-    pinocchio_token::instructions::CloseAccount {
+    CloseAccountIface {
       account: escrow_ata_x,
       authority: escrow_pda,
       destination: maker,
+      token_program,
     }
     .invoke_signed(&[seed_signer.clone()])?;
 
+    config.sub_open_escrow();
+    MakerIndex::from_account_view(maker_index)?.close_offer();
+    log!("open escrows: {}", config.open_escrows());
+
     log!("Close EscrowPDA 1");
     //set the first byte to 255
     {
@@ -215,7 +229,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
     check_data_len(data, 0)?;
 
-    let [maker, maker_ata_x, maker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar] =
+    let [maker, maker_ata_x, maker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -231,25 +245,29 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
     writable(maker_ata_y)?;
 
     writable(escrow_ata_x)?;
-    check_ata(escrow_ata_x, escrow_pda, mint_x)?;
+    check_ata_iface(escrow_ata_x, escrow_pda, mint_x, token_program)?;
 
     writable(escrow_ata_y)?;
-    check_ata(escrow_ata_y, escrow_pda, mint_y)?;
+    check_ata_iface(escrow_ata_y, escrow_pda, mint_y, token_program)?;
     log!("EscrowTokWithdraw try_from 4");
 
     writable(escrow_pda)?;
     writable(config_pda)?;
+    writable(maker_index)?;
+    if MakerIndex::from_account_view(maker_index)?.maker() != maker.address() {
+      return Err(Ee::MakerIndexPDA.into());
+    }
     if escrow_pda.is_data_empty() {
       return Err(Ee::EscrowDataEmpty.into());
     }
     log!("EscrowTokWithdraw try_from 5");
-    rent_exempt_mint(mint_x, rent_sysvar, 0)?;
-    rent_exempt_mint(mint_y, rent_sysvar, 1)?;
+    rent_exempt_mint_iface(mint_x, rent_sysvar, token_program, 0)?;
+    rent_exempt_mint_iface(mint_y, rent_sysvar, token_program, 1)?;
     //TODO: fee is part of exchange amount
 
     log!("EscrowTokWithdraw try_from 6");
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_mint_iface(mint_x, token_program)?;
+    check_mint_iface(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
 
     Ok(Self {
       maker,
@@ -261,10 +279,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
       mint_y,
       escrow_pda,
       config_pda,
+      maker_index,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
     })
   }
 }
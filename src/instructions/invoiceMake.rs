@@ -0,0 +1,145 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_decimals_iface, check_mint_iface, check_rent_sysvar, check_sysprog, derive_pda2,
+  executable, get_time_i64, instructions::check_signer, none_zero_u64, parse_i64, parse_u64,
+  to32bytes, writable, Config, Ee, Invoice, PROG_ADDR,
+};
+
+/// Config admin or prog_owner issues an Invoice for amount of mint, payable into the vault ATA by payer until due_at. A zero payer lets anyone pay
+pub struct InvoiceMake<'a> {
+  pub issuer: &'a AccountView, //signer
+  pub mint: &'a AccountView,
+  pub invoice_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub decimals: u8,
+  pub id: u64,
+  pub amount: u64,
+  pub due_at: i64,
+  pub payer: &'a [u8; 32],
+}
+impl<'a> InvoiceMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &67;
+
+  pub fn process(self) -> ProgramResult {
+    let InvoiceMake {
+      issuer,
+      mint,
+      invoice_pda,
+      config_pda,
+      token_program: _,
+      system_program: _,
+      rent_sysvar,
+      decimals,
+      id,
+      amount,
+      due_at,
+      payer,
+    } = self;
+    log!("InvoiceMake process()");
+    let id_bytes = &id.to_le_bytes();
+    let (expected_pda, bump) = derive_pda2(issuer.address(), id_bytes, Invoice::SEED)?;
+    if invoice_pda.address() != &expected_pda || invoice_pda.lamports() > 0 {
+      return Ee::InvoicePDA.e();
+    }
+    log!("Make Invoice PDA");
+    let signer_seeds = [
+      Seed::from(Invoice::SEED),
+      Seed::from(issuer.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: issuer,
+      to: invoice_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Invoice::LEN)?,
+      space: Invoice::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let invoice = Invoice::from_account_view(invoice_pda)?;
+    invoice.set_issuer(issuer.address());
+    invoice.set_config(config_pda.address());
+    invoice.set_payer(&Address::new_from_array(*payer));
+    invoice.set_mint(mint.address());
+    invoice.set_amount(amount)?;
+    invoice.set_due_at(due_at);
+    invoice.set_id(id);
+    invoice.set_decimals(decimals);
+    invoice.set_bump(bump);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for InvoiceMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("InvoiceMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [issuer, mint, invoice_pda, config_pda, token_program, system_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(issuer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(issuer)?;
+    writable(invoice_pda)?;
+
+    //1+8+8+8+32: u8 takes 1, u64, u64 and i64 take 24, the payer takes 32 bytes
+    if data.len() != 57 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let decimals = data[0];
+    let id = parse_u64(&data[1..9])?;
+    let amount = parse_u64(&data[9..17])?;
+    let due_at = parse_i64(&data[17..25])?;
+    let payer = to32bytes(&data[25..57])?;
+    log!("id: {}, amount: {}, due_at: {}", id, amount, due_at);
+    none_zero_u64(amount)?;
+    if due_at <= get_time_i64()? {
+      return Err(Ee::InvoiceOverdue.into());
+    }
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    if config.admin().ne(issuer.address()) && config.prog_owner().ne(issuer.address()) {
+      return Err(Ee::OnlyAdmin.into());
+    }
+    if !config.mints().contains(&mint.address()) {
+      return Err(Ee::MintNotAccepted.into());
+    }
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    Ok(Self {
+      issuer,
+      mint,
+      invoice_pda,
+      config_pda,
+      token_program,
+      system_program,
+      rent_sysvar,
+      decimals,
+      id,
+      amount,
+      due_at,
+      payer,
+    })
+  }
+}
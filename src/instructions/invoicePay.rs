@@ -0,0 +1,156 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar,
+  check_sysprog, check_vault, derive_pda2, emit_event, executable, get_time_i64,
+  instructions::check_signer, net_received, rent_exempt_tokacct_iface, tok_acct_amount, writable,
+  Config, Ee, Invoice, TransferIface,
+};
+
+/// Pay an Invoice into the vault ATA like TokLgcPay, then mark it paid and emit an InvoicePaid receipt event keyed by the issuer and invoice id. Serves both Token and Token2022 via token_program
+pub struct InvoicePay<'a> {
+  pub payer: &'a AccountView, //signer
+  pub payer_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub invoice_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> InvoicePay<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &68;
+
+  pub fn process(self) -> ProgramResult {
+    let InvoicePay {
+      payer,
+      payer_ata,
+      vault_ata,
+      vault,
+      mint,
+      invoice_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("InvoicePay process()");
+    let invoice = Invoice::from_account_view(invoice_pda)?;
+    //other PDAs of this program can have the Invoice length, so the address must come from the Invoice seeds
+    let (expected_pda, bump) =
+      derive_pda2(invoice.issuer(), &invoice.id().to_le_bytes(), Invoice::SEED)?;
+    if invoice_pda.address() != &expected_pda || invoice.bump() != bump {
+      return Ee::InvoicePDA.e();
+    }
+    if invoice.mint().ne(mint.address()) {
+      return Ee::InvoicePDA.e();
+    }
+    //only the Config of the issuer receives the payment into its vault
+    if config_pda.address().ne(invoice.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    let time = get_time_i64()?;
+    invoice.check_payable(payer.address(), time)?;
+    let amount = invoice.amount();
+    log!("invoice id: {}, amount: {}", invoice.id(), amount);
+    check_ata_iface(payer_ata, payer, mint, token_program)?;
+    ata_balc_iface(payer_ata, amount, token_program)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    check_vault(vault, config.vault())?;
+
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: payer,
+        account: vault_ata,
+        wallet: vault,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(vault_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(vault_ata, rent_sysvar, token_program)?;
+    }
+
+    let balc_before = tok_acct_amount(vault_ata)?;
+    TransferIface {
+      from: payer_ata,
+      mint,
+      to: vault_ata,
+      authority: payer,
+      amount,
+      decimals: invoice.decimals(),
+      token_program,
+      hook_accounts,
+    }
+    .invoke()?;
+
+    //record what the vault received, which can be less than amount after transfer fees
+    let received = net_received(vault_ata, balc_before)?;
+    config.add_token_balance(mint.address(), received)?;
+    invoice.set_paid(payer.address(), received, time);
+
+    emit_event(&[
+      Invoice::PAID_EVENT,
+      invoice.issuer().as_ref(),
+      &invoice.id().to_le_bytes(),
+      payer.address().as_ref(),
+      mint.address().as_ref(),
+      &amount.to_le_bytes(),
+      &received.to_le_bytes(),
+      &time.to_le_bytes(),
+    ]);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for InvoicePay<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("InvoicePay try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [payer, payer_ata, vault_ata, vault, mint, invoice_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(payer_ata)?;
+    writable(vault_ata)?;
+    writable(invoice_pda)?;
+    writable(config_pda)?;
+    Ok(Self {
+      payer,
+      payer_ata,
+      vault_ata,
+      vault,
+      mint,
+      invoice_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
@@ -0,0 +1,180 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_data_len, check_rent_sysvar, check_sysprog, check_vault_exists, derive_pda2, get_time,
+  instructions::check_signer, none_zero_u64, parse_u32, parse_u64, sol_balc, sync_vault_yield,
+  writable, Ee, Vault, VaultLock, PROG_ADDR,
+};
+
+/// Deposit SOL into a user's vault, locked with an unlock time or a cliff-plus-linear vesting schedule. The funder makes the vault if the user has none. The user co-signs, so nobody can take the user's one lock slot with a lock they never agreed to
+pub struct LockSol<'a> {
+  pub funder: &'a AccountView, //signer
+  pub user: &'a AccountView,   //signer
+  pub vault: &'a AccountView,
+  pub lock: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub yield_accounts: &'a [AccountView], //optional YieldPool and Config PDA
+  pub amount: u64,
+  pub cliff_at: u32,
+  pub end_at: u32,
+}
+impl<'a> LockSol<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &28;
+
+  pub fn process(self) -> ProgramResult {
+    let LockSol {
+      funder,
+      user,
+      vault,
+      lock,
+      system_program: _,
+      rent_sysvar,
+      yield_accounts,
+      amount,
+      cliff_at,
+      end_at,
+    } = self;
+    log!("LockSol process()");
+    make_vault_lock(funder, user, &VaultLock::SOL_MINT, lock, rent_sysvar)?;
+    let state = VaultLock::from_account_view(lock)?;
+    state.set_schedule(get_time()?, cliff_at, end_at)?;
+    state.set_total(amount);
+
+    check_vault_exists(funder, user, vault, rent_sysvar)?;
+    let vault_state = Vault::from_account_view(vault)?;
+    sync_vault_yield(vault_state, yield_accounts)?;
+    SystemTransfer {
+      from: funder,
+      to: vault,
+      lamports: amount,
+    }
+    .invoke()?;
+    let principal = vault_state
+      .principal()
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    vault_state.set_principal(principal);
+    log!("{} lamports locked until {}", amount, end_at);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for LockSol<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("LockSol try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [funder, user, vault, lock, system_program, rent_sysvar, yield_accounts @ ..] = accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    check_signer(user)?;
+    writable(vault)?;
+    writable(lock)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    //8+4+4: u64 takes 8, 2x u32 take 8 bytes
+    check_data_len(data, 16)?;
+    let amount = parse_u64(&data[0..8])?;
+    let cliff_at = parse_u32(&data[8..12])?;
+    let end_at = parse_u32(&data[12..16])?;
+    log!(
+      "amount: {}, cliff_at: {}, end_at: {}",
+      amount,
+      cliff_at,
+      end_at
+    );
+    none_zero_u64(amount)?;
+    sol_balc(funder, amount)?;
+
+    Ok(Self {
+      funder,
+      user,
+      vault,
+      lock,
+      system_program,
+      rent_sysvar,
+      yield_accounts,
+      amount,
+      cliff_at,
+      end_at,
+    })
+  }
+}
+
+/// Make the VaultLock PDA for user and mint. One open lock per user and mint
+pub fn make_vault_lock(
+  funder: &AccountView,
+  user: &AccountView,
+  mint: &[u8; 32],
+  lock: &AccountView,
+  rent_sysvar: &AccountView,
+) -> ProgramResult {
+  let (expected_lock, bump) = derive_pda2(user.address(), mint, VaultLock::SEED)?;
+  if lock.address() != &expected_lock {
+    return Ee::LockPDA.e();
+  }
+  if lock.lamports() > 0 {
+    return Ee::LockExists.e();
+  }
+  log!("Make VaultLock");
+  let signer_seeds = [
+    Seed::from(VaultLock::SEED),
+    Seed::from(user.address().as_ref()),
+    Seed::from(mint.as_ref()),
+    Seed::from(core::slice::from_ref(&bump)),
+  ];
+  let seed_signer = Signer::from(&signer_seeds);
+
+  let rent = Rent::from_account_view(rent_sysvar)?;
+  pinocchio_system::instructions::CreateAccount {
+    from: funder,
+    to: lock,
+    lamports: rent.try_minimum_balance(VaultLock::LEN)?,
+    space: VaultLock::LEN as u64,
+    owner: &PROG_ADDR,
+  }
+  .invoke_signed(&[seed_signer])?;
+
+  let state = VaultLock::from_account_view(lock)?;
+  state.set_user(user.address());
+  state.set_mint(&Address::new_from_array(*mint));
+  state.set_funder(funder.address());
+  state.set_bump(bump);
+  Ok(())
+}
+
+/// A withdrawal from user's vault must leave what the user's VaultLock for mint still locks. lock is found by its address, so it cannot be left out. Without a lock account nothing is locked
+pub fn check_vault_lock(
+  user: &Address,
+  mint: &[u8; 32],
+  lock: &AccountView,
+  balance_after: u64,
+) -> ProgramResult {
+  let (expected_lock, _bump) = derive_pda2(user, mint, VaultLock::SEED)?;
+  if lock.address() != &expected_lock {
+    return Ee::LockPDA.e();
+  }
+  if lock.lamports() == 0 {
+    return Ok(());
+  }
+  let locked = VaultLock::from_account_view(lock)?.locked(get_time()?)?;
+  log!("locked: {}, balance after: {}", locked, balance_after);
+  if balance_after < locked {
+    return Ee::LockedAmount.e();
+  }
+  Ok(())
+}
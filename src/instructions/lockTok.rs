@@ -0,0 +1,168 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_pda, check_rent_sysvar, check_sysprog, derive_pda1, executable, get_time,
+  instructions::check_signer, make_vault_lock, net_received, none_zero_u64, parse_u32, parse_u64,
+  rent_exempt_mint_iface, rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee,
+  TransferIface, VaultLock, VAULT_SEED,
+};
+
+/// Deposit tokens into a user's vault ATA, locked with an unlock time or a cliff-plus-linear vesting schedule. Serves both Token and Token2022 via token_program. The user co-signs, as in LockSol
+pub struct LockTok<'a> {
+  pub funder: &'a AccountView, //signer
+  pub from_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub lock: &'a AccountView,
+  pub user: &'a AccountView, //signer
+  pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+  pub decimals: u8,
+  pub amount: u64,
+  pub cliff_at: u32,
+  pub end_at: u32,
+}
+impl<'a> LockTok<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &29;
+
+  pub fn process(self) -> ProgramResult {
+    let LockTok {
+      funder,
+      from_ata,
+      vault_ata,
+      vault,
+      lock,
+      user,
+      mint,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+      decimals,
+      amount,
+      cliff_at,
+      end_at,
+    } = self;
+    log!("LockTok process()");
+    make_vault_lock(funder, user, mint.address().as_array(), lock, rent_sysvar)?;
+
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: funder,
+        account: vault_ata,
+        wallet: vault,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(vault_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(vault_ata, rent_sysvar, token_program)?;
+    }
+
+    let balc_before = tok_acct_amount(vault_ata)?;
+    TransferIface {
+      from: from_ata,
+      mint,
+      to: vault_ata,
+      authority: funder,
+      amount,
+      decimals,
+      token_program,
+      hook_accounts,
+    }
+    .invoke()?;
+    //the lock covers what the vault received, which can be less than amount after transfer fees
+    let received = net_received(vault_ata, balc_before)?;
+    none_zero_u64(received)?;
+    Config::from_account_view(config_pda)?.add_token_balance(mint.address(), received)?;
+
+    let state = VaultLock::from_account_view(lock)?;
+    state.set_schedule(get_time()?, cliff_at, end_at)?;
+    state.set_total(received);
+    log!("{} tokens locked until {}", received, end_at);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for LockTok<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("LockTok try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [funder, from_ata, vault_ata, vault, lock, user, mint, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    check_signer(user)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    writable(from_ata)?;
+    check_ata_iface(from_ata, funder, mint, token_program)?;
+    writable(vault_ata)?;
+    writable(lock)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    Config::from_account_view(config_pda)?.check_address(config_pda)?;
+
+    let (expected_vault, _bump) = derive_pda1(user.address(), VAULT_SEED)?;
+    if vault.address() != &expected_vault {
+      return Err(Ee::VaultPDA.into());
+    }
+
+    //1+8+4+4: u8 takes 1, u64 takes 8, 2x u32 take 8 bytes
+    check_data_len(data, 17)?;
+    let decimals = data[0];
+    let amount = parse_u64(&data[1..9])?;
+    let cliff_at = parse_u32(&data[9..13])?;
+    let end_at = parse_u32(&data[13..17])?;
+    log!("decimals: {}, amount: {}", decimals, amount);
+    log!("cliff_at: {}, end_at: {}", cliff_at, end_at);
+
+    none_zero_u64(amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
+
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+
+    Ok(Self {
+      funder,
+      from_ata,
+      vault_ata,
+      vault,
+      lock,
+      user,
+      mint,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+      decimals,
+      amount,
+      cliff_at,
+      end_at,
+    })
+  }
+}
@@ -0,0 +1,151 @@
+use pinocchio::Address;
+
+//Leaves and nodes are hashed with different prefixes so a node can never pass as a leaf. Each pair is hashed in sorted order, so a proof is only the sibling hashes from leaf to root
+pub const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+pub const MERKLE_NODE_PREFIX: &[u8] = &[1];
+
+/// sha256 of the concatenated vals: the sol_sha256 syscall on chain, sha2-const-stable off chain
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+  #[cfg(target_os = "solana")]
+  {
+    let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+    // SAFETY: sol_sha256 reads vals as a slice of byte slices and always fills all 32 bytes
+    unsafe {
+      pinocchio::syscalls::sol_sha256(
+        vals.as_ptr() as *const u8,
+        vals.len() as u64,
+        hash.as_mut_ptr() as *mut u8,
+      );
+      hash.assume_init()
+    }
+  }
+  #[cfg(not(target_os = "solana"))]
+  {
+    let mut hasher = sha2_const_stable::Sha256::new();
+    for val in vals {
+      hasher = hasher.update(val);
+    }
+    hasher.finalize()
+  }
+}
+/// Leaf of a Distributor tree: a claimant may claim amount at index once
+pub fn claim_leaf(index: u32, claimant: &Address, amount: u64) -> [u8; 32] {
+  hashv(&[
+    MERKLE_LEAF_PREFIX,
+    &index.to_le_bytes(),
+    claimant.as_ref(),
+    &amount.to_le_bytes(),
+  ])
+}
+pub fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+  if a <= b {
+    hashv(&[MERKLE_NODE_PREFIX, a, b])
+  } else {
+    hashv(&[MERKLE_NODE_PREFIX, b, a])
+  }
+}
+/// proof is the sibling hashes packed as 32 bytes each
+pub fn verify_proof(proof: &[u8], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+  if !proof.len().is_multiple_of(32) {
+    return false;
+  }
+  let computed = proof.chunks_exact(32).fold(leaf, |hash, sibling| {
+    let mut sibling_arr = [0u8; 32];
+    sibling_arr.copy_from_slice(sibling);
+    merkle_node(&hash, &sibling_arr)
+  });
+  computed == *root
+}
+
+/// Off-chain helper to build a Distributor tree from its claim leaves and get the root and proofs. An odd node at the end of a level moves up unpaired
+#[cfg(not(target_os = "solana"))]
+pub struct MerkleTree {
+  levels: alloc::vec::Vec<alloc::vec::Vec<[u8; 32]>>,
+}
+#[cfg(not(target_os = "solana"))]
+impl MerkleTree {
+  pub fn new(leaves: alloc::vec::Vec<[u8; 32]>) -> Self {
+    let mut levels = alloc::vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+      let next = levels[levels.len() - 1]
+        .chunks(2)
+        .map(|pair| match pair {
+          [a, b] => merkle_node(a, b),
+          [a] => *a,
+          _ => unreachable!(),
+        })
+        .collect();
+      levels.push(next);
+    }
+    Self { levels }
+  }
+  /// from (index, claimant, amount) of each claim
+  pub fn from_claims(claims: &[(u32, Address, u64)]) -> Self {
+    Self::new(
+      claims
+        .iter()
+        .map(|(index, claimant, amount)| claim_leaf(*index, claimant, *amount))
+        .collect(),
+    )
+  }
+  /// zeros for an empty tree
+  pub fn root(&self) -> [u8; 32] {
+    self.levels[self.levels.len() - 1]
+      .first()
+      .copied()
+      .unwrap_or([0u8; 32])
+  }
+  /// sibling hashes of the leaf at index, packed for DistributorClaim instruction data
+  pub fn proof(&self, index: usize) -> alloc::vec::Vec<u8> {
+    let mut proof = alloc::vec::Vec::new();
+    let mut i = index;
+    for level in &self.levels[..self.levels.len() - 1] {
+      if let Some(sibling) = level.get(i ^ 1) {
+        proof.extend_from_slice(sibling);
+      }
+      i /= 2;
+    }
+    proof
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn claims(n: u32) -> alloc::vec::Vec<(u32, Address, u64)> {
+    (0..n)
+      .map(|i| {
+        let claimant = Address::new_from_array([i as u8 + 1; 32]);
+        (i, claimant, 100 + i as u64)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_proof_verifies_every_leaf() {
+    //odd leaf counts move the last node up unpaired
+    for n in [1, 2, 3, 5, 7, 8] {
+      let claims = claims(n);
+      let tree = MerkleTree::from_claims(&claims);
+      let root = tree.root();
+      for (index, claimant, amount) in &claims {
+        let proof = tree.proof(*index as usize);
+        let leaf = claim_leaf(*index, claimant, *amount);
+        assert!(verify_proof(&proof, &root, leaf), "n {} index {}", n, index);
+        let wrong_leaf = claim_leaf(*index, claimant, *amount + 1);
+        assert!(!verify_proof(&proof, &root, wrong_leaf));
+      }
+    }
+  }
+
+  #[test]
+  fn test_proof_rejects_partial_hash() {
+    let claims = claims(3);
+    let tree = MerkleTree::from_claims(&claims);
+    let (index, claimant, amount) = &claims[0];
+    let proof = tree.proof(0);
+    let leaf = claim_leaf(*index, claimant, *amount);
+    assert!(!verify_proof(&proof[..proof.len() - 1], &tree.root(), leaf));
+  }
+}
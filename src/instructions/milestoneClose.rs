@@ -0,0 +1,175 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog, close_pda,
+  derive_ata, executable, get_time_i64, instructions::check_signer, rent_exempt_tokacct_iface,
+  tok_acct_amount, writable, CloseAccountIface, Config, Ee, MakerIndex, Milestone, TransferIface,
+};
+
+/// Payer closes a Milestone escrow once every milestone is settled, or after the deadline. What the Milestone ATA still holds reverts to the payer
+pub struct MilestoneClose<'a> {
+  pub payer: &'a AccountView, //signer
+  pub payer_ata: &'a AccountView,
+  pub milestone_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub milestone_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+}
+impl<'a> MilestoneClose<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &57;
+
+  pub fn process(self) -> ProgramResult {
+    let MilestoneClose {
+      payer,
+      payer_ata,
+      milestone_ata,
+      mint,
+      milestone_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+    } = self;
+    log!("MilestoneClose process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    let milestone = Milestone::from_account_view(milestone_pda)?;
+    if payer.address().ne(milestone.payer()) {
+      return Ee::OnlyMaker.e();
+    }
+    //only the Config that counted this escrow at make gets it uncounted
+    if config_pda.address().ne(milestone.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    if milestone.mint().ne(mint.address()) {
+      return Ee::EscrowMintX.e();
+    }
+    log!("open milestones: {}", milestone.open_count());
+    if milestone.open_count() > 0 && get_time_i64()? < milestone.deadline() {
+      return Ee::MilestoneDeadline.e();
+    }
+    //an empty decoy must not leave tokens stranded in the real Milestone ATA
+    if milestone_ata.address()
+      != &derive_ata(
+        milestone_pda.address(),
+        mint.address(),
+        token_program.address(),
+      )?
+    {
+      return Ee::AtaOrOwner.e();
+    }
+
+    let id_bytes = &milestone.id().to_le_bytes();
+    let bump = milestone.bump();
+    let signer_seeds = [
+      Seed::from(Milestone::SEED),
+      Seed::from(payer.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    let amount = tok_acct_amount(milestone_ata)?;
+    log!("Milestone ATA amount: {}", amount);
+    if amount > 0 {
+      log!("Refund the payer");
+      if payer_ata.is_data_empty() {
+        log!("Make payer_ata");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: payer,
+          account: payer_ata,
+          wallet: payer,
+          mint,
+          system_program,
+          token_program,
+        }
+        .invoke()?;
+      } else {
+        check_ata_iface(payer_ata, payer, mint, token_program)?;
+        rent_exempt_tokacct_iface(payer_ata, rent_sysvar, token_program)?;
+      }
+      TransferIface {
+        from: milestone_ata,
+        mint,
+        to: payer_ata,
+        authority: milestone_pda,
+        amount,
+        decimals: milestone.decimals(),
+        token_program,
+        hook_accounts: &[],
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    }
+    CloseAccountIface {
+      account: milestone_ata,
+      authority: milestone_pda,
+      destination: payer,
+      token_program,
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    config.sub_open_escrow();
+    MakerIndex::from_account_view(maker_index)?.close_offer();
+    log!("open escrows: {}", config.open_escrows());
+
+    log!("Close Milestone PDA");
+    close_pda(milestone_pda, payer)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MilestoneClose<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MilestoneClose try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [payer, payer_ata, milestone_ata, mint, milestone_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(payer)?;
+    writable(payer_ata)?;
+    writable(milestone_ata)?;
+    writable(milestone_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+    if MakerIndex::from_account_view(maker_index)?.maker() != payer.address() {
+      return Err(Ee::MakerIndexPDA.into());
+    }
+    Ok(Self {
+      payer,
+      payer_ata,
+      milestone_ata,
+      mint,
+      milestone_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+    })
+  }
+}
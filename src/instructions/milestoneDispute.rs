@@ -0,0 +1,58 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{check_data_len, get_time_i64, instructions::check_signer, writable, Ee, Milestone};
+
+/// The payer or the payee disputes a milestone before the deadline. Only the arbiter can release a disputed milestone until then
+pub struct MilestoneDispute<'a> {
+  pub signer: &'a AccountView, //signer
+  pub milestone_pda: &'a AccountView,
+  pub index: u8,
+}
+impl<'a> MilestoneDispute<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &56;
+
+  pub fn process(self) -> ProgramResult {
+    let MilestoneDispute {
+      signer,
+      milestone_pda,
+      index,
+    } = self;
+    log!("MilestoneDispute process()");
+    let milestone = Milestone::from_account_view(milestone_pda)?;
+    if signer.address().ne(milestone.payer()) && signer.address().ne(milestone.payee()) {
+      return Ee::MilestoneAuth.e();
+    }
+    if get_time_i64()? >= milestone.deadline() {
+      return Ee::MilestoneDeadline.e();
+    }
+    milestone.dispute(index)?;
+    log!("disputed: {}", milestone.disputed());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MilestoneDispute<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MilestoneDispute try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //u8 takes 1 byte
+    check_data_len(data, 1)?;
+
+    let [signer, milestone_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(milestone_pda)?;
+    let index = data[0];
+    log!("index: {}", index);
+    Ok(Self {
+      signer,
+      milestone_pda,
+      index,
+    })
+  }
+}
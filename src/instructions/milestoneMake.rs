@@ -0,0 +1,234 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_decimals_iface, check_mint_iface,
+  check_rent_sysvar, check_sysprog, executable, get_time_i64, instructions::check_signer,
+  make_maker_index, parse_i64, parse_u64, rent_exempt_tokacct_iface, writable, Config, Ee,
+  MakerIndex, Milestone, TransferIface, ID, MAX_MILESTONES, PROG_ADDR,
+};
+
+/// Make a Milestone payment escrow: the payer locks the sum of the milestone amounts for the payee, with an arbiter for disputes. id 0 takes the next id from the payer's MakerIndex. TransferHook mints are not supported
+pub struct MilestoneMake<'a> {
+  pub payer: &'a AccountView, //signer
+  pub payer_ata: &'a AccountView,
+  pub milestone_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub payee: &'a AccountView,
+  pub arbiter: &'a AccountView,
+  pub milestone_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub maker_index: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub decimals: u8,
+  pub deadline: i64,
+  pub id: u64,
+  pub amounts: [u64; MAX_MILESTONES],
+  pub count: usize,
+  pub total: u64,
+}
+impl<'a> MilestoneMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &54;
+
+  pub fn process(self) -> ProgramResult {
+    let MilestoneMake {
+      payer,
+      payer_ata,
+      milestone_ata,
+      mint,
+      payee,
+      arbiter,
+      milestone_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      decimals,
+      deadline,
+      id,
+      amounts,
+      count,
+      total,
+    } = self;
+    log!("MilestoneMake process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    if deadline <= get_time_i64()? {
+      return Ee::MilestoneDeadline.e();
+    }
+    make_maker_index(payer, maker_index, rent_sysvar)?;
+    let index = MakerIndex::from_account_view(maker_index)?;
+    let id = index.allocate(id)?;
+    log!("allocated id: {}, open offers: {}", id, index.open_count());
+
+    let seed = [
+      Milestone::SEED,
+      payer.address().as_array(),
+      &id.to_le_bytes(),
+    ];
+    let (expected_milestone, bump) = Address::find_program_address(&seed[..], &ID.into());
+    if expected_milestone.ne(milestone_pda.address()) || milestone_pda.lamports() > 0 {
+      return Ee::MilestonePDA.e();
+    }
+    log!("Make Milestone PDA");
+    let id_bytes = &id.to_le_bytes();
+    let seeds = [
+      Seed::from(Milestone::SEED),
+      Seed::from(payer.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: payer,
+      to: milestone_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Milestone::LEN)?,
+      space: Milestone::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    if milestone_ata.is_data_empty() {
+      log!("Make milestone_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: payer,
+        account: milestone_ata,
+        wallet: milestone_pda,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("milestone_ata has data");
+      check_ata_iface(milestone_ata, milestone_pda, mint, token_program)?;
+      rent_exempt_tokacct_iface(milestone_ata, rent_sysvar, token_program)?;
+    }
+    TransferIface {
+      from: payer_ata,
+      mint,
+      to: milestone_ata,
+      authority: payer,
+      amount: total,
+      decimals,
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke()?;
+
+    let milestone = Milestone::from_account_view(milestone_pda)?;
+    milestone.set_payer(payer.address());
+    milestone.set_config(config_pda.address());
+    milestone.set_payee(payee.address());
+    milestone.set_arbiter(arbiter.address());
+    milestone.set_mint(mint.address());
+    milestone.set_amounts(&amounts[..count])?;
+    milestone.set_deadline(deadline);
+    milestone.set_id(id);
+    milestone.set_decimals(decimals);
+    milestone.set_bump(bump);
+    config.add_open_escrow()?;
+    log!("open escrows: {}", config.open_escrows());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MilestoneMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MilestoneMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [payer, payer_ata, milestone_ata, mint, payee, arbiter, milestone_pda, config_pda, maker_index, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(payer_ata)?;
+    writable(milestone_ata)?;
+    writable(milestone_pda)?;
+    writable(config_pda)?;
+    writable(maker_index)?;
+
+    //1+8+8+1: u8 takes 1, i64 and u64 take 16, count u8 takes 1, then u64 per milestone
+    if data.len() < 18 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let decimals = data[0];
+    let deadline = parse_i64(&data[1..9])?;
+    let id = parse_u64(&data[9..17])?;
+    let count = data[17] as usize;
+    if count == 0 || count > MAX_MILESTONES {
+      return Err(Ee::MilestoneIndex.into());
+    }
+    if data.len() != 18 + count * 8 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let mut amounts = [0u64; MAX_MILESTONES];
+    let mut total: u64 = 0;
+    for (i, amount) in amounts.iter_mut().take(count).enumerate() {
+      *amount = parse_u64(&data[18 + i * 8..26 + i * 8])?;
+      total = total
+        .checked_add(*amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    log!(
+      "deadline: {}, id: {}, count: {}, total: {}",
+      deadline,
+      id,
+      count,
+      total
+    );
+    //the arbiter must be neither party
+    if payee.address() == payer.address()
+      || arbiter.address() == payer.address()
+      || arbiter.address() == payee.address()
+    {
+      return Err(Ee::MilestoneAuth.into());
+    }
+
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    check_ata_iface(payer_ata, payer, mint, token_program)?;
+    ata_balc_iface(payer_ata, total, token_program)?;
+    Ok(Self {
+      payer,
+      payer_ata,
+      milestone_ata,
+      mint,
+      payee,
+      arbiter,
+      milestone_pda,
+      config_pda,
+      maker_index,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      decimals,
+      deadline,
+      id,
+      amounts,
+      count,
+      total,
+    })
+  }
+}
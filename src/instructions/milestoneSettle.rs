@@ -0,0 +1,189 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  derive_ata, executable, get_time_i64, instructions::check_signer, rent_exempt_tokacct_iface,
+  tok_acct_amount, u8_to_bool, writable, Ee, Milestone, TransferIface,
+};
+
+/// Settle one milestone: release it to the payee, or refund it to the payer. Before the deadline, the payer releases an undisputed milestone, the payee refunds, and the arbiter does either. After the deadline only the payer settles
+pub struct MilestoneSettle<'a> {
+  pub signer: &'a AccountView, //signer
+  pub payer: &'a AccountView,
+  pub recipient: &'a AccountView,
+  pub recipient_ata: &'a AccountView,
+  pub milestone_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub milestone_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub index: u8,
+  pub release: bool,
+}
+impl<'a> MilestoneSettle<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &55;
+
+  pub fn process(self) -> ProgramResult {
+    let MilestoneSettle {
+      signer,
+      payer,
+      recipient,
+      recipient_ata,
+      milestone_ata,
+      mint,
+      milestone_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      index,
+      release,
+    } = self;
+    log!("MilestoneSettle process()");
+    let milestone = Milestone::from_account_view(milestone_pda)?;
+    if payer.address().ne(milestone.payer()) {
+      return Ee::OnlyMaker.e();
+    }
+    if milestone.mint().ne(mint.address()) {
+      return Ee::EscrowMintX.e();
+    }
+    milestone.check_open(index)?;
+
+    let past_deadline = get_time_i64()? >= milestone.deadline();
+    let is_payer = signer.address() == milestone.payer();
+    let is_payee = signer.address() == milestone.payee();
+    let is_arbiter = signer.address() == milestone.arbiter();
+    if past_deadline && !is_payer {
+      return Ee::MilestoneDeadline.e();
+    }
+    let allowed = if release {
+      is_arbiter || (is_payer && (past_deadline || !milestone.is_disputed(index)))
+    } else {
+      is_arbiter || is_payee || (is_payer && past_deadline)
+    };
+    if !allowed {
+      return Ee::MilestoneAuth.e();
+    }
+    let expected_recipient = if release {
+      milestone.payee()
+    } else {
+      milestone.payer()
+    };
+    if recipient.address().ne(expected_recipient) {
+      return Ee::MilestoneAuth.e();
+    }
+    if milestone_ata.address()
+      != &derive_ata(
+        milestone_pda.address(),
+        mint.address(),
+        token_program.address(),
+      )?
+    {
+      return Ee::AtaOrOwner.e();
+    }
+
+    let balance = tok_acct_amount(milestone_ata)?;
+    //the last open milestone takes what is left, which can be less after transfer fees
+    let amount = if milestone.open_count() == 1 {
+      balance
+    } else {
+      milestone.amount(index)?.min(balance)
+    };
+    log!(
+      "milestone {}, release: {}, amount: {}",
+      index,
+      release as u8,
+      amount
+    );
+    milestone.settle(index, release)?;
+
+    if recipient_ata.is_data_empty() {
+      log!("Make recipient_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: signer,
+        account: recipient_ata,
+        wallet: recipient,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(recipient_ata, recipient, mint, token_program)?;
+    }
+    rent_exempt_tokacct_iface(recipient_ata, rent_sysvar, token_program)?;
+    let id_bytes = &milestone.id().to_le_bytes();
+    let bump = milestone.bump();
+    let signer_seeds = [
+      Seed::from(Milestone::SEED),
+      Seed::from(payer.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    TransferIface {
+      from: milestone_ata,
+      mint,
+      to: recipient_ata,
+      authority: milestone_pda,
+      amount,
+      decimals: milestone.decimals(),
+      token_program,
+      hook_accounts: &[],
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MilestoneSettle<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MilestoneSettle try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //2x u8 take 2 bytes
+    check_data_len(data, 2)?;
+
+    let [signer, payer, recipient, recipient_ata, milestone_ata, mint, milestone_pda, token_program, system_program, atoken_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(recipient_ata)?;
+    writable(milestone_ata)?;
+    writable(milestone_pda)?;
+
+    let index = data[0];
+    let release = u8_to_bool(data[1])?;
+    log!("index: {}, release: {}", index, release as u8);
+    Ok(Self {
+      signer,
+      payer,
+      recipient,
+      recipient_ata,
+      milestone_ata,
+      mint,
+      milestone_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      index,
+      release,
+    })
+  }
+}
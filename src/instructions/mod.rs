@@ -1,16 +1,56 @@
 //---------------== Module Declaration
 //declare your new function mod here to be included into this project, then export it via "pub use"
 #[allow(non_snake_case)]
+pub mod auctionBid;
+#[allow(non_snake_case)]
+pub mod auctionBidWithdraw;
+#[allow(non_snake_case)]
+pub mod auctionMake;
+#[allow(non_snake_case)]
+pub mod auctionSettle;
+#[allow(non_snake_case)]
+pub mod basketClose;
+#[allow(non_snake_case)]
+pub mod basketMake;
+#[allow(non_snake_case)]
+pub mod basketTake;
+#[allow(non_snake_case)]
 pub mod config2Update;
 #[allow(non_snake_case)]
 pub mod configClose;
 #[allow(non_snake_case)]
 pub mod configInit;
 #[allow(non_snake_case)]
+pub mod configMigrate;
+#[allow(non_snake_case)]
 pub mod configResize;
 #[allow(non_snake_case)]
 pub mod configUpdate;
 #[allow(non_snake_case)]
+pub mod delegateRevoke;
+#[allow(non_snake_case)]
+pub mod delegateSet;
+#[allow(non_snake_case)]
+pub mod delegateWithdrawSol;
+#[allow(non_snake_case)]
+pub mod delegateWithdrawTok;
+#[allow(non_snake_case)]
+pub mod distributorClaim;
+#[allow(non_snake_case)]
+pub mod distributorClawback;
+#[allow(non_snake_case)]
+pub mod distributorMake;
+#[allow(non_snake_case)]
+pub mod escrowMigrate;
+#[allow(non_snake_case)]
+pub mod escrowSolClose;
+#[allow(non_snake_case)]
+pub mod escrowSolMake;
+#[allow(non_snake_case)]
+pub mod escrowSolTake;
+#[allow(non_snake_case)]
+pub mod escrowTokAmend;
+#[allow(non_snake_case)]
 pub mod escrowTokCancel;
 #[allow(non_snake_case)]
 pub mod escrowTokMake;
@@ -19,18 +59,71 @@ pub mod escrowTokTake;
 #[allow(non_snake_case)]
 pub mod escrowTokWithdraw;
 #[allow(non_snake_case)]
+pub mod invoiceMake;
+#[allow(non_snake_case)]
+pub mod invoicePay;
+#[allow(non_snake_case)]
+pub mod lockSol;
+#[allow(non_snake_case)]
+pub mod lockTok;
+pub mod merkle;
+#[allow(non_snake_case)]
+pub mod milestoneClose;
+#[allow(non_snake_case)]
+pub mod milestoneDispute;
+#[allow(non_snake_case)]
+pub mod milestoneMake;
+#[allow(non_snake_case)]
+pub mod milestoneSettle;
+#[allow(non_snake_case)]
+pub mod multisigInit;
+#[allow(non_snake_case)]
 pub mod oraclesRead;
 #[allow(non_snake_case)]
+pub mod proposalApprove;
+#[allow(non_snake_case)]
+pub mod proposalExecute;
+#[allow(non_snake_case)]
+pub mod proposalMake;
+#[allow(non_snake_case)]
 pub mod solDeposit;
 #[allow(non_snake_case)]
 pub mod solWithdraw;
 #[allow(non_snake_case)]
+pub mod streamCancel;
+#[allow(non_snake_case)]
+pub mod streamMake;
+#[allow(non_snake_case)]
+pub mod streamWithdraw;
+#[allow(non_snake_case)]
+pub mod subscriptionCancel;
+#[allow(non_snake_case)]
+pub mod subscriptionMake;
+#[allow(non_snake_case)]
+pub mod subscriptionPull;
+#[allow(non_snake_case)]
+pub mod timelockCancel;
+#[allow(non_snake_case)]
+pub mod timelockExecute;
+#[allow(non_snake_case)]
+pub mod timelockInit;
+#[allow(non_snake_case)]
+pub mod timelockQueue;
+#[allow(non_snake_case)]
+pub mod tok22Ext;
+#[allow(non_snake_case)]
+pub mod tok22HarvestFees;
+#[allow(non_snake_case)]
 pub mod tok22InitATA;
 #[allow(non_snake_case)]
 pub mod tok22InitMint;
 #[allow(non_snake_case)]
 pub mod tok22MintToken;
 #[allow(non_snake_case)]
+pub mod tok22UpdateMetadata;
+#[allow(non_snake_case)]
+pub mod tokIface;
+#[allow(non_snake_case)]
 pub mod tokLgcDeposit;
 #[allow(non_snake_case)]
 pub mod tokLgcInitATA;
@@ -45,25 +138,80 @@ pub mod tokLgcRedeem;
 #[allow(non_snake_case)]
 pub mod tokLgcWithdraw;
 #[allow(non_snake_case)]
+pub mod unlockSol;
+#[allow(non_snake_case)]
+pub mod unlockTok;
+#[allow(non_snake_case)]
 pub mod userInit;
 pub mod utils;
+#[allow(non_snake_case)]
+pub mod yieldClaim;
+#[allow(non_snake_case)]
+pub mod yieldFund;
 
 //file names start with a lower case + Camel cases, but struct names start with Upper case + Camel cases!
+pub use auctionBid::*;
+pub use auctionBidWithdraw::*;
+pub use auctionMake::*;
+pub use auctionSettle::*;
+pub use basketClose::*;
+pub use basketMake::*;
+pub use basketTake::*;
 pub use config2Update::*;
 pub use configClose::*;
 pub use configInit::*;
+pub use configMigrate::*;
 pub use configResize::*;
 pub use configUpdate::*;
+pub use delegateRevoke::*;
+pub use delegateSet::*;
+pub use delegateWithdrawSol::*;
+pub use delegateWithdrawTok::*;
+pub use distributorClaim::*;
+pub use distributorClawback::*;
+pub use distributorMake::*;
+pub use escrowMigrate::*;
+pub use escrowSolClose::*;
+pub use escrowSolMake::*;
+pub use escrowSolTake::*;
+pub use escrowTokAmend::*;
 pub use escrowTokCancel::*;
 pub use escrowTokMake::*;
 pub use escrowTokTake::*;
 pub use escrowTokWithdraw::*;
+pub use invoiceMake::*;
+pub use invoicePay::*;
+pub use lockSol::*;
+pub use lockTok::*;
+pub use merkle::*;
+pub use milestoneClose::*;
+pub use milestoneDispute::*;
+pub use milestoneMake::*;
+pub use milestoneSettle::*;
+pub use multisigInit::*;
 pub use oraclesRead::*;
+pub use proposalApprove::*;
+pub use proposalExecute::*;
+pub use proposalMake::*;
 pub use solDeposit::*;
 pub use solWithdraw::*;
+pub use streamCancel::*;
+pub use streamMake::*;
+pub use streamWithdraw::*;
+pub use subscriptionCancel::*;
+pub use subscriptionMake::*;
+pub use subscriptionPull::*;
+pub use timelockCancel::*;
+pub use timelockExecute::*;
+pub use timelockInit::*;
+pub use timelockQueue::*;
+pub use tok22Ext::*;
+pub use tok22HarvestFees::*;
 pub use tok22InitATA::*;
 pub use tok22InitMint::*;
 pub use tok22MintToken::*;
+pub use tok22UpdateMetadata::*;
+pub use tokIface::*;
 pub use tokLgcDeposit::*;
 pub use tokLgcInitATA::*;
 pub use tokLgcInitMint::*;
@@ -71,8 +219,12 @@ pub use tokLgcMintToken::*;
 pub use tokLgcPay::*;
 pub use tokLgcRedeem::*;
 pub use tokLgcWithdraw::*;
+pub use unlockSol::*;
+pub use unlockTok::*;
 pub use userInit::*;
 pub use utils::*;
+pub use yieldClaim::*;
+pub use yieldFund::*;
 
 use shank::ShankInstruction;
 
@@ -89,12 +241,17 @@ pub enum ProgramIx {
   #[account(1, writable, name = "vault", desc = "VaultPDA")]
   #[account(2, name = "system_program", desc = "System Program")]
   #[account(3, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(4, optional, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(5, optional, name = "config_pda", desc = "Config PDA of the YieldPool")]
   DepositSol { amount: u64 },
 
-  /// 1 Withdraw lamports from the vault
+  /// 1 Withdraw lamports from the vault, leaving what the SOL VaultLock still locks
   #[account(0, signer, writable, name = "user", desc = "User")]
   #[account(1, writable, name = "vault", desc = "Vault PDA")]
   #[account(2, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(3, name = "lock", desc = "SOL VaultLock PDA, empty if none")]
+  #[account(4, optional, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(5, optional, name = "config_pda", desc = "Config PDA of the YieldPool")]
   WithdrawSol { amount: u64 },
 
   /// 2 TokLgc Init Mint
@@ -129,7 +286,7 @@ pub enum ProgramIx {
   #[account(7, name = "rent_sysvar", desc = "RentSysvar")]
   TokLgcMintToken { decimals: u8, amount: u64 },
 
-  /// 5 TokLgc Deposit Tokens
+  /// 5 TokLgc Deposit Tokens. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "user", desc = "User")]
   #[account(1, writable, name = "from", desc = "From ATA")]
   #[account(2, writable, name = "to", desc = "To ATA")]
@@ -141,19 +298,21 @@ pub enum ProgramIx {
   #[account(8, name = "atoken_program", desc = "Associated Token Program")]
   TokLgcDeposit { decimals: u8, amount: u64 },
 
-  /// 6 TokLgc Withdraw Token
+  /// 6 TokLgc Withdraw Token, leaving what the VaultLock for mint still locks. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "user", desc = "User")]
   #[account(1, writable, name = "from", desc = "From ATA")]
   #[account(2, writable, name = "to", desc = "To ATA")]
   #[account(3, name = "from_wallet", desc = "From Wallet")]
   #[account(4, name = "mint", desc = "Mint")]
-  #[account(5, name = "token_program", desc = "Token Program")]
-  #[account(6, name = "system_program", desc = "System Program")]
-  #[account(7, name = "atoken_program", desc = "Associated Token Program")]
-  #[account(8, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, name = "lock", desc = "VaultLock PDA, empty if none")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
   TokLgcWithdraw { decimals: u8, amount: u64 },
 
-  /// 7 TokLgc User Pays Tokens to VaultPDA
+  /// 7 TokLgc User Pays Tokens to VaultPDA. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "user", desc = "User")]
   #[account(1, writable, name = "from", desc = "User ATA")]
   #[account(2, writable, name = "to", desc = "Vault ATA")]
@@ -166,12 +325,12 @@ pub enum ProgramIx {
   #[account(9, name = "rent_sysvar", desc = "RentSysvar")]
   TokLgcPay { decimals: u8, amount: u64 },
 
-  /// 8 TokLgc Redeem Tokens
+  /// 8 TokLgc Redeem Tokens. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "user", desc = "User")]
   #[account(1, writable, name = "from", desc = "From ATA")]
   #[account(2, writable, name = "to", desc = "To ATA")]
   #[account(3, name = "vault", desc = "Vault as From PDA")]
-  #[account(4, name = "config_pda", desc = "Config PDA")]
+  #[account(4, writable, name = "config_pda", desc = "Config PDA")]
   #[account(5, name = "mint", desc = "Mint")]
   #[account(6, name = "token_program", desc = "Token Program")]
   #[account(7, name = "system_program", desc = "System Program")]
@@ -180,10 +339,10 @@ pub enum ProgramIx {
   TokLgcRedeem { decimals: u8, amount: u64 },
 
   //---------== Token2022
-  /// 9 Token2022 Init Mint
+  /// 9 Token2022 Init Mint with MetadataPointer and TokenMetadata extensions
   #[account(0, signer, writable, name = "payer", desc = "Payer")]
   #[account(1, signer, writable, name = "mint", desc = "Mint")]
-  #[account(2, name = "mint_authority", desc = "Mint Authority")]
+  #[account(2, signer, name = "mint_authority", desc = "Mint Authority")]
   #[account(3, name = "token_program", desc = "Token Program")]
   #[account(4, name = "freeze_authority_opt", desc = "Freeze Authority")]
   #[account(5, name = "system_program", desc = "System Program")]
@@ -193,6 +352,13 @@ pub enum ProgramIx {
     token_name: [u8; 10],
     token_symbol: [u8; 6],
     token_uri: [u8; 32],
+    //optional tail; ext_flags bits: 0 TransferFee, 1 InterestBearing, 2 NonTransferable, 3 PermanentDelegate, 4 DefaultAccountState, 5 MintCloseAuthority
+    ext_flags: u8,
+    transfer_fee_bps: u16,
+    maximum_fee: u64,
+    interest_rate: i16,
+    default_state: u8,
+    permanent_delegate: [u8; 32],
   },
 
   /// 10 Token2022 Init ATA(Associated Token Acct)
@@ -242,14 +408,14 @@ pub enum ProgramIx {
     //str_u8: [u8; 32],
   },
 
-  /// 14 Close Config PDA
+  /// 14 Close Config PDA when no escrow is open and the vault ATAs are empty. force skips those checks for the prog_owner. Remaining accounts: [mint, vault_ata] for each config mint
   #[account(0, signer, writable, name = "authority", desc = "Authority")]
   #[account(1, writable, name = "config_pda", desc = "PDA")]
-  #[account(2, name = "dest", desc = "Destination")]
-  CloseConfigPda {},
+  #[account(2, writable, name = "dest", desc = "Destination as prog_owner")]
+  CloseConfigPda { force: bool },
 
   //---------------== Escrow PDA
-  /// 15 Escrow Token Make Offer
+  /// 15 Escrow Token Make Offer. Token or Token2022 by token_program. id 0 takes the next id from the MakerIndex. mode bits: 1 NFT X (0 decimals, supply 1, amount 1), 2 Collection Y (mint_y is a Token2022 group mint; any member NFT fills it), 4 Dutch Y (followed by end_amount_y u64, start_time i64, end_time i64; the asked Y decays linearly), 8 Trigger (followed by oracle_vendor u8, trigger_above u8, trigger_price u64, feed_id [u8;32]; takeable only while the oracle price is past trigger_price). Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "maker", desc = "Maker X")]
   #[account(1, writable, name = "maker_ata_x", desc = "Maker ATA X")]
   #[account(2, writable, name = "escrow_ata_x", desc = "Escrow ATA X")]
@@ -257,18 +423,20 @@ pub enum ProgramIx {
   #[account(4, name = "mint_y", desc = "Mint Y")]
   #[account(5, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(6, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(7, name = "token_program", desc = "Token Program")]
-  #[account(8, name = "system_program", desc = "System Program")]
-  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
-  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(7, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(8, name = "token_program", desc = "Token Program")]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(11, name = "rent_sysvar", desc = "RentSysvar")]
   EscrowTokMake {
     decimal_x: u8,
     amount_x: u64,
     decimal_y: u8,
     amount_y: u64,
     id: u64,
+    mode: u8,
   },
-  /// 16 Escrow Token Take Offer
+  /// 16 Escrow Token Take Offer. Token or Token2022 by token_program. Fails if the offer gives less than min_x, asks more than max_y, or its seq changed. For a Collection Y offer, mint_y is the member NFT delivered. Remaining accounts: the oracle PDA for a Trigger offer, then TransferHook accounts
   #[account(0, signer, writable, name = "taker", desc = "Taker")]
   #[account(1, writable, name = "taker_ata_x", desc = "Taker ATA X")]
   #[account(2, writable, name = "taker_ata_y", desc = "Taker ATA Y")]
@@ -284,12 +452,12 @@ pub enum ProgramIx {
   #[account(12, name = "rent_sysvar", desc = "RentSysvar")]
   EscrowTokTake {
     decimal_x: u8,
-    amount_x: u64,
+    min_x: u64,
     decimal_y: u8,
-    amount_y: u64,
-    id: u64,
+    max_y: u64,
+    seq: u32,
   },
-  /// 17 Maker Withdraws Token Y (and X?)
+  /// 17 Maker Withdraws Token Y (and X?). Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "maker", desc = "Maker")]
   #[account(1, writable, name = "maker_ata_x", desc = "Maker ATA X")]
   #[account(2, writable, name = "maker_ata_y", desc = "Maker ATA Y")]
@@ -299,13 +467,14 @@ pub enum ProgramIx {
   #[account(6, name = "mint_y", desc = "Mint Y")]
   #[account(7, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(8, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(9, name = "token_program", desc = "Token Program")]
-  #[account(10, name = "system_program", desc = "System Program")]
-  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
-  #[account(12, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(9, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(10, name = "token_program", desc = "Token Program")]
+  #[account(11, name = "system_program", desc = "System Program")]
+  #[account(12, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(13, name = "rent_sysvar", desc = "RentSysvar")]
   EscrowTokWithdraw {},
 
-  /// 18 Escrow Token Cancel Offer
+  /// 18 Escrow Token Cancel Offer. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
   #[account(0, signer, writable, name = "maker", desc = "Maker X")]
   #[account(1, writable, name = "maker_ata_x", desc = "Maker ATA X")]
   #[account(2, writable, name = "maker_ata_y", desc = "Maker ATA Y")]
@@ -315,10 +484,11 @@ pub enum ProgramIx {
   #[account(6, name = "mint_y", desc = "Mint Y")]
   #[account(7, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(8, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(9, name = "token_program", desc = "Token Program")]
-  #[account(10, name = "system_program", desc = "System Program")]
-  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
-  #[account(12, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(9, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(10, name = "token_program", desc = "Token Program")]
+  #[account(11, name = "system_program", desc = "System Program")]
+  #[account(12, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(13, name = "rent_sysvar", desc = "RentSysvar")]
   EscrowTokCancel {},
 
   //---------------== Config PDA
@@ -372,6 +542,613 @@ pub enum ProgramIx {
   #[account(4, name = "token_prog", desc = "Token Program")]
   #[account(5, writable, name = "from_ata", desc = "From ATA")]
   FlashloanRepay { flashloan_vendor: u8, amount: u64 },
+
+  //---------== Token2022
+  /// 24 Token2022 Update Metadata Field. field: 0 Name, 1 Symbol, 2 Uri, 3 custom Key
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "update_authority",
+    desc = "Update Authority"
+  )]
+  #[account(1, writable, name = "mint", desc = "Mint")]
+  #[account(2, name = "token_program", desc = "Token Program")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  Token2022UpdateMetadata {
+    field: u8,
+    key: [u8; 10],
+    value: [u8; 32],
+  },
+
+  /// 25 Token2022 Harvest withheld transfer fees into the Vault ATA. Remaining accounts: token accounts to harvest from
+  #[account(
+    0,
+    signer,
+    name = "admin",
+    desc = "Admin as Withdraw Withheld Authority"
+  )]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "mint", desc = "Mint")]
+  #[account(3, name = "vault", desc = "Vault as To Wallet")]
+  #[account(4, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(5, name = "token_program", desc = "Token Program")]
+  Tok22HarvestFees {},
+
+  /// 26 Admin sets the Config SOL vault yield rate from now on and funds the YieldPool
+  #[account(0, signer, writable, name = "admin", desc = "Admin or Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  YieldFund { rate_bps: u16, amount: u64 },
+
+  /// 27 User claims accrued SOL vault yield
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "vault", desc = "Vault PDA")]
+  #[account(2, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(3, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(4, name = "config_pda", desc = "Config PDA of the YieldPool")]
+  ClaimYield {},
+
+  /// 28 Lock SOL in a user's vault until end_at, vesting linearly after cliff_at. cliff_at == end_at for a plain unlock time
+  #[account(0, signer, writable, name = "funder", desc = "Funder")]
+  #[account(1, signer, name = "user", desc = "User as Beneficiary")]
+  #[account(2, writable, name = "vault", desc = "Vault PDA")]
+  #[account(3, writable, name = "lock", desc = "VaultLock PDA")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  #[account(5, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(6, optional, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(7, optional, name = "config_pda", desc = "Config PDA of the YieldPool")]
+  LockSol {
+    amount: u64,
+    cliff_at: u32,
+    end_at: u32,
+  },
+
+  /// 29 Lock tokens in a user's vault ATA until end_at, vesting linearly after cliff_at. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "funder", desc = "Funder")]
+  #[account(1, writable, name = "from", desc = "Funder ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault PDA")]
+  #[account(4, writable, name = "lock", desc = "VaultLock PDA")]
+  #[account(5, signer, name = "user", desc = "User as Beneficiary")]
+  #[account(6, name = "mint", desc = "Mint")]
+  #[account(7, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(8, name = "token_program", desc = "Token Program")]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(11, name = "rent_sysvar", desc = "RentSysvar")]
+  LockTok {
+    decimals: u8,
+    amount: u64,
+    cliff_at: u32,
+    end_at: u32,
+  },
+
+  /// 30 User closes a fully vested SOL VaultLock
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "lock", desc = "VaultLock PDA")]
+  #[account(2, writable, name = "funder", desc = "Funder to get the rent back")]
+  UnlockSol {},
+
+  /// 31 User closes a fully vested token VaultLock
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "lock", desc = "VaultLock PDA")]
+  #[account(2, writable, name = "funder", desc = "Funder to get the rent back")]
+  #[account(3, name = "mint", desc = "Mint")]
+  UnlockTok {},
+
+  /// 32 User sets a delegate to withdraw up to cap per period from the vault. Zero mint for SOL
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "delegate_pda", desc = "VaultDelegate PDA")]
+  #[account(2, name = "delegate", desc = "Delegate")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  DelegateSet {
+    mint: [u8; 32],
+    cap: u64,
+    period: u32,
+  },
+
+  /// 33 User revokes a vault delegate
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "delegate_pda", desc = "VaultDelegate PDA")]
+  DelegateRevoke {},
+
+  /// 34 Delegate withdraws SOL from a user vault within its cap
+  #[account(0, signer, name = "delegate", desc = "Delegate")]
+  #[account(1, name = "user", desc = "User")]
+  #[account(2, writable, name = "vault", desc = "Vault PDA")]
+  #[account(3, writable, name = "delegate_pda", desc = "VaultDelegate PDA")]
+  #[account(4, writable, name = "to", desc = "To Wallet")]
+  #[account(5, name = "rent_sysvar", desc = "RentSysvar")]
+  #[account(6, name = "lock", desc = "SOL VaultLock PDA, empty if none")]
+  #[account(7, optional, writable, name = "yield_pool", desc = "YieldPool")]
+  #[account(8, optional, name = "config_pda", desc = "Config PDA of the YieldPool")]
+  DelegateWithdrawSol { amount: u64 },
+
+  /// 35 Delegate withdraws tokens from a user vault within its cap. Token or Token2022 by token_program. Remaining accounts: TransferHook accounts
+  #[account(0, signer, name = "delegate", desc = "Delegate")]
+  #[account(1, name = "user", desc = "User")]
+  #[account(2, writable, name = "from", desc = "Vault ATA")]
+  #[account(3, writable, name = "to", desc = "To ATA")]
+  #[account(4, name = "vault", desc = "Vault PDA")]
+  #[account(5, writable, name = "delegate_pda", desc = "VaultDelegate PDA")]
+  #[account(6, name = "mint", desc = "Mint")]
+  #[account(7, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(8, name = "lock", desc = "VaultLock PDA, empty if none")]
+  #[account(9, name = "token_program", desc = "Token Program")]
+  DelegateWithdrawTok { decimals: u8, amount: u64 },
+
+  /// 36 Program owner makes an M-of-N Multisig and hands it prog_owner. Remaining accounts: members
+  #[account(0, signer, writable, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "multisig", desc = "Multisig PDA")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  MultisigInit { threshold: u8 },
+
+  /// 37 Multisig member proposes a config change. action: 1 SetAdmin, 2 SetProgOwner, 3 SetFee, 4 SetStatus
+  #[account(0, signer, writable, name = "member", desc = "Multisig Member")]
+  #[account(1, name = "multisig", desc = "Multisig PDA")]
+  #[account(2, writable, name = "proposal", desc = "Proposal PDA")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  ProposalMake {
+    id: u64,
+    action: u8,
+    num_u8: u8,
+    num_u64: u64,
+    account1: [u8; 32],
+  },
+
+  /// 38 Multisig member approves a proposal
+  #[account(0, signer, name = "member", desc = "Multisig Member")]
+  #[account(1, name = "multisig", desc = "Multisig PDA")]
+  #[account(2, writable, name = "proposal", desc = "Proposal PDA")]
+  ProposalApprove {},
+
+  /// 39 Multisig member executes a proposal that met the threshold
+  #[account(0, signer, name = "member", desc = "Multisig Member")]
+  #[account(1, name = "multisig", desc = "Multisig PDA")]
+  #[account(2, writable, name = "proposal", desc = "Proposal PDA")]
+  #[account(3, writable, name = "config_pda", desc = "Config PDA")]
+  ProposalExecute {},
+
+  /// 40 Program owner hands prog_owner to a Timelock, storing the delay in seconds on Config
+  #[account(0, signer, writable, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "timelock", desc = "Timelock PDA")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  TimelockInit { delay: u32 },
+
+  /// 41 Timelock owner queues a config change. action: 1 SetAdmin, 2 SetProgOwner, 3 SetFee, 4 SetStatus, 5 SetDelay, 6 CloseConfig
+  #[account(0, signer, writable, name = "owner", desc = "Timelock Owner")]
+  #[account(1, name = "timelock", desc = "Timelock PDA")]
+  #[account(2, name = "config_pda", desc = "Config PDA")]
+  #[account(3, writable, name = "queued", desc = "QueuedAction PDA")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  #[account(5, name = "rent_sysvar", desc = "RentSysvar")]
+  TimelockQueue {
+    id: u64,
+    action: u8,
+    num_u8: u8,
+    num_u64: u64,
+    account1: [u8; 32],
+  },
+
+  /// 42 Anyone executes a queued config change after its eta. Remaining accounts for CloseConfig: [mint, vault_ata] for each config mint
+  #[account(0, signer, name = "signer", desc = "Signer")]
+  #[account(1, name = "timelock", desc = "Timelock PDA")]
+  #[account(2, writable, name = "queued", desc = "QueuedAction PDA")]
+  #[account(3, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(
+    4,
+    writable,
+    name = "owner",
+    desc = "Timelock Owner to get the rent back"
+  )]
+  TimelockExecute {},
+
+  /// 43 Timelock owner cancels a queued config change
+  #[account(0, signer, writable, name = "owner", desc = "Timelock Owner")]
+  #[account(1, name = "timelock", desc = "Timelock PDA")]
+  #[account(2, writable, name = "queued", desc = "QueuedAction PDA")]
+  TimelockCancel {},
+
+  //---------------== Escrow PDA
+  /// 44 Maker amends an open offer: new amount_y, and tops up or withdraws part of X. Bumps the Escrow seq. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "maker_ata_x", desc = "Maker ATA X")]
+  #[account(2, writable, name = "escrow_ata_x", desc = "Escrow ATA X")]
+  #[account(3, name = "mint_x", desc = "Mint X")]
+  #[account(4, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(5, name = "token_program", desc = "Token Program")]
+  EscrowTokAmend {
+    amount_y: u64,
+    top_up_x: u64,
+    withdraw_x: u64,
+  },
+  /// 45 Escrow Offer with one native SOL leg held as lamports in the Escrow PDA. sol_x true offers SOL for token Y; false offers token X for SOL. mint is the token leg. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "maker_ata", desc = "Maker ATA of the token leg")]
+  #[account(2, writable, name = "escrow_ata", desc = "Escrow ATA of the token leg")]
+  #[account(3, name = "mint", desc = "Mint of the token leg")]
+  #[account(4, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  EscrowSolMake {
+    sol_x: bool,
+    decimals: u8,
+    amount_x: u64,
+    amount_y: u64,
+    id: u64,
+  },
+  /// 46 Take an Escrow Offer with one SOL leg. Same min_x, max_y and seq guards as EscrowTokTake. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "taker", desc = "Taker")]
+  #[account(1, writable, name = "taker_ata", desc = "Taker ATA of the token leg")]
+  #[account(2, writable, name = "escrow_ata", desc = "Escrow ATA of the token leg")]
+  #[account(3, name = "mint", desc = "Mint of the token leg")]
+  #[account(4, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, name = "token_program", desc = "Token Program")]
+  #[account(7, name = "system_program", desc = "System Program")]
+  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(9, name = "rent_sysvar", desc = "RentSysvar")]
+  EscrowSolTake { min_x: u64, max_y: u64, seq: u32 },
+  /// 47 Maker closes an Escrow Offer with one SOL leg: cancels it before a take, or withdraws after. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "maker_ata", desc = "Maker ATA of the token leg")]
+  #[account(2, writable, name = "escrow_ata", desc = "Escrow ATA of the token leg")]
+  #[account(3, name = "mint", desc = "Mint of the token leg")]
+  #[account(4, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  EscrowSolClose {},
+  /// 48 Basket Offer of 1 to 4 offered mints for 1 to 4 wanted mints under one token_program, each leg in its own Basket ATA. Data: leg_count_x, leg_count_y, id, then amount u64 and decimals u8 per leg, X legs first. Remaining accounts: X legs as [mint, maker_ata, basket_ata], then Y legs as [mint, basket_ata]
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "basket_pda", desc = "Basket PDA")]
+  #[account(2, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(3, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(4, name = "token_program", desc = "Token Program")]
+  #[account(5, name = "system_program", desc = "System Program")]
+  #[account(6, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(7, name = "rent_sysvar", desc = "RentSysvar")]
+  BasketMake {
+    leg_count_x: u8,
+    leg_count_y: u8,
+    id: u64,
+  },
+  /// 49 Take a Basket Offer: pays every Y leg and receives every X leg atomically. Remaining accounts per leg, X legs first: [mint, basket_ata, taker_ata]
+  #[account(0, signer, writable, name = "taker", desc = "Taker")]
+  #[account(1, writable, name = "basket_pda", desc = "Basket PDA")]
+  #[account(2, name = "config_pda", desc = "Config PDA")]
+  #[account(3, name = "token_program", desc = "Token Program")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  #[account(5, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(6, name = "rent_sysvar", desc = "RentSysvar")]
+  BasketTake {},
+  /// 50 Maker closes a Basket Offer: cancels it before a take, or withdraws after. Remaining accounts per leg, X legs first: [mint, basket_ata, maker_ata]
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "basket_pda", desc = "Basket PDA")]
+  #[account(2, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(3, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(4, name = "token_program", desc = "Token Program")]
+  #[account(5, name = "system_program", desc = "System Program")]
+  #[account(6, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(7, name = "rent_sysvar", desc = "RentSysvar")]
+  BasketClose {},
+  /// 51 Make an English Auction of amount_x X for Y, bids from reserve_y until end_time. id 0 takes the next id from the MakerIndex. TransferHook mints are not supported
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "maker_ata_x", desc = "Maker ATA X")]
+  #[account(2, writable, name = "auction_ata_x", desc = "Auction ATA X")]
+  #[account(3, name = "mint_x", desc = "Mint X")]
+  #[account(4, name = "mint_y", desc = "Mint Y")]
+  #[account(5, writable, name = "auction_pda", desc = "Auction PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(8, name = "token_program", desc = "Token Program")]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(11, name = "rent_sysvar", desc = "RentSysvar")]
+  AuctionMake {
+    decimal_x: u8,
+    amount_x: u64,
+    decimal_y: u8,
+    reserve_y: u64,
+    end_time: i64,
+    id: u64,
+  },
+  /// 52 Bid on an Auction before end_time. The outbid bid stays in its Bid PDA for AuctionBidWithdraw, or for a new bid that adds amount to it
+  #[account(0, signer, writable, name = "bidder", desc = "Bidder")]
+  #[account(1, writable, name = "bidder_ata_y", desc = "Bidder ATA Y")]
+  #[account(2, writable, name = "bid_pda", desc = "Bid PDA")]
+  #[account(3, writable, name = "bid_ata_y", desc = "Bid ATA Y")]
+  #[account(4, name = "mint_y", desc = "Mint Y")]
+  #[account(5, writable, name = "auction_pda", desc = "Auction PDA")]
+  #[account(6, name = "token_program", desc = "Token Program")]
+  #[account(7, name = "system_program", desc = "System Program")]
+  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(9, name = "rent_sysvar", desc = "RentSysvar")]
+  AuctionBid { amount: u64 },
+  /// 53 Settle an Auction after end_time: the highest bid to the maker and X to the winner. With no bid, the winner is the maker
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "maker", desc = "Maker")]
+  #[account(2, writable, name = "maker_ata_y", desc = "Maker ATA Y")]
+  #[account(3, writable, name = "winner", desc = "Winner")]
+  #[account(4, writable, name = "winner_ata_x", desc = "Winner ATA X")]
+  #[account(5, writable, name = "auction_ata_x", desc = "Auction ATA X")]
+  #[account(6, writable, name = "bid_pda", desc = "Bid PDA of the winner")]
+  #[account(7, writable, name = "bid_ata_y", desc = "Bid ATA Y of the winner")]
+  #[account(8, name = "mint_x", desc = "Mint X")]
+  #[account(9, name = "mint_y", desc = "Mint Y")]
+  #[account(10, writable, name = "auction_pda", desc = "Auction PDA")]
+  #[account(11, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(12, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(13, name = "token_program", desc = "Token Program")]
+  #[account(14, name = "system_program", desc = "System Program")]
+  #[account(15, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(16, name = "rent_sysvar", desc = "RentSysvar")]
+  AuctionSettle {},
+  /// 54 Make a Milestone payment escrow: the payer locks the sum of 1 to 8 milestone amounts for the payee, with an arbiter for disputes. Data: decimals, deadline, id, count, then amount u64 per milestone. id 0 takes the next id from the MakerIndex. TransferHook mints are not supported
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "payer_ata", desc = "Payer ATA")]
+  #[account(2, writable, name = "milestone_ata", desc = "Milestone ATA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, name = "payee", desc = "Payee")]
+  #[account(5, name = "arbiter", desc = "Arbiter")]
+  #[account(6, writable, name = "milestone_pda", desc = "Milestone PDA")]
+  #[account(7, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(8, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(9, name = "token_program", desc = "Token Program")]
+  #[account(10, name = "system_program", desc = "System Program")]
+  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(12, name = "rent_sysvar", desc = "RentSysvar")]
+  MilestoneMake {
+    decimals: u8,
+    deadline: i64,
+    id: u64,
+    count: u8,
+  },
+  /// 55 Settle a milestone: release it to the payee or refund it to the payer. Before the deadline, the payer releases an undisputed milestone, the payee refunds, and the arbiter does either; after it, only the payer settles
+  #[account(0, signer, writable, name = "signer", desc = "Payer, payee or arbiter")]
+  #[account(1, name = "payer", desc = "Payer")]
+  #[account(2, name = "recipient", desc = "Payee to release, or payer to refund")]
+  #[account(3, writable, name = "recipient_ata", desc = "Recipient ATA")]
+  #[account(4, writable, name = "milestone_ata", desc = "Milestone ATA")]
+  #[account(5, name = "mint", desc = "Mint")]
+  #[account(6, writable, name = "milestone_pda", desc = "Milestone PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  MilestoneSettle { index: u8, release: bool },
+  /// 56 The payer or the payee disputes a milestone before the deadline, leaving its release to the arbiter
+  #[account(0, signer, name = "signer", desc = "Payer or payee")]
+  #[account(1, writable, name = "milestone_pda", desc = "Milestone PDA")]
+  MilestoneDispute { index: u8 },
+  /// 57 Payer closes a Milestone escrow once every milestone is settled, or after the deadline; the rest reverts to the payer
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "payer_ata", desc = "Payer ATA")]
+  #[account(2, writable, name = "milestone_ata", desc = "Milestone ATA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, writable, name = "milestone_pda", desc = "Milestone PDA")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  MilestoneClose {},
+  /// 58 User subscribes to pay amount per period into the vault for up to max_periods. The Subscription PDA becomes the delegate of the user ATA for amount * max_periods; the first payment is due at once
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "user_ata", desc = "User ATA")]
+  #[account(2, name = "mint", desc = "Mint")]
+  #[account(3, writable, name = "subscription_pda", desc = "Subscription PDA")]
+  #[account(4, name = "config_pda", desc = "Config PDA")]
+  #[account(5, name = "token_program", desc = "Token Program")]
+  #[account(6, name = "system_program", desc = "System Program")]
+  #[account(7, name = "rent_sysvar", desc = "RentSysvar")]
+  SubscriptionMake {
+    decimals: u8,
+    amount: u64,
+    period: u32,
+    max_periods: u32,
+  },
+  /// 59 Anyone pulls one due Subscription payment into the vault ATA. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "cranker", desc = "Cranker")]
+  #[account(1, name = "user", desc = "User")]
+  #[account(2, writable, name = "user_ata", desc = "User ATA")]
+  #[account(3, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(4, name = "vault", desc = "Vault")]
+  #[account(5, name = "mint", desc = "Mint")]
+  #[account(6, writable, name = "subscription_pda", desc = "Subscription PDA")]
+  #[account(7, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(8, name = "token_program", desc = "Token Program")]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(11, name = "rent_sysvar", desc = "RentSysvar")]
+  SubscriptionPull {},
+  /// 60 User cancels a Subscription, revoking its token delegate approval
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "user_ata", desc = "User ATA")]
+  #[account(2, name = "mint", desc = "Mint")]
+  #[account(3, writable, name = "subscription_pda", desc = "Subscription PDA")]
+  #[account(4, name = "token_program", desc = "Token Program")]
+  SubscriptionCancel {},
+  /// 61 Config admin or prog_owner streams tokens from the vault to a recipient at rate per second between start_at and end_at
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "funder",
+    desc = "Config admin or prog_owner"
+  )]
+  #[account(1, name = "recipient", desc = "Recipient")]
+  #[account(2, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "stream_pda", desc = "Stream PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "rent_sysvar", desc = "RentSysvar")]
+  StreamMake {
+    decimals: u8,
+    rate: u64,
+    start_at: u32,
+    end_at: u32,
+  },
+  /// 62 Recipient withdraws what a Stream has accrued from the vault ATA. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "recipient", desc = "Recipient")]
+  #[account(1, writable, name = "recipient_ata", desc = "Recipient ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "stream_pda", desc = "Stream PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  StreamWithdraw {},
+  /// 63 Funder cancels a Stream, paying out what has accrued; the unvested remainder stays in the vault. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "funder", desc = "Funder")]
+  #[account(1, name = "recipient", desc = "Recipient")]
+  #[account(2, writable, name = "recipient_ata", desc = "Recipient ATA")]
+  #[account(3, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(4, name = "vault", desc = "Vault")]
+  #[account(5, name = "mint", desc = "Mint")]
+  #[account(6, writable, name = "stream_pda", desc = "Stream PDA")]
+  #[account(7, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(8, name = "token_program", desc = "Token Program")]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(11, name = "rent_sysvar", desc = "RentSysvar")]
+  StreamCancel {},
+  /// 64 Config admin or prog_owner funds a merkle airdrop of total into the vault ATA. root commits to (index u32, claimant, amount u64) leaves. Remaining accounts: TransferHook accounts
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "funder",
+    desc = "Config admin or prog_owner"
+  )]
+  #[account(1, writable, name = "funder_ata", desc = "Funder ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "distributor_pda", desc = "Distributor PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  DistributorMake {
+    decimals: u8,
+    id: u64,
+    total: u64,
+    deadline: i64,
+    count: u32,
+    root: [u8; 32],
+  },
+  /// 65 Claimant proves a Distributor leaf and gets its amount from the vault ATA. The proof follows amount as 32 bytes per sibling hash. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "claimant", desc = "Claimant")]
+  #[account(1, writable, name = "claimant_ata", desc = "Claimant ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "distributor_pda", desc = "Distributor PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  DistributorClaim { index: u32, amount: u64 },
+  /// 66 Funder claws back what a Distributor has not paid out after its deadline. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "funder", desc = "Funder")]
+  #[account(1, writable, name = "funder_ata", desc = "Funder ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "distributor_pda", desc = "Distributor PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  DistributorClawback {},
+  /// 67 Config admin or prog_owner issues an Invoice for amount of mint, payable into the vault ATA until due_at. A zero payer lets anyone pay
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "issuer",
+    desc = "Config admin or prog_owner"
+  )]
+  #[account(1, name = "mint", desc = "Mint")]
+  #[account(2, writable, name = "invoice_pda", desc = "Invoice PDA")]
+  #[account(3, name = "config_pda", desc = "Config PDA")]
+  #[account(4, name = "token_program", desc = "Token Program")]
+  #[account(5, name = "system_program", desc = "System Program")]
+  #[account(6, name = "rent_sysvar", desc = "RentSysvar")]
+  InvoiceMake {
+    decimals: u8,
+    id: u64,
+    amount: u64,
+    due_at: i64,
+    payer: [u8; 32],
+  },
+  /// 68 Pay an Invoice into the vault ATA, marking it paid and emitting an InvoicePaid event: issuer, id, payer, mint, amount, received, paid_at. Remaining accounts: TransferHook accounts
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "payer_ata", desc = "Payer ATA")]
+  #[account(2, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(3, name = "vault", desc = "Vault")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, writable, name = "invoice_pda", desc = "Invoice PDA")]
+  #[account(6, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(8, name = "system_program", desc = "System Program")]
+  #[account(9, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(10, name = "rent_sysvar", desc = "RentSysvar")]
+  InvoicePay {},
+  /// 69 Grow a Config or Config2 PDA made before open_escrows and creator to the current layout
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "creator", desc = "Address that seeds the Config PDA")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  #[account(4, name = "rent_sysvar", desc = "RentSysvar")]
+  ConfigMigrate {},
+  /// 70 Grow an Escrow PDA made before seq to the current layout and count it in the MakerIndex
+  #[account(0, signer, writable, name = "maker", desc = "Maker")]
+  #[account(1, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(2, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(3, writable, name = "maker_index", desc = "MakerIndex PDA")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  #[account(5, name = "rent_sysvar", desc = "RentSysvar")]
+  EscrowMigrate {},
+  /// 71 Outbid bidder takes its bid back and closes its Bid PDA. Also after the Auction is settled
+  #[account(0, signer, writable, name = "bidder", desc = "Bidder")]
+  #[account(1, writable, name = "bidder_ata_y", desc = "Bidder ATA Y")]
+  #[account(2, writable, name = "bid_pda", desc = "Bid PDA")]
+  #[account(3, writable, name = "bid_ata_y", desc = "Bid ATA Y")]
+  #[account(4, name = "mint_y", desc = "Mint Y")]
+  #[account(5, name = "auction_pda", desc = "Auction PDA")]
+  #[account(6, name = "token_program", desc = "Token Program")]
+  #[account(7, name = "system_program", desc = "System Program")]
+  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  AuctionBidWithdraw { decimals: u8 },
   //---------------== Admin PDA
   //---------------== User PDA
   //---------------== Action PDA
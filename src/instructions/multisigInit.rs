@@ -0,0 +1,111 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda1,
+  instructions::check_signer, writable, Config, Ee, Multisig, PROG_ADDR,
+};
+
+/// Program owner hands prog_owner over to a new M-of-N Multisig. Remaining accounts: members
+pub struct MultisigInit<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub multisig: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub members: &'a [AccountView],
+  pub threshold: u8,
+}
+impl<'a> MultisigInit<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &36;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigInit {
+      prog_owner,
+      config_pda,
+      multisig,
+      system_program: _,
+      rent_sysvar,
+      members,
+      threshold,
+    } = self;
+    log!("MultisigInit process()");
+    let (expected_multisig, bump) = derive_pda1(config_pda.address(), Multisig::SEED)?;
+    if multisig.address() != &expected_multisig {
+      return Ee::MultisigPDA.e();
+    }
+    let signer_seeds = [
+      Seed::from(Multisig::SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    let rent = Rent::from_account_view(rent_sysvar)?;
+    pinocchio_system::instructions::CreateAccount {
+      from: prog_owner,
+      to: multisig,
+      lamports: rent.try_minimum_balance(Multisig::LEN)?,
+      space: Multisig::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let state = Multisig::from_account_view(multisig)?;
+    state.set_config(config_pda.address());
+    state.set_members(members, threshold)?;
+    state.set_bump(bump);
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.set_prog_owner(multisig.address());
+    log!(
+      "Multisig is the prog_owner: {} of {}",
+      threshold,
+      members.len()
+    );
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigInit<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigInit try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [prog_owner, config_pda, multisig, system_program, rent_sysvar, members @ ..] = accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    writable(multisig)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    check_data_len(data, 1)?;
+    let threshold = data[0];
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      multisig,
+      system_program,
+      rent_sysvar,
+      members,
+      threshold,
+    })
+  }
+}
@@ -0,0 +1,57 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{instructions::check_signer, writable, Ee, Multisig, Proposal};
+
+/// Multisig member approves a proposal
+pub struct ProposalApprove<'a> {
+  pub member: &'a AccountView, //signer
+  pub proposal: &'a AccountView,
+  pub member_index: u8,
+}
+impl<'a> ProposalApprove<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &38;
+
+  pub fn process(self) -> ProgramResult {
+    let ProposalApprove {
+      member: _,
+      proposal,
+      member_index,
+    } = self;
+    log!("ProposalApprove process()");
+    let state = Proposal::from_account_view(proposal)?;
+    state.approve(member_index);
+    log!("approvals: {}", state.approval_count());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ProposalApprove<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ProposalApprove try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [member, multisig, proposal] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(member)?;
+    writable(proposal)?;
+
+    let state = Proposal::from_account_view(proposal)?;
+    if state.multisig() != multisig.address() {
+      return Err(Ee::ProposalPDA.into());
+    }
+    if state.executed() {
+      return Err(Ee::ProposalExecuted.into());
+    }
+    let member_index = Multisig::from_account_view(multisig)?.member_index(member.address())?;
+    Ok(Self {
+      member,
+      proposal,
+      member_index,
+    })
+  }
+}
@@ -0,0 +1,74 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{check_pda, instructions::check_signer, writable, Config, Ee, Multisig, Proposal};
+
+/// Any multisig member executes a proposal once its approvals meet the threshold
+pub struct ProposalExecute<'a> {
+  pub member: &'a AccountView, //signer
+  pub proposal: &'a AccountView,
+  pub config_pda: &'a AccountView,
+}
+impl<'a> ProposalExecute<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &39;
+
+  pub fn process(self) -> ProgramResult {
+    let ProposalExecute {
+      member: _,
+      proposal,
+      config_pda,
+    } = self;
+    log!("ProposalExecute process()");
+    let state = Proposal::from_account_view(proposal)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    state
+      .action()
+      .apply(config, state.account1(), state.num_u8(), state.num_u64())?;
+    state.set_executed(true);
+    log!("Proposal {} executed", state.id());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ProposalExecute<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ProposalExecute try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [member, multisig, proposal, config_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(member)?;
+    writable(proposal)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    let msig = Multisig::from_account_view(multisig)?;
+    msig.member_index(member.address())?;
+    if msig.config() != config_pda.address() {
+      return Err(Ee::MultisigPDA.into());
+    }
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner() != multisig.address() {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    let state = Proposal::from_account_view(proposal)?;
+    if state.multisig() != multisig.address() {
+      return Err(Ee::ProposalPDA.into());
+    }
+    if state.executed() {
+      return Err(Ee::ProposalExecuted.into());
+    }
+    if state.approval_count() < msig.threshold() {
+      return Err(Ee::ProposalThreshold.into());
+    }
+    Ok(Self {
+      member,
+      proposal,
+      config_pda,
+    })
+  }
+}
@@ -0,0 +1,129 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_rent_sysvar, check_sysprog, derive_pda2, instructions::check_signer,
+  parse_u64, to32bytes, writable, Ee, Multisig, Proposal, ProposalAction, PROG_ADDR,
+};
+
+/// Multisig member proposes a config change and approves it
+pub struct ProposalMake<'a> {
+  pub member: &'a AccountView, //signer
+  pub multisig: &'a AccountView,
+  pub proposal: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub id: u64,
+  pub action: u8,
+  pub num_u8: u8,
+  pub num_u64: u64,
+  pub account1: &'a [u8; 32],
+  pub member_index: u8,
+}
+impl<'a> ProposalMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &37;
+
+  pub fn process(self) -> ProgramResult {
+    let ProposalMake {
+      member,
+      multisig,
+      proposal,
+      system_program: _,
+      rent_sysvar,
+      id,
+      action,
+      num_u8,
+      num_u64,
+      account1,
+      member_index,
+    } = self;
+    log!("ProposalMake process()");
+    let id_bytes = id.to_le_bytes();
+    let (expected_proposal, bump) = derive_pda2(multisig.address(), &id_bytes, Proposal::SEED)?;
+    if proposal.address() != &expected_proposal {
+      return Ee::ProposalPDA.e();
+    }
+    let signer_seeds = [
+      Seed::from(Proposal::SEED),
+      Seed::from(multisig.address().as_ref()),
+      Seed::from(&id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    let rent = Rent::from_account_view(rent_sysvar)?;
+    pinocchio_system::instructions::CreateAccount {
+      from: member,
+      to: proposal,
+      lamports: rent.try_minimum_balance(Proposal::LEN)?,
+      space: Proposal::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let state = Proposal::from_account_view(proposal)?;
+    state.set_multisig(multisig.address());
+    state.set_id(id);
+    state.set_action(action);
+    state.set_num_u8(num_u8);
+    state.set_num_u64(num_u64);
+    state.set_account1(&Address::new_from_array(*account1));
+    state.approve(member_index);
+    state.set_bump(bump);
+    log!("Proposal {} made", id);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ProposalMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ProposalMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [member, multisig, proposal, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(member)?;
+    writable(proposal)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    //8+1+1+8+32: u64 id, u8 action, u8 num_u8, u64 num_u64, 32 account1
+    check_data_len(data, 50)?;
+    let id = parse_u64(&data[0..8])?;
+    let action = data[8];
+    let num_u8 = data[9];
+    let num_u64 = parse_u64(&data[10..18])?;
+    let account1 = to32bytes(&data[18..50])?;
+    log!("id: {}, action: {}", id, action);
+
+    let kind = ProposalAction::from(action);
+    if matches!(kind, ProposalAction::SetDelay | ProposalAction::CloseConfig) {
+      return Err(Ee::FunctionSelector.into());
+    }
+    kind.check(num_u8, num_u64)?;
+    let member_index = Multisig::from_account_view(multisig)?.member_index(member.address())?;
+
+    Ok(Self {
+      member,
+      multisig,
+      proposal,
+      system_program,
+      rent_sysvar,
+      id,
+      action,
+      num_u8,
+      num_u64,
+      account1,
+      member_index,
+    })
+  }
+}
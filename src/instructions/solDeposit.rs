@@ -9,21 +9,24 @@ use pinocchio_log::log;
 use pinocchio_system::instructions::Transfer as SystemTransfer;
 
 use crate::{
-  check_rent_sysvar, check_sysprog,
+  check_rent_sysvar, check_sysprog, get_rent_exempt,
   instructions::{check_pda, check_signer, derive_pda1, parse_u64},
-  none_zero_u64, sol_balc, writable, Ee, PROG_ADDR, VAULT_SEED, VAULT_SIZE,
+  none_zero_u64, sol_balc, sync_vault_yield, writable, Ee, Vault, PROG_ADDR, VAULT_LEGACY_SIZE,
+  VAULT_SEED, VAULT_SIZE,
 };
 
 // Deposit SOL to program PDA
 // make and rent-funds the vault PDA
 // check the PDA exists and is owned by the program
 // transfer the SOL amount to the vault
+// accrue yield and add the amount to the vault principal
 
 //Deposit Accounts
 pub struct DepositSol<'a> {
   pub user: &'a AccountView,
   pub vault: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub yield_accounts: &'a [AccountView], //optional YieldPool and Config PDA
   pub amount: u64,
 }
 impl<'a> DepositSol<'a> {
@@ -34,10 +37,13 @@ impl<'a> DepositSol<'a> {
       user,
       vault,
       rent_sysvar,
+      yield_accounts,
       amount,
     } = self;
     log!("DepositSol process");
-    check_vault_exists(user, vault, rent_sysvar)?;
+    check_vault_exists(user, user, vault, rent_sysvar)?;
+    let state = Vault::from_account_view(vault)?;
+    sync_vault_yield(state, yield_accounts)?;
 
     log!("DepositSol 2");
     SystemTransfer {
@@ -46,6 +52,11 @@ impl<'a> DepositSol<'a> {
       lamports: amount,
     }
     .invoke()?;
+    let principal = state
+      .principal()
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    state.set_principal(principal);
     log!("success: {} Lamports deposited to vault", amount);
     Ok(())
   }
@@ -58,7 +69,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DepositSol<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, vault, system_program, rent_sysvar] = accounts else {
+    let [user, vault, system_program, rent_sysvar, yield_accounts @ ..] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(user)?;
@@ -76,24 +87,27 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DepositSol<'a> {
       user,
       vault,
       rent_sysvar,
+      yield_accounts,
       amount,
     })
   }
 }
 
-/// Ensure the vault exists; if not, create it with PDA seeds. user must be a signer, vault must be writable, and rent minimum must be respected for creation.
-fn check_vault_exists(
+/// Ensure the vault exists; if not, create it with PDA seeds. payer must be a signer, vault must be writable, and rent minimum must be respected for creation.
+pub fn check_vault_exists(
+  payer: &AccountView,
   user: &AccountView,
   vault: &AccountView,
   rent_sysvar: &AccountView,
 ) -> ProgramResult {
   log!("check_vault_exists");
+  // the vault state belongs to this user
+  let (expected_vault_pda, bump) = derive_pda1(user.address(), VAULT_SEED)?;
+  if vault.address() != &expected_vault_pda {
+    return Ee::VaultPDA.e();
+  }
   // Create when empty and fund rent-exempt.
   if vault.lamports() == 0 {
-    let (expected_vault_pda, bump) = derive_pda1(user.address(), VAULT_SEED)?;
-    if vault.address() != &expected_vault_pda {
-      return Ee::VaultPDA.e();
-    }
     let signer_seeds = [
       Seed::from(VAULT_SEED),
       Seed::from(user.address().as_ref()),
@@ -108,19 +122,51 @@ fn check_vault_exists(
     log!("VAULT_SIZE: {}", VAULT_SIZE);
 
     pinocchio_system::instructions::CreateAccount {
-      from: user,
+      from: payer,
       to: vault,
       lamports: needed_lamports,
       space: VAULT_SIZE as u64,
       owner: &PROG_ADDR,
     }
     .invoke_signed(&[seed_signer])?;
+    Vault::from_account_view(vault)?.set_bump(bump);
 
     log!("Vault created");
   } else {
     // If vault already exists
     check_pda(vault)?;
+    migrate_legacy_vault(vault, rent_sysvar, Some(payer), bump)?;
     log!("Vault already exists");
   }
   Ok(())
 }
+
+/// Grow a 16-byte vault made before the Vault struct, and count its SOL above rent as principal.
+/// The payer covers the extra rent; without one the vault pays it from its own lamports
+pub fn migrate_legacy_vault(
+  vault: &AccountView,
+  rent_sysvar: &AccountView,
+  payer: Option<&AccountView>,
+  bump: u8,
+) -> ProgramResult {
+  if vault.data_len() != VAULT_LEGACY_SIZE {
+    return Ok(());
+  }
+  log!("Migrate legacy vault");
+  vault.resize(VAULT_SIZE)?;
+  let min_balc = get_rent_exempt(vault, rent_sysvar, VAULT_SIZE)?;
+  if let Some(payer) = payer {
+    if vault.lamports() < min_balc {
+      SystemTransfer {
+        from: payer,
+        to: vault,
+        lamports: min_balc - vault.lamports(),
+      }
+      .invoke()?;
+    }
+  }
+  let state = Vault::from_account_view(vault)?;
+  state.set_principal(vault.lamports().saturating_sub(min_balc));
+  state.set_bump(bump);
+  Ok(())
+}
@@ -3,16 +3,21 @@ use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
 use crate::{
-  get_rent_exempt,
+  check_vault_lock, get_rent_exempt,
   instructions::{check_pda, check_signer, derive_pda1, parse_u64},
-  none_zero_u64, writable, Ee, VAULT_SEED, VAULT_SIZE,
+  migrate_legacy_vault, none_zero_u64, sync_vault_yield, writable, Ee, Vault, VaultLock,
+  VAULT_SEED, VAULT_SIZE,
 };
 
-//  vault is owned by the program, matches the PDA derived from user. The withdrawn amount is everything above the rent minimum.
+//  vault is owned by the program, matches the PDA derived from user. The withdrawn amount is everything above the rent minimum and what the user's SOL VaultLock still locks.
 pub struct WithdrawSol<'a> {
   pub user: &'a AccountView,
   pub vault: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub lock: &'a AccountView,
+  pub yield_accounts: &'a [AccountView], //optional YieldPool and Config PDA
   pub amount: u64,
+  pub bump: u8,
 }
 impl<'a> WithdrawSol<'a> {
   pub const DISCRIMINATOR: &'a u8 = &1;
@@ -22,9 +27,16 @@ impl<'a> WithdrawSol<'a> {
     let WithdrawSol {
       user,
       vault,
+      rent_sysvar,
+      lock: _,
+      yield_accounts,
       amount,
+      bump,
     } = self;
     log!("withdrawSol process()");
+    migrate_legacy_vault(vault, rent_sysvar, None, bump)?;
+    let state = Vault::from_account_view(vault)?;
+    sync_vault_yield(state, yield_accounts)?;
 
     // Transfer SOL from vault to user
     {
@@ -40,6 +52,7 @@ impl<'a> WithdrawSol<'a> {
         .ok_or_else(|| ProgramError::ArithmeticOverflow)?;
       user.set_lamports(sum_lam);
     }
+    state.set_principal(state.principal().saturating_sub(amount));
     log!("{} lamports withdrawn from vault", amount);
     Ok(())
   }
@@ -52,7 +65,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for WithdrawSol<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, vault, rent_sysvar] = accounts else {
+    let [user, vault, rent_sysvar, lock, yield_accounts @ ..] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(user)?;
@@ -62,7 +75,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for WithdrawSol<'a> {
     let amount = parse_u64(data)?;
     none_zero_u64(amount)?;
 
-    let (expected_vault_pda, _bump) = derive_pda1(user.address(), VAULT_SEED)?;
+    let (expected_vault_pda, bump) = derive_pda1(user.address(), VAULT_SEED)?;
     if vault.address() != &expected_vault_pda {
       return Err(Ee::VaultPDA.into());
     }
@@ -84,10 +97,20 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for WithdrawSol<'a> {
     {
       return Err(Ee::PdaToBeBelowRentExempt.into());
     }
+    check_vault_lock(
+      user.address(),
+      &VaultLock::SOL_MINT,
+      lock,
+      vault_balc - vault_min_balc - amount,
+    )?;
     Ok(Self {
       user,
       vault,
+      rent_sysvar,
+      lock,
+      yield_accounts,
       amount,
+      bump,
     })
   }
 }
@@ -0,0 +1,127 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog, close_pda, executable,
+  get_time, instructions::check_signer, pay_stream, writable, Config, Ee, Stream,
+};
+
+/// Funder cancels a Stream: what has accrued but not been withdrawn goes to the recipient, the unvested remainder stays in the vault and is no longer committed, and the Stream PDA is closed
+pub struct StreamCancel<'a> {
+  pub funder: &'a AccountView, //signer
+  pub recipient: &'a AccountView,
+  pub recipient_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub stream_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> StreamCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &63;
+
+  pub fn process(self) -> ProgramResult {
+    let StreamCancel {
+      funder,
+      recipient,
+      recipient_ata,
+      vault_ata,
+      vault,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("StreamCancel process()");
+    let stream = Stream::from_account_view(stream_pda)?;
+    if stream.funder().ne(funder.address()) {
+      return Ee::OnlyAdmin.e();
+    }
+    if stream.recipient().ne(recipient.address()) || stream.mint().ne(mint.address()) {
+      return Ee::StreamPDA.e();
+    }
+    //only the Config that funded the Stream pays it from its vault
+    if config_pda.address().ne(stream.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    let amount = stream.withdraw(get_time()?)?;
+    log!("accrued to recipient: {}", amount);
+    if amount > 0 {
+      pay_stream(
+        stream,
+        amount,
+        funder,
+        recipient,
+        recipient_ata,
+        vault_ata,
+        vault,
+        mint,
+        config_pda,
+        token_program,
+        system_program,
+        rent_sysvar,
+        hook_accounts,
+      )?;
+    }
+    let unvested = stream.total()?.saturating_sub(stream.withdrawn());
+    log!("unvested: {}", unvested);
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    config.release(mint.address(), unvested);
+
+    log!("Close Stream PDA");
+    close_pda(stream_pda, funder)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for StreamCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("StreamCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [funder, recipient, recipient_ata, vault_ata, vault, mint, stream_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(funder)?;
+    writable(recipient_ata)?;
+    writable(vault_ata)?;
+    writable(stream_pda)?;
+    writable(config_pda)?;
+    Ok(Self {
+      funder,
+      recipient,
+      recipient_ata,
+      vault_ata,
+      vault,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
@@ -0,0 +1,159 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_data_len, check_decimals_iface, check_mint_iface, check_rent_sysvar,
+  check_sysprog, check_vault, derive_pda3, executable, get_time, instructions::check_signer,
+  none_zero_u64, parse_u32, parse_u64, tok_acct_amount, writable, Config, Ee, Stream, PROG_ADDR,
+};
+
+/// Config admin or prog_owner streams tokens from the vault to a recipient at rate per second between start_at and end_at. The vault ATA must hold the stream total on top of what is already committed to open Streams and Distributors
+pub struct StreamMake<'a> {
+  pub funder: &'a AccountView, //signer
+  pub recipient: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub stream_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub decimals: u8,
+  pub rate: u64,
+  pub start_at: u32,
+  pub end_at: u32,
+}
+impl<'a> StreamMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &61;
+
+  pub fn process(self) -> ProgramResult {
+    let StreamMake {
+      funder,
+      recipient,
+      vault_ata,
+      vault: _,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program: _,
+      system_program: _,
+      rent_sysvar,
+      decimals,
+      rate,
+      start_at,
+      end_at,
+    } = self;
+    log!("StreamMake process()");
+    //one Stream per Config, recipient and mint
+    let (expected_pda, bump) = derive_pda3(
+      config_pda.address(),
+      recipient.address().as_ref(),
+      mint.address().as_ref(),
+      Stream::SEED,
+    )?;
+    if stream_pda.address() != &expected_pda || stream_pda.lamports() > 0 {
+      return Ee::StreamPDA.e();
+    }
+    log!("Make Stream PDA");
+    let signer_seeds = [
+      Seed::from(Stream::SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(recipient.address().as_ref()),
+      Seed::from(mint.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: funder,
+      to: stream_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Stream::LEN)?,
+      space: Stream::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let stream = Stream::from_account_view(stream_pda)?;
+    stream.set_funder(funder.address());
+    stream.set_config(config_pda.address());
+    stream.set_recipient(recipient.address());
+    stream.set_mint(mint.address());
+    stream.set_rate(rate)?;
+    stream.set_withdrawn(0);
+    stream.set_schedule(start_at, end_at, get_time()?)?;
+    stream.set_decimals(decimals);
+    stream.set_bump(bump);
+
+    let total = stream.total()?;
+    log!("stream total: {}", total);
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.commit(mint.address(), total, tok_acct_amount(vault_ata)?)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for StreamMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("StreamMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //1+8+4+4: u8 takes 1, u64 takes 8, 2x u32 take 8 bytes
+    check_data_len(data, 17)?;
+
+    let [funder, recipient, vault_ata, vault, mint, stream_pda, config_pda, token_program, system_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(funder)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(funder)?;
+    writable(stream_pda)?;
+    writable(config_pda)?;
+
+    let decimals = data[0];
+    let rate = parse_u64(&data[1..9])?;
+    let start_at = parse_u32(&data[9..13])?;
+    let end_at = parse_u32(&data[13..17])?;
+    log!("rate: {}, start_at: {}, end_at: {}", rate, start_at, end_at);
+    none_zero_u64(rate)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    if config.admin().ne(funder.address()) && config.prog_owner().ne(funder.address()) {
+      return Err(Ee::OnlyAdmin.into());
+    }
+    if !config.mints().contains(&mint.address()) {
+      return Err(Ee::MintNotAccepted.into());
+    }
+    check_vault(vault, config.vault())?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    check_ata_iface(vault_ata, vault, mint, token_program)?;
+    Ok(Self {
+      funder,
+      recipient,
+      vault_ata,
+      vault,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program,
+      system_program,
+      rent_sysvar,
+      decimals,
+      rate,
+      start_at,
+      end_at,
+    })
+  }
+}
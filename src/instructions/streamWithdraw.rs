@@ -0,0 +1,181 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  check_vault, executable, get_time, instructions::check_signer, rent_exempt_tokacct_iface,
+  writable, Config, Ee, Stream, TransferIface, VAULT_SEED,
+};
+
+/// Recipient withdraws what a Stream has accrued so far from the vault ATA. Serves both Token and Token2022 via token_program
+pub struct StreamWithdraw<'a> {
+  pub recipient: &'a AccountView, //signer
+  pub recipient_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub stream_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> StreamWithdraw<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &62;
+
+  pub fn process(self) -> ProgramResult {
+    let StreamWithdraw {
+      recipient,
+      recipient_ata,
+      vault_ata,
+      vault,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("StreamWithdraw process()");
+    let stream = Stream::from_account_view(stream_pda)?;
+    if stream.recipient().ne(recipient.address()) {
+      return Ee::OnlyUser.e();
+    }
+    if stream.mint().ne(mint.address()) {
+      return Ee::StreamPDA.e();
+    }
+    //only the Config that funded the Stream pays it from its vault
+    if config_pda.address().ne(stream.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    let amount = stream.withdraw(get_time()?)?;
+    log!("amount: {}, withdrawn: {}", amount, stream.withdrawn());
+    if amount == 0 {
+      return Ee::StreamNothingDue.e();
+    }
+    pay_stream(
+      stream,
+      amount,
+      recipient,
+      recipient,
+      recipient_ata,
+      vault_ata,
+      vault,
+      mint,
+      config_pda,
+      token_program,
+      system_program,
+      rent_sysvar,
+      hook_accounts,
+    )
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for StreamWithdraw<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("StreamWithdraw try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [recipient, recipient_ata, vault_ata, vault, mint, stream_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(recipient)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(recipient_ata)?;
+    writable(vault_ata)?;
+    writable(stream_pda)?;
+    writable(config_pda)?;
+    Ok(Self {
+      recipient,
+      recipient_ata,
+      vault_ata,
+      vault,
+      mint,
+      stream_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
+
+/// Transfer amount of a Stream from the vault ATA to the recipient ATA, signed by the vault. payer funds the recipient ATA if it does not exist yet
+#[allow(clippy::too_many_arguments)]
+pub fn pay_stream(
+  stream: &Stream,
+  amount: u64,
+  payer: &AccountView,
+  recipient: &AccountView,
+  recipient_ata: &AccountView,
+  vault_ata: &AccountView,
+  vault: &AccountView,
+  mint: &AccountView,
+  config_pda: &AccountView,
+  token_program: &AccountView,
+  system_program: &AccountView,
+  rent_sysvar: &AccountView,
+  hook_accounts: &[AccountView],
+) -> ProgramResult {
+  let config: &mut Config = Config::from_account_view(config_pda)?;
+  config.check_address(config_pda)?;
+  check_vault(vault, config.vault())?;
+  check_ata_iface(vault_ata, vault, mint, token_program)?;
+
+  if recipient_ata.is_data_empty() {
+    log!("Make recipient_ata");
+    pinocchio_associated_token_account::instructions::Create {
+      funding_account: payer,
+      account: recipient_ata,
+      wallet: recipient,
+      mint,
+      system_program,
+      token_program,
+    }
+    .invoke()?;
+  } else {
+    check_ata_iface(recipient_ata, recipient, mint, token_program)?;
+    rent_exempt_tokacct_iface(recipient_ata, rent_sysvar, token_program)?;
+  }
+
+  let vault_bump = config.vault_bump();
+  let signer_seeds = [
+    Seed::from(VAULT_SEED),
+    Seed::from(config.creator().as_ref()),
+    Seed::from(core::slice::from_ref(&vault_bump)),
+  ];
+  let seed_signer = Signer::from(&signer_seeds);
+  TransferIface {
+    from: vault_ata,
+    mint,
+    to: recipient_ata,
+    authority: vault,
+    amount,
+    decimals: stream.decimals(),
+    token_program,
+    hook_accounts,
+  }
+  .invoke_signed(&[seed_signer])?;
+  config.sub_token_balance(mint.address(), amount);
+  config.release(mint.address(), amount);
+  Ok(())
+}
@@ -0,0 +1,78 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_data_len, close_pda, executable, instructions::check_signer,
+  tok_acct_delegate, writable, Ee, RevokeIface, Subscription,
+};
+
+/// User cancels a Subscription: the token delegate approval is revoked if it still belongs to the Subscription PDA, which is then closed
+pub struct SubscriptionCancel<'a> {
+  pub user: &'a AccountView, //signer
+  pub user_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub subscription_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+}
+impl<'a> SubscriptionCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &60;
+
+  pub fn process(self) -> ProgramResult {
+    let SubscriptionCancel {
+      user,
+      user_ata,
+      mint,
+      subscription_pda,
+      token_program,
+    } = self;
+    log!("SubscriptionCancel process()");
+    let subscription = Subscription::from_account_view(subscription_pda)?;
+    if subscription.user().ne(user.address()) {
+      return Ee::OnlyUser.e();
+    }
+    if subscription.mint().ne(mint.address()) {
+      return Ee::SubscriptionPDA.e();
+    }
+    check_ata_iface(user_ata, user, mint, token_program)?;
+    //another delegate approved since is left alone
+    if tok_acct_delegate(user_ata)?.as_ref() == Some(subscription_pda.address()) {
+      log!("Revoke the Subscription PDA");
+      RevokeIface {
+        source: user_ata,
+        authority: user,
+        token_program,
+      }
+      .invoke()?;
+    }
+    log!("Close Subscription PDA");
+    close_pda(subscription_pda, user)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SubscriptionCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("SubscriptionCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [user, user_ata, mint, subscription_pda, token_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    executable(token_program)?;
+    writable(user)?;
+    writable(user_ata)?;
+    writable(subscription_pda)?;
+    Ok(Self {
+      user,
+      user_ata,
+      mint,
+      subscription_pda,
+      token_program,
+    })
+  }
+}
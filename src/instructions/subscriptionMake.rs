@@ -0,0 +1,160 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_data_len, check_decimals_iface, check_mint_iface, check_rent_sysvar,
+  check_sysprog, derive_pda2, executable, get_time_i64, instructions::check_signer, none_zero_u32,
+  none_zero_u64, parse_u32, parse_u64, writable, ApproveIface, Config, Ee, Subscription, PROG_ADDR,
+};
+
+/// User subscribes to pay amount per period into the vault for up to max_periods. The Subscription PDA is approved as delegate of the user ATA for the total, and the first payment is due at once
+pub struct SubscriptionMake<'a> {
+  pub user: &'a AccountView, //signer
+  pub user_ata: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub subscription_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub decimals: u8,
+  pub amount: u64,
+  pub period: u32,
+  pub max_periods: u32,
+}
+impl<'a> SubscriptionMake<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &58;
+
+  pub fn process(self) -> ProgramResult {
+    let SubscriptionMake {
+      user,
+      user_ata,
+      mint,
+      subscription_pda,
+      config_pda,
+      token_program,
+      system_program: _,
+      rent_sysvar,
+      decimals,
+      amount,
+      period,
+      max_periods,
+    } = self;
+    log!("SubscriptionMake process()");
+    let (expected_pda, bump) =
+      derive_pda2(user.address(), mint.address().as_ref(), Subscription::SEED)?;
+    if subscription_pda.address() != &expected_pda || subscription_pda.lamports() > 0 {
+      return Ee::SubscriptionPDA.e();
+    }
+    log!("Make Subscription PDA");
+    let signer_seeds = [
+      Seed::from(Subscription::SEED),
+      Seed::from(user.address().as_ref()),
+      Seed::from(mint.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    pinocchio_system::instructions::CreateAccount {
+      from: user,
+      to: subscription_pda,
+      lamports: Rent::from_account_view(rent_sysvar)?.try_minimum_balance(Subscription::LEN)?,
+      space: Subscription::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let allowance = amount
+      .checked_mul(max_periods as u64)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    log!("Approve the Subscription PDA for {}", allowance);
+    ApproveIface {
+      source: user_ata,
+      mint,
+      delegate: subscription_pda,
+      authority: user,
+      amount: allowance,
+      decimals,
+      token_program,
+    }
+    .invoke()?;
+
+    let subscription = Subscription::from_account_view(subscription_pda)?;
+    subscription.set_user(user.address());
+    subscription.set_config(config_pda.address());
+    subscription.set_mint(mint.address());
+    subscription.set_amount(amount)?;
+    subscription.set_paid_through(get_time_i64()?);
+    subscription.set_period(period)?;
+    subscription.set_max_periods(max_periods)?;
+    subscription.set_paid_periods(0);
+    subscription.set_decimals(decimals);
+    subscription.set_bump(bump);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SubscriptionMake<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("SubscriptionMake try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //1+8+4+4: u8 takes 1, u64 takes 8, 2x u32 take 8 bytes
+    check_data_len(data, 17)?;
+
+    let [user, user_ata, mint, subscription_pda, config_pda, token_program, system_program, rent_sysvar] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(user_ata)?;
+    writable(subscription_pda)?;
+
+    let decimals = data[0];
+    let amount = parse_u64(&data[1..9])?;
+    let period = parse_u32(&data[9..13])?;
+    let max_periods = parse_u32(&data[13..17])?;
+    log!(
+      "amount: {}, period: {}, max_periods: {}",
+      amount,
+      period,
+      max_periods
+    );
+    none_zero_u64(amount)?;
+    none_zero_u32(period)?;
+    none_zero_u32(max_periods)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    if !config.mints().contains(&mint.address()) {
+      return Err(Ee::MintNotAccepted.into());
+    }
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
+    check_ata_iface(user_ata, user, mint, token_program)?;
+    Ok(Self {
+      user,
+      user_ata,
+      mint,
+      subscription_pda,
+      config_pda,
+      token_program,
+      system_program,
+      rent_sysvar,
+      decimals,
+      amount,
+      period,
+      max_periods,
+    })
+  }
+}
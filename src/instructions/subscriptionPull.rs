@@ -0,0 +1,157 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata_iface, check_atoken_gpvbd, check_data_len, check_rent_sysvar, check_sysprog,
+  check_vault, executable, get_time_i64, instructions::check_signer, net_received,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee, Subscription, TransferIface,
+};
+
+/// Permissionless crank: pull one due Subscription payment from the user ATA into the vault ATA, signed by the Subscription PDA as token delegate. Serves both Token and Token2022 via token_program
+pub struct SubscriptionPull<'a> {
+  pub cranker: &'a AccountView, //signer
+  pub user: &'a AccountView,
+  pub user_ata: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub subscription_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
+}
+impl<'a> SubscriptionPull<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &59;
+
+  pub fn process(self) -> ProgramResult {
+    let SubscriptionPull {
+      cranker,
+      user,
+      user_ata,
+      vault_ata,
+      vault,
+      mint,
+      subscription_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program: _,
+      rent_sysvar,
+      hook_accounts,
+    } = self;
+    log!("SubscriptionPull process()");
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+    check_vault(vault, config.vault())?;
+
+    let subscription = Subscription::from_account_view(subscription_pda)?;
+    if subscription.user().ne(user.address()) {
+      return Ee::OnlyUser.e();
+    }
+    if subscription.mint().ne(mint.address()) {
+      return Ee::SubscriptionPDA.e();
+    }
+    //payments only go to the vault the user subscribed to
+    if config_pda.address().ne(subscription.config()) {
+      return Ee::ConfigPDA.e();
+    }
+    check_ata_iface(user_ata, user, mint, token_program)?;
+    subscription.pay_period(get_time_i64()?)?;
+    log!(
+      "paid periods: {} of {}, paid through: {}",
+      subscription.paid_periods(),
+      subscription.max_periods(),
+      subscription.paid_through()
+    );
+
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: cranker,
+        account: vault_ata,
+        wallet: vault,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      check_ata_iface(vault_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(vault_ata, rent_sysvar, token_program)?;
+    }
+
+    let bump = subscription.bump();
+    let signer_seeds = [
+      Seed::from(Subscription::SEED),
+      Seed::from(user.address().as_ref()),
+      Seed::from(mint.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+    let balc_before = tok_acct_amount(vault_ata)?;
+    TransferIface {
+      from: user_ata,
+      mint,
+      to: vault_ata,
+      authority: subscription_pda,
+      amount: subscription.amount(),
+      decimals: subscription.decimals(),
+      token_program,
+      hook_accounts,
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    //record what the vault received, which can be less than amount after transfer fees
+    let received = net_received(vault_ata, balc_before)?;
+    config.add_token_balance(mint.address(), received)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for SubscriptionPull<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("SubscriptionPull try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [cranker, user, user_ata, vault_ata, vault, mint, subscription_pda, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(cranker)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(user_ata)?;
+    writable(vault_ata)?;
+    writable(subscription_pda)?;
+    writable(config_pda)?;
+    Ok(Self {
+      cranker,
+      user,
+      user_ata,
+      vault_ata,
+      vault,
+      mint,
+      subscription_pda,
+      config_pda,
+      token_program,
+      system_program,
+      atoken_program,
+      rent_sysvar,
+      hook_accounts,
+    })
+  }
+}
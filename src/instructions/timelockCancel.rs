@@ -0,0 +1,43 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{close_pda, instructions::check_signer, writable, Ee, QueuedAction, Timelock};
+
+/// Timelock owner cancels a queued config change
+pub struct TimelockCancel<'a> {
+  pub owner: &'a AccountView, //signer
+  pub queued: &'a AccountView,
+}
+impl<'a> TimelockCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &43;
+
+  pub fn process(self) -> ProgramResult {
+    let TimelockCancel { owner, queued } = self;
+    log!("TimelockCancel process()");
+    close_pda(queued, owner)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TimelockCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("TimelockCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [owner, timelock, queued] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(owner)?;
+    writable(queued)?;
+
+    if Timelock::from_account_view(timelock)?.owner() != owner.address() {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    if QueuedAction::from_account_view(queued)?.timelock() != timelock.address() {
+      return Err(Ee::QueuedActionPDA.into());
+    }
+    Ok(Self { owner, queued })
+  }
+}
@@ -0,0 +1,94 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_config_empty, check_pda, close_pda, get_time, instructions::check_signer, writable, Config,
+  Ee, ProposalAction, QueuedAction, Timelock,
+};
+
+/// Anyone runs a queued config change after its eta. CloseConfig sends the config rent to the Timelock owner, and needs [mint, vault_ata] pairs as remaining accounts
+pub struct TimelockExecute<'a> {
+  pub signer: &'a AccountView,
+  pub timelock: &'a AccountView,
+  pub queued: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub owner: &'a AccountView,
+  pub mint_atas: &'a [AccountView],
+}
+impl<'a> TimelockExecute<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &42;
+
+  pub fn process(self) -> ProgramResult {
+    let TimelockExecute {
+      signer: _,
+      timelock: _,
+      queued,
+      config_pda,
+      owner,
+      mint_atas,
+    } = self;
+    log!("TimelockExecute process()");
+    let state = QueuedAction::from_account_view(queued)?;
+    match state.action() {
+      ProposalAction::SetDelay => {
+        Config::from_account_view(config_pda)?.set_timelock_delay(state.num_u64() as u32);
+      }
+      ProposalAction::CloseConfig => {
+        check_config_empty(Config::from_account_view(config_pda)?, mint_atas)?;
+        close_pda(config_pda, owner)?
+      }
+      action => {
+        let config: &mut Config = Config::from_account_view(config_pda)?;
+        action.apply(config, state.account1(), state.num_u8(), state.num_u64())?;
+      }
+    }
+    log!("Action {} executed", state.id());
+    close_pda(queued, owner)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TimelockExecute<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("TimelockExecute try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [signer, timelock, queued, config_pda, owner, mint_atas @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(queued)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    writable(owner)?;
+
+    let lock = Timelock::from_account_view(timelock)?;
+    if lock.config() != config_pda.address() {
+      return Err(Ee::TimelockPDA.into());
+    }
+    if lock.owner() != owner.address() {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner() != timelock.address() {
+      return Err(Ee::TimelockPDA.into());
+    }
+    let state = QueuedAction::from_account_view(queued)?;
+    if state.timelock() != timelock.address() {
+      return Err(Ee::QueuedActionPDA.into());
+    }
+    if get_time()? < state.eta() {
+      return Err(Ee::TimelockEta.into());
+    }
+    Ok(Self {
+      signer,
+      timelock,
+      queued,
+      config_pda,
+      owner,
+      mint_atas,
+    })
+  }
+}
@@ -0,0 +1,105 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda1,
+  instructions::check_signer, none_zero_u32, parse_u32, writable, Config, Ee, Timelock, PROG_ADDR,
+};
+
+/// Program owner hands prog_owner over to a Timelock, so config changes are queued with the delay stored in Config
+pub struct TimelockInit<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub timelock: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub delay: u32,
+}
+impl<'a> TimelockInit<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &40;
+
+  pub fn process(self) -> ProgramResult {
+    let TimelockInit {
+      prog_owner,
+      config_pda,
+      timelock,
+      system_program: _,
+      rent_sysvar,
+      delay,
+    } = self;
+    log!("TimelockInit process()");
+    let (expected_timelock, bump) = derive_pda1(config_pda.address(), Timelock::SEED)?;
+    if timelock.address() != &expected_timelock {
+      return Ee::TimelockPDA.e();
+    }
+    let signer_seeds = [
+      Seed::from(Timelock::SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    let rent = Rent::from_account_view(rent_sysvar)?;
+    pinocchio_system::instructions::CreateAccount {
+      from: prog_owner,
+      to: timelock,
+      lamports: rent.try_minimum_balance(Timelock::LEN)?,
+      space: Timelock::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let state = Timelock::from_account_view(timelock)?;
+    state.set_config(config_pda.address());
+    state.set_owner(prog_owner.address());
+    state.set_bump(bump);
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.set_prog_owner(timelock.address());
+    config.set_timelock_delay(delay);
+    log!("Timelock is the prog_owner with delay: {}", delay);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TimelockInit<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("TimelockInit try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [prog_owner, config_pda, timelock, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    writable(timelock)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    check_data_len(data, 4)?;
+    let delay = parse_u32(data)?;
+    none_zero_u32(delay)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      timelock,
+      system_program,
+      rent_sysvar,
+      delay,
+    })
+  }
+}
@@ -0,0 +1,138 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda2, get_time,
+  instructions::check_signer, parse_u64, to32bytes, writable, Config, Ee, ProposalAction,
+  QueuedAction, Timelock, PROG_ADDR,
+};
+
+/// Timelock owner queues a config change that can run after the Config timelock_delay.
+/// action: 1 SetAdmin, 2 SetProgOwner, 3 SetFee, 4 SetStatus, 5 SetDelay, 6 CloseConfig
+pub struct TimelockQueue<'a> {
+  pub owner: &'a AccountView, //signer
+  pub timelock: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub queued: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub id: u64,
+  pub action: u8,
+  pub num_u8: u8,
+  pub num_u64: u64,
+  pub account1: &'a [u8; 32],
+}
+impl<'a> TimelockQueue<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &41;
+
+  pub fn process(self) -> ProgramResult {
+    let TimelockQueue {
+      owner,
+      timelock,
+      config_pda,
+      queued,
+      system_program: _,
+      rent_sysvar,
+      id,
+      action,
+      num_u8,
+      num_u64,
+      account1,
+    } = self;
+    log!("TimelockQueue process()");
+    let id_bytes = id.to_le_bytes();
+    let (expected_queued, bump) = derive_pda2(timelock.address(), &id_bytes, QueuedAction::SEED)?;
+    if queued.address() != &expected_queued {
+      return Ee::QueuedActionPDA.e();
+    }
+    let signer_seeds = [
+      Seed::from(QueuedAction::SEED),
+      Seed::from(timelock.address().as_ref()),
+      Seed::from(&id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    let rent = Rent::from_account_view(rent_sysvar)?;
+    pinocchio_system::instructions::CreateAccount {
+      from: owner,
+      to: queued,
+      lamports: rent.try_minimum_balance(QueuedAction::LEN)?,
+      space: QueuedAction::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let delay = Config::from_account_view(config_pda)?.timelock_delay();
+    let eta = get_time()?
+      .checked_add(delay)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let state = QueuedAction::from_account_view(queued)?;
+    state.set_timelock(timelock.address());
+    state.set_id(id);
+    state.set_action(action);
+    state.set_num_u8(num_u8);
+    state.set_num_u64(num_u64);
+    state.set_account1(&Address::new_from_array(*account1));
+    state.set_eta(eta);
+    state.set_bump(bump);
+    log!("Action {} queued until {}", id, eta);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TimelockQueue<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("TimelockQueue try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [owner, timelock, config_pda, queued, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(owner)?;
+    writable(queued)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    //8+1+1+8+32: u64 id, u8 action, u8 num_u8, u64 num_u64, 32 account1
+    check_data_len(data, 50)?;
+    let id = parse_u64(&data[0..8])?;
+    let action = data[8];
+    let num_u8 = data[9];
+    let num_u64 = parse_u64(&data[10..18])?;
+    let account1 = to32bytes(&data[18..50])?;
+    log!("id: {}, action: {}", id, action);
+    ProposalAction::from(action).check(num_u8, num_u64)?;
+
+    let lock = Timelock::from_account_view(timelock)?;
+    if lock.owner() != owner.address() {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    if lock.config() != config_pda.address() {
+      return Err(Ee::TimelockPDA.into());
+    }
+    check_pda(config_pda)?;
+    Ok(Self {
+      owner,
+      timelock,
+      config_pda,
+      queued,
+      system_program,
+      rent_sysvar,
+      id,
+      action,
+      num_u8,
+      num_u64,
+      account1,
+    })
+  }
+}
@@ -0,0 +1,510 @@
+use pinocchio::{
+  cpi::{invoke, invoke_with_bounds},
+  error::ProgramError,
+  instruction::{InstructionAccount, InstructionView},
+  AccountView, Address, ProgramResult,
+};
+
+use crate::Ee;
+
+//----------------== Token2022 Extensions
+// pinocchio_token_2022 only ships base instructions, so extension instructions are built here by hand
+// https://solana.com/docs/tokens/extensions
+
+/// Extensions start after the 165 byte TokenAccount base + 1 byte AccountType. Mints are padded up to 165 bytes
+pub const TOK22_EXT_START: usize = 166;
+/// TLV header: u16 extension type + u16 value length
+pub const TOK22_TLV_HEADER: usize = 4;
+
+/// Extension types
+pub const EXT_TRANSFER_HOOK: u16 = 14;
+pub const EXT_GROUP_MEMBER_POINTER: u16 = 22;
+pub const EXT_TOKEN_GROUP_MEMBER: u16 = 23;
+
+/// Extension value lengths
+/// 2 authorities(64) + withheld_amount(8) + older and newer TransferFee(2 * 18)
+pub const TRANSFER_FEE_CONFIG_LEN: usize = 32 + 32 + 8 + 18 + 18;
+pub const MINT_CLOSE_AUTHORITY_LEN: usize = 32;
+pub const DEFAULT_ACCOUNT_STATE_LEN: usize = 1;
+pub const NON_TRANSFERABLE_LEN: usize = 0;
+/// rate_authority(32) + initialization_timestamp(8) + pre_update_average_rate(2) + last_update_timestamp(8) + current_rate(2)
+pub const INTEREST_BEARING_CONFIG_LEN: usize = 32 + 8 + 2 + 8 + 2;
+pub const PERMANENT_DELEGATE_LEN: usize = 32;
+/// authority(32) + program_id(32)
+pub const TRANSFER_HOOK_LEN: usize = 32 + 32;
+
+/// authority(32) + metadata_address(32)
+pub const METADATA_POINTER_LEN: usize = 32 + 32;
+/// update_authority(32) + mint(32) + 3 string length prefixes(u32) + additional_metadata vector length(u32)
+pub const TOKEN_METADATA_BASE_LEN: usize = 32 + 32 + 4 * 3 + 4;
+/// authority(32) + member_address(32)
+pub const GROUP_MEMBER_POINTER_LEN: usize = 32 + 32;
+/// mint(32) + group(32) + member_number(8)
+pub const TOKEN_GROUP_MEMBER_LEN: usize = 32 + 32 + 8;
+
+/// Token2022 instruction indexes
+const INITIALIZE_MINT_CLOSE_AUTHORITY: u8 = 25;
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const DEFAULT_ACCOUNT_STATE_EXTENSION: u8 = 28;
+const INITIALIZE_NON_TRANSFERABLE_MINT: u8 = 32;
+const INTEREST_BEARING_MINT_EXTENSION: u8 = 33;
+const INITIALIZE_PERMANENT_DELEGATE: u8 = 35;
+const METADATA_POINTER_EXTENSION: u8 = 39;
+/// sha256("spl_token_metadata_interface:initialize_account")[..8]
+pub const METADATA_INIT_DISC: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+/// sha256("spl_token_metadata_interface:updating_field")[..8]
+pub const METADATA_UPDATE_FIELD_DISC: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+/// Metadata fields for UpdateField: 0 Name, 1 Symbol, 2 Uri, 3 custom Key
+pub const FIELD_KEY: u8 = 3;
+
+/// max lengths of fixed size name/symbol/uri/key inputs
+pub const NAME_MAX: usize = 10;
+pub const SYMBOL_MAX: usize = 6;
+pub const URI_MAX: usize = 32;
+pub const KEY_MAX: usize = 10;
+
+/// size of an extension entry including its TLV header
+pub fn tok22_ext_space(value_len: usize) -> usize {
+  TOK22_TLV_HEADER + value_len
+}
+/// TokenMetadata value length for the given strings, with no additional metadata
+pub fn token_metadata_len(name: &[u8], symbol: &[u8], uri: &[u8]) -> usize {
+  TOKEN_METADATA_BASE_LEN + name.len() + symbol.len() + uri.len()
+}
+/// fixed size inputs are padded with zeros; strip them before writing strings
+pub fn trim_zeros(bytes: &[u8]) -> &[u8] {
+  let end = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+  &bytes[..end]
+}
+/// write a Borsh string(u32 length + bytes) at offset, then return the next offset
+fn write_str(buf: &mut [u8], offset: usize, s: &[u8]) -> usize {
+  let start = offset + 4;
+  buf[offset..start].copy_from_slice(&(s.len() as u32).to_le_bytes());
+  buf[start..start + s.len()].copy_from_slice(s);
+  start + s.len()
+}
+
+/// Initialize the MetadataPointer extension. Must come before InitializeMint
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Mint account
+pub struct InitMetadataPointer<'a> {
+  pub mint: &'a AccountView,
+  pub authority: Option<&'a Address>,
+  pub metadata_address: Option<&'a Address>,
+  pub token_program: &'a Address,
+}
+impl InitMetadataPointer<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let accounts = [InstructionAccount::writable(self.mint.address())];
+
+    // [0] extension ix, [1] Initialize, [2..34] authority, [34..66] metadata_address
+    // None is written as all zeros(OptionalNonZeroPubkey)
+    let mut data = [0u8; 66];
+    data[0] = METADATA_POINTER_EXTENSION;
+    data[1] = 0;
+    if let Some(authority) = self.authority {
+      data[2..34].copy_from_slice(authority.as_array());
+    }
+    if let Some(metadata_address) = self.metadata_address {
+      data[34..66].copy_from_slice(metadata_address.as_array());
+    }
+    let ix = InstructionView {
+      program_id: self.token_program,
+      accounts: &accounts,
+      data: &data,
+    };
+    invoke(&ix, &[self.mint])
+  }
+}
+
+/// Initialize TokenMetadata inside the mint. The mint must already hold lamports for the reallocated size
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Metadata account(the mint itself)
+///   1. `[]` Update authority
+///   2. `[]` Mint
+///   3. `[SIGNER]` Mint authority
+pub struct InitTokenMetadata<'a> {
+  pub metadata: &'a AccountView,
+  pub update_authority: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_authority: &'a AccountView,
+  pub name: &'a [u8],
+  pub symbol: &'a [u8],
+  pub uri: &'a [u8],
+  pub token_program: &'a Address,
+}
+impl InitTokenMetadata<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    if self.name.len() > NAME_MAX || self.symbol.len() > SYMBOL_MAX || self.uri.len() > URI_MAX {
+      return Ee::MetadataStrLen.e();
+    }
+    let accounts = [
+      InstructionAccount::writable(self.metadata.address()),
+      InstructionAccount::readonly(self.update_authority.address()),
+      InstructionAccount::readonly(self.mint.address()),
+      InstructionAccount::readonly_signer(self.mint_authority.address()),
+    ];
+
+    // [0..8] disc, then Borsh strings: name, symbol, uri
+    let mut data = [0u8; 8 + 4 * 3 + NAME_MAX + SYMBOL_MAX + URI_MAX];
+    data[0..8].copy_from_slice(&METADATA_INIT_DISC);
+    let mut offset = write_str(&mut data, 8, self.name);
+    offset = write_str(&mut data, offset, self.symbol);
+    offset = write_str(&mut data, offset, self.uri);
+
+    let ix = InstructionView {
+      program_id: self.token_program,
+      accounts: &accounts,
+      data: &data[..offset],
+    };
+    invoke(
+      &ix,
+      &[
+        self.metadata,
+        self.update_authority,
+        self.mint,
+        self.mint_authority,
+      ],
+    )
+  }
+}
+
+/// Update one TokenMetadata field. field: 0 Name, 1 Symbol, 2 Uri, 3 custom Key
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Metadata account(the mint itself)
+///   1. `[SIGNER]` Update authority
+pub struct UpdateMetadataField<'a> {
+  pub metadata: &'a AccountView,
+  pub update_authority: &'a AccountView,
+  pub field: u8,
+  pub key: &'a [u8],
+  pub value: &'a [u8],
+  pub token_program: &'a Address,
+}
+impl UpdateMetadataField<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    if self.field > FIELD_KEY {
+      return Err(ProgramError::InvalidInstructionData);
+    }
+    if self.key.len() > KEY_MAX || self.value.len() > URI_MAX {
+      return Ee::MetadataStrLen.e();
+    }
+    let accounts = [
+      InstructionAccount::writable(self.metadata.address()),
+      InstructionAccount::readonly_signer(self.update_authority.address()),
+    ];
+
+    // [0..8] disc, [8] Field enum, (Key string), then value string
+    let mut data = [0u8; 8 + 1 + 4 + KEY_MAX + 4 + URI_MAX];
+    data[0..8].copy_from_slice(&METADATA_UPDATE_FIELD_DISC);
+    data[8] = self.field;
+    let mut offset = 9;
+    if self.field == FIELD_KEY {
+      offset = write_str(&mut data, offset, self.key);
+    }
+    offset = write_str(&mut data, offset, self.value);
+
+    let ix = InstructionView {
+      program_id: self.token_program,
+      accounts: &accounts,
+      data: &data[..offset],
+    };
+    invoke(&ix, &[self.metadata, self.update_authority])
+  }
+}
+
+//----------------== Optional Mint Extensions
+/// Bit flags selecting optional mint extensions in Token2022InitMint
+pub const FLAG_TRANSFER_FEE: u8 = 1;
+pub const FLAG_INTEREST_BEARING: u8 = 1 << 1;
+pub const FLAG_NON_TRANSFERABLE: u8 = 1 << 2;
+pub const FLAG_PERMANENT_DELEGATE: u8 = 1 << 3;
+pub const FLAG_DEFAULT_ACCOUNT_STATE: u8 = 1 << 4;
+pub const FLAG_MINT_CLOSE_AUTHORITY: u8 = 1 << 5;
+pub const FLAGS_ALL: u8 = (1 << 6) - 1;
+
+/// AccountState for DefaultAccountState: 1 Initialized, 2 Frozen
+pub const ACCOUNT_STATE_INITIALIZED: u8 = 1;
+pub const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+/// Optional extensions chosen at mint creation. Every extension authority is the mint authority
+pub struct MintExtensions<'a> {
+  pub flags: u8,
+  pub transfer_fee_bps: u16,
+  pub maximum_fee: u64,
+  pub interest_rate: i16,
+  pub default_state: u8,
+  pub permanent_delegate: &'a [u8; 32],
+}
+impl MintExtensions<'_> {
+  pub fn has(&self, flag: u8) -> bool {
+    self.flags & flag != 0
+  }
+  /// space taken by the selected extensions, including TLV headers
+  pub fn space(&self) -> usize {
+    let mut space = 0;
+    if self.has(FLAG_TRANSFER_FEE) {
+      space += tok22_ext_space(TRANSFER_FEE_CONFIG_LEN);
+    }
+    if self.has(FLAG_INTEREST_BEARING) {
+      space += tok22_ext_space(INTEREST_BEARING_CONFIG_LEN);
+    }
+    if self.has(FLAG_NON_TRANSFERABLE) {
+      space += tok22_ext_space(NON_TRANSFERABLE_LEN);
+    }
+    if self.has(FLAG_PERMANENT_DELEGATE) {
+      space += tok22_ext_space(PERMANENT_DELEGATE_LEN);
+    }
+    if self.has(FLAG_DEFAULT_ACCOUNT_STATE) {
+      space += tok22_ext_space(DEFAULT_ACCOUNT_STATE_LEN);
+    }
+    if self.has(FLAG_MINT_CLOSE_AUTHORITY) {
+      space += tok22_ext_space(MINT_CLOSE_AUTHORITY_LEN);
+    }
+    space
+  }
+  pub fn check(&self) -> ProgramResult {
+    if self.flags & !FLAGS_ALL != 0 {
+      return Ee::Tok22ExtFlags.e();
+    }
+    if self.has(FLAG_TRANSFER_FEE) && self.transfer_fee_bps > 10_000 {
+      return Ee::TransferFeeBps.e();
+    }
+    //Token2022 rejects fees on a mint whose tokens can never be transferred
+    if self.has(FLAG_TRANSFER_FEE) && self.has(FLAG_NON_TRANSFERABLE) {
+      return Ee::NonTransferableFee.e();
+    }
+    if self.has(FLAG_DEFAULT_ACCOUNT_STATE)
+      && self.default_state != ACCOUNT_STATE_INITIALIZED
+      && self.default_state != ACCOUNT_STATE_FROZEN
+    {
+      return Ee::DefaultAccountState.e();
+    }
+    if self.has(FLAG_PERMANENT_DELEGATE) && self.permanent_delegate == &[0u8; 32] {
+      return Ee::PermanentDelegate.e();
+    }
+    Ok(())
+  }
+  /// Initialize the selected extensions. Must come before InitializeMint
+  pub fn invoke(
+    &self,
+    mint: &AccountView,
+    authority: &Address,
+    token_program: &Address,
+  ) -> ProgramResult {
+    let authority = authority.as_array();
+    if self.has(FLAG_TRANSFER_FEE) {
+      // [ix, Initialize, Some, config_authority, Some, withdraw_withheld_authority, bps, maximum_fee]
+      let mut data = [0u8; 78];
+      data[0] = TRANSFER_FEE_EXTENSION;
+      data[2] = 1;
+      data[3..35].copy_from_slice(authority);
+      data[35] = 1;
+      data[36..68].copy_from_slice(authority);
+      data[68..70].copy_from_slice(&self.transfer_fee_bps.to_le_bytes());
+      data[70..78].copy_from_slice(&self.maximum_fee.to_le_bytes());
+      invoke_mint_ext(mint, token_program, &data)?;
+    }
+    if self.has(FLAG_INTEREST_BEARING) {
+      // [ix, Initialize, rate_authority, rate]
+      let mut data = [0u8; 36];
+      data[0] = INTEREST_BEARING_MINT_EXTENSION;
+      data[2..34].copy_from_slice(authority);
+      data[34..36].copy_from_slice(&self.interest_rate.to_le_bytes());
+      invoke_mint_ext(mint, token_program, &data)?;
+    }
+    if self.has(FLAG_NON_TRANSFERABLE) {
+      invoke_mint_ext(mint, token_program, &[INITIALIZE_NON_TRANSFERABLE_MINT])?;
+    }
+    if self.has(FLAG_PERMANENT_DELEGATE) {
+      let mut data = [0u8; 33];
+      data[0] = INITIALIZE_PERMANENT_DELEGATE;
+      data[1..33].copy_from_slice(self.permanent_delegate);
+      invoke_mint_ext(mint, token_program, &data)?;
+    }
+    if self.has(FLAG_DEFAULT_ACCOUNT_STATE) {
+      let data = [DEFAULT_ACCOUNT_STATE_EXTENSION, 0, self.default_state];
+      invoke_mint_ext(mint, token_program, &data)?;
+    }
+    if self.has(FLAG_MINT_CLOSE_AUTHORITY) {
+      // [ix, Some, close_authority]
+      let mut data = [0u8; 34];
+      data[0] = INITIALIZE_MINT_CLOSE_AUTHORITY;
+      data[1] = 1;
+      data[2..34].copy_from_slice(authority);
+      invoke_mint_ext(mint, token_program, &data)?;
+    }
+    Ok(())
+  }
+}
+/// extension initializers only take the mint account
+fn invoke_mint_ext(mint: &AccountView, token_program: &Address, data: &[u8]) -> ProgramResult {
+  let accounts = [InstructionAccount::writable(mint.address())];
+  let ix = InstructionView {
+    program_id: token_program,
+    accounts: &accounts,
+    data,
+  };
+  invoke(&ix, &[mint])
+}
+
+//----------------== Transfer Fees
+/// max token accounts to harvest withheld fees from in one instruction
+pub const MAX_HARVEST_SOURCES: usize = 10;
+
+/// Move withheld fees from token accounts into the mint. Permissionless
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Mint
+///   1. `[WRITABLE]` Token accounts to harvest from, up to MAX_HARVEST_SOURCES
+pub struct HarvestWithheldToMint<'a> {
+  pub mint: &'a AccountView,
+  pub sources: &'a [AccountView],
+  pub token_program: &'a Address,
+}
+impl HarvestWithheldToMint<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let n = self.sources.len();
+    if n > MAX_HARVEST_SOURCES {
+      return Err(ProgramError::InvalidArgument);
+    }
+    // index 0 is the mint, then sources. Unused slots are cut off below
+    let views: [&AccountView; MAX_HARVEST_SOURCES + 1] = core::array::from_fn(|i| {
+      if (1..=n).contains(&i) {
+        &self.sources[i - 1]
+      } else {
+        self.mint
+      }
+    });
+    let accounts: [InstructionAccount; MAX_HARVEST_SOURCES + 1] =
+      core::array::from_fn(|i| InstructionAccount::writable(views[i].address()));
+    // [ix, HarvestWithheldTokensToMint]
+    let ix = InstructionView {
+      program_id: self.token_program,
+      accounts: &accounts[..n + 1],
+      data: &[TRANSFER_FEE_EXTENSION, 4],
+    };
+    invoke_with_bounds::<{ MAX_HARVEST_SOURCES + 1 }>(&ix, &views[..n + 1])
+  }
+}
+
+/// Withdraw withheld fees collected in the mint
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Mint
+///   1. `[WRITABLE]` Destination token account
+///   2. `[SIGNER]` Withdraw withheld authority
+pub struct WithdrawWithheldFromMint<'a> {
+  pub mint: &'a AccountView,
+  pub destination: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub token_program: &'a Address,
+}
+impl WithdrawWithheldFromMint<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let accounts = [
+      InstructionAccount::writable(self.mint.address()),
+      InstructionAccount::writable(self.destination.address()),
+      InstructionAccount::readonly_signer(self.authority.address()),
+    ];
+    // [ix, WithdrawWithheldTokensFromMint]
+    let ix = InstructionView {
+      program_id: self.token_program,
+      accounts: &accounts,
+      data: &[TRANSFER_FEE_EXTENSION, 2],
+    };
+    invoke(&ix, &[self.mint, self.destination, self.authority])
+  }
+}
+
+//----------------== Transfer Hook
+/// seed of the ExtraAccountMetaList PDA under the hook program: ["extra-account-metas", mint]
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+/// max accounts forwarded to a hooked TransferChecked: hook program + ExtraAccountMetaList + resolved extras
+pub const MAX_HOOK_ACCOUNTS: usize = 10;
+
+/// find an extension value in Token2022 account data by walking its TLV entries
+pub fn tok22_ext_data(data: &[u8], ext_type: u16) -> Option<&[u8]> {
+  let mut offset = TOK22_EXT_START;
+  while offset + TOK22_TLV_HEADER <= data.len() {
+    let t = u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+    let start = offset + TOK22_TLV_HEADER;
+    if t == 0 || start + len > data.len() {
+      return None;
+    }
+    if t == ext_type {
+      return Some(&data[start..start + len]);
+    }
+    offset = start + len;
+  }
+  None
+}
+/// hook program of a Token2022 mint, or None if the mint has no TransferHook or it is unset
+pub fn transfer_hook_program(mint: &AccountView) -> Result<Option<Address>, ProgramError> {
+  if mint.data_len() <= TOK22_EXT_START {
+    return Ok(None);
+  }
+  let data = mint.try_borrow()?;
+  let Some(value) = tok22_ext_data(&data, EXT_TRANSFER_HOOK) else {
+    return Ok(None);
+  };
+  if value.len() != TRANSFER_HOOK_LEN {
+    return Ok(None);
+  }
+  let program_id: [u8; 32] = value[32..64]
+    .try_into()
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+  if program_id == [0u8; 32] {
+    return Ok(None);
+  }
+  Ok(Some(Address::new_from_array(program_id)))
+}
+/// group (collection) of a Token2022 member mint, or None unless its GroupMemberPointer names the mint itself and the mint holds the TokenGroupMember
+pub fn group_of_member(mint: &AccountView) -> Result<Option<Address>, ProgramError> {
+  if !mint.owned_by(&pinocchio_token_2022::ID) || mint.data_len() <= TOK22_EXT_START {
+    return Ok(None);
+  }
+  let data = mint.try_borrow()?;
+  let Some(pointer) = tok22_ext_data(&data, EXT_GROUP_MEMBER_POINTER) else {
+    return Ok(None);
+  };
+  if pointer.len() != GROUP_MEMBER_POINTER_LEN || &pointer[32..64] != mint.address().as_ref() {
+    return Ok(None);
+  }
+  let Some(member) = tok22_ext_data(&data, EXT_TOKEN_GROUP_MEMBER) else {
+    return Ok(None);
+  };
+  if member.len() != TOKEN_GROUP_MEMBER_LEN || &member[0..32] != mint.address().as_ref() {
+    return Ok(None);
+  }
+  let group: [u8; 32] = member[32..64]
+    .try_into()
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+  Ok(Some(Address::new_from_array(group)))
+}
+/// check the hook program and its ExtraAccountMetaList are among the forwarded accounts.
+/// Token2022 resolves the extra accounts themselves from the same list during the transfer
+pub fn check_hook_accounts(
+  mint: &AccountView,
+  hook_program: &Address,
+  hook_accounts: &[AccountView],
+) -> ProgramResult {
+  if hook_accounts.len() > MAX_HOOK_ACCOUNTS {
+    return Ee::TransferHookAccounts.e();
+  }
+  let (validation, _) = Address::try_find_program_address(
+    &[EXTRA_ACCOUNT_METAS_SEED, mint.address().as_ref()],
+    hook_program,
+  )
+  .ok_or(ProgramError::InvalidSeeds)?;
+  let has = |addr: &Address| hook_accounts.iter().any(|a| a.address() == addr);
+  if !has(hook_program) || !has(&validation) {
+    return Ee::TransferHookAccounts.e();
+  }
+  Ok(())
+}
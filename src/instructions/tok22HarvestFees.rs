@@ -0,0 +1,103 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_ata22, check_mint22a, check_pda, check_vault, executable, instructions::check_signer,
+  net_received, tok_acct_amount, writable, Config, Ee, HarvestWithheldToMint,
+  WithdrawWithheldFromMint, MAX_HARVEST_SOURCES,
+};
+
+/// Token2022: Admin harvests withheld transfer fees into the Vault ATA
+pub struct Tok22HarvestFees<'a> {
+  pub admin: &'a AccountView, //signer, also the mint's withdraw withheld authority
+  pub mint: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub sources: &'a [AccountView],
+  pub config: &'a mut Config,
+}
+impl<'a> Tok22HarvestFees<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &25;
+
+  pub fn process(self) -> ProgramResult {
+    let Tok22HarvestFees {
+      admin,
+      mint,
+      vault_ata,
+      token_program,
+      sources,
+      config,
+    } = self;
+    log!("Tok22HarvestFees process()");
+
+    if !sources.is_empty() {
+      log!("Harvest from {} token accounts", sources.len());
+      HarvestWithheldToMint {
+        mint,
+        sources,
+        token_program: token_program.address(),
+      }
+      .invoke()?;
+    }
+
+    let balc_before = tok_acct_amount(vault_ata)?;
+    log!("Withdraw withheld fees from mint");
+    WithdrawWithheldFromMint {
+      mint,
+      destination: vault_ata,
+      authority: admin,
+      token_program: token_program.address(),
+    }
+    .invoke()?;
+    let fees = net_received(vault_ata, balc_before)?;
+
+    config.add_token_balance(mint.address(), fees)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Tok22HarvestFees<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("Tok22HarvestFees try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [admin, config_pda, mint, vault, vault_ata, token_program, sources @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(admin)?;
+    executable(token_program)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    writable(mint)?;
+    writable(vault_ata)?;
+    check_mint22a(mint, token_program)?;
+    if sources.len() > MAX_HARVEST_SOURCES {
+      return Err(ProgramError::InvalidArgument);
+    }
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_address(config_pda)?;
+
+    if config.admin().ne(admin.address()) && config.prog_owner().ne(admin.address()) {
+      return Err(ProgramError::IncorrectAuthority);
+    }
+    if !config.mints().contains(&mint.address()) {
+      return Err(Ee::MintNotAccepted.into());
+    }
+    check_vault(vault, config.vault())?;
+    check_ata22(vault_ata, vault, mint)?;
+
+    Ok(Self {
+      admin,
+      mint,
+      vault_ata,
+      token_program,
+      sources,
+      config,
+    })
+  }
+}
@@ -5,15 +5,17 @@ use pinocchio_system::instructions::CreateAccount;
 
 use crate::{
   check_data_len, check_decimals_max, check_rent_sysvar, check_sysprog, executable, initialized,
-  instructions::check_signer, not_initialized, to10bytes, to32bytes, to6bytes, writable,
+  instructions::check_signer, not_initialized, parse_u16, parse_u64, to10bytes, to32bytes,
+  to6bytes, tok22_ext_space, token_metadata_len, trim_zeros, writable, InitMetadataPointer,
+  InitTokenMetadata, MintExtensions, METADATA_POINTER_LEN, TOK22_EXT_START,
 };
-use pinocchio_token_2022::{instructions::InitializeMint, state::Mint};
+use pinocchio_token_2022::instructions::InitializeMint;
 
 //Initiate Token2022 Mint Account
 pub struct Token2022InitMint<'a> {
   pub payer: &'a AccountView, //signer
   pub mint: &'a AccountView,
-  pub mint_authority: &'a AccountView,
+  pub mint_authority: &'a AccountView, //signer for TokenMetadata
   pub token_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
   pub freeze_authority_opt: Option<&'a Address>, // or Pubkey
@@ -21,6 +23,7 @@ pub struct Token2022InitMint<'a> {
   pub token_name: [u8; 10],
   pub token_symbol: [u8; 6],
   pub token_uri: [u8; 32],
+  pub extensions: MintExtensions<'a>,
 }
 impl<'a> Token2022InitMint<'a> {
   pub const DISCRIMINATOR: &'a u8 = &9;
@@ -37,28 +40,26 @@ impl<'a> Token2022InitMint<'a> {
       token_name,
       token_symbol,
       token_uri,
+      extensions,
     } = self;
     log!("Token2022InitMint process()");
 
-    /// [4 (extension discriminator) + 32 (update_authority) + 32 (metadata)]
-    pub const METADATA_POINTER_SIZE: usize = 4 + 32 + 32;
-    /// [4 (extension discriminator) + 32 (update_authority) + 32 (mint) + 10 (size of name ) + 6 (size of symbol) + 32 (size of uri) + 4 (size of additional_metadata)]
-    pub const METADATA_EXTENSION_BASE_SIZE: usize = 4 + 32 + 32 + 10 + 6 + 32 + 4;
-    /// Padding used so that Mint and Account extensions start at the same index
-    pub const EXTENSIONS_PADDING_AND_OFFSET: usize = 84;
+    let name = trim_zeros(&token_name);
+    let symbol = trim_zeros(&token_symbol);
+    let uri = trim_zeros(&token_uri);
 
-    let extension_size = METADATA_POINTER_SIZE
-      + METADATA_EXTENSION_BASE_SIZE
-      + token_name.len()
-      + token_symbol.len()
-      + token_uri.len();
-    let _total_mint_size = Mint::BASE_LEN + EXTENSIONS_PADDING_AND_OFFSET + extension_size;
+    // Only the MetadataPointer and optional extensions are allocated before InitializeMint. TokenMetadata is appended by Token2022 via realloc, so its rent is prefunded here
+    let space = TOK22_EXT_START + tok22_ext_space(METADATA_POINTER_LEN) + extensions.space();
+    let total_mint_size = space + tok22_ext_space(token_metadata_len(name, symbol, uri));
 
     let rent = Rent::from_account_view(rent_sysvar)?;
-    let lamports = rent.try_minimum_balance(Mint::BASE_LEN)?;
-    let space = Mint::BASE_LEN as u64;
-    log!("lamports: {}, space: {}", lamports, space);
-    //let mint = Keypair::new();
+    let lamports = rent.try_minimum_balance(total_mint_size)?;
+    log!(
+      "lamports: {}, space: {}, total: {}",
+      lamports,
+      space,
+      total_mint_size
+    );
 
     log!("Make Mint Account"); //payer and mint are both keypairs!
     CreateAccount {
@@ -66,10 +67,22 @@ impl<'a> Token2022InitMint<'a> {
       to: mint,    //Keypair
       owner: token_program.address(),
       lamports,
-      space,
+      space: space as u64,
+    }
+    .invoke()?;
+
+    log!("Init MetadataPointer");
+    InitMetadataPointer {
+      mint,
+      authority: Some(mint_authority.address()),
+      metadata_address: Some(mint.address()),
+      token_program: token_program.address(),
     }
     .invoke()?;
 
+    log!("Init optional extensions: {}", extensions.flags);
+    extensions.invoke(mint, mint_authority.address(), token_program.address())?;
+
     log!("Init Mint");
     InitializeMint {
       mint, //Keypair
@@ -81,60 +94,18 @@ impl<'a> Token2022InitMint<'a> {
     }
     .invoke()?;
 
-    /*TODO: add metadata:
-    https://solana.stackexchange.com/questions/16831/how-to-add-metadata-for-token-program
-    https://github.com/solana-foundation/anchor/blob/b6724d2bcbfb5531224057c49afaa4e8c50c5137/tests/spl/token-extensions/programs/token-extensions/src/instructions.rs#L31
-
-    https://github.com/solana-developers/program-examples
-        invoke(
-        &mpl_instruction::create_metadata_accounts_v3(
-            *token_metadata_program.key,
-            *metadata_account.key,
-            *mint_account.key,
-            *mint_authority.key,
-            *payer.key,
-            *mint_authority.key,
-            args.token_title,
-            args.token_symbol,
-            args.token_uri,
-            None,
-            0,
-            true,
-            false,
-            None,
-            None,
-            None,
-        ),
-        &[
-            metadata_account.clone(),
-            mint_account.clone(),
-            mint_authority.clone(),
-            payer.clone(),
-            token_metadata_program.clone(),
-            rent.clone(),
-        ],
-    )?;
-
-    https://solana.com/docs/tokens/extensions/metadata
-    Initialize MetadataPointer extension pointing to the Mint account
-    InitializeMetadataPointer {
-      mint: mint_account,
-      authority: Some(*payer.address()),
-      metadata_address: Some(*mint_account.address()),
-    }.invoke()?;
-
-     //https://www.helius.dev/blog/pinocchio#how-is-pinocchio-more-performant-than-solana-program
-
-     // Set the metadata within the Mint account
-    InitializeTokenMetadata {
-        metadata: mint,
-        update_authority: payer,
-        mint: mint,
-        mint_authority: payer,
-        name: &name,
-        symbol: &symbol,
-        uri: &uri,
-    }.invoke()?;*/
+    log!("Init TokenMetadata");
+    InitTokenMetadata {
+      metadata: mint,
+      update_authority: mint_authority,
+      mint,
+      mint_authority,
+      name,
+      symbol,
+      uri,
+      token_program: token_program.address(),
+    }
+    .invoke()?;
     Ok(())
   }
   pub fn init_if_needed(self) -> ProgramResult {
@@ -159,6 +130,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(payer)?;
+    check_signer(mint_authority)?;
     executable(token_program)?;
     check_sysprog(system_program)?;
     check_rent_sysvar(rent_sysvar)?;
@@ -174,7 +146,10 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
       None
     };
 
-    check_data_len(data, 49)?; //1+16+32=49
+    //1+16+32=49, then optional extensions: 1+2+8+2+1+32=46
+    if data.len() != 49 {
+      check_data_len(data, 95)?;
+    }
     let decimals = data[0];
     log!("decimals: {}", decimals);
     check_decimals_max(decimals, 18)?;
@@ -189,6 +164,28 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
     let token_uri = *to32bytes(&data[17..49])?;
     log!("token_uri: {}", &token_uri);
 
+    let extensions = if data.len() == 49 {
+      MintExtensions {
+        flags: 0,
+        transfer_fee_bps: 0,
+        maximum_fee: 0,
+        interest_rate: 0,
+        default_state: 0,
+        permanent_delegate: &[0u8; 32],
+      }
+    } else {
+      MintExtensions {
+        flags: data[49],
+        transfer_fee_bps: parse_u16(&data[50..52])?,
+        maximum_fee: parse_u64(&data[52..60])?,
+        interest_rate: i16::from_le_bytes([data[60], data[61]]),
+        default_state: data[62],
+        permanent_delegate: to32bytes(&data[63..95])?,
+      }
+    };
+    log!("extension flags: {}", extensions.flags);
+    extensions.check()?;
+
     Ok(Self {
       payer,
       mint,
@@ -200,6 +197,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
       token_name,
       token_symbol,
       token_uri,
+      extensions,
     })
   }
 }
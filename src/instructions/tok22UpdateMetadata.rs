@@ -0,0 +1,110 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_data_len, check_mint22a, check_rent_sysvar, check_sysprog, executable, get_rent_exempt,
+  instructions::check_signer, to10bytes, to32bytes, trim_zeros, writable, Ee, UpdateMetadataField,
+  FIELD_KEY,
+};
+
+/// Token2022 Update a TokenMetadata field stored inside the mint
+pub struct Token2022UpdateMetadata<'a> {
+  pub update_authority: &'a AccountView, //signer
+  pub mint: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub field: u8,
+  pub key: [u8; 10],
+  pub value: [u8; 32],
+}
+impl<'a> Token2022UpdateMetadata<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &24;
+
+  pub fn process(self) -> ProgramResult {
+    let Token2022UpdateMetadata {
+      update_authority,
+      mint,
+      token_program,
+      system_program: _,
+      rent_sysvar,
+      field,
+      key,
+      value,
+    } = self;
+    log!("Token2022UpdateMetadata process()");
+    let key = trim_zeros(&key);
+    let value = trim_zeros(&value);
+
+    // Token2022 reallocs the mint but does not pay for it. Prefund the worst case: a new key entry
+    let max_len = mint.data_len() + 4 + key.len() + 4 + value.len();
+    let min_lamport = get_rent_exempt(mint, rent_sysvar, max_len)?;
+    let prev_lamport = mint.lamports();
+    if min_lamport > prev_lamport {
+      log!("deposit lamports");
+      SystemTransfer {
+        from: update_authority,
+        to: mint,
+        lamports: min_lamport - prev_lamport,
+      }
+      .invoke()?;
+    }
+
+    log!("Update Metadata field: {}", field);
+    UpdateMetadataField {
+      metadata: mint,
+      update_authority,
+      field,
+      key,
+      value,
+      token_program: token_program.address(),
+    }
+    .invoke()?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022UpdateMetadata<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("Token2022UpdateMetadata try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [update_authority, mint, token_program, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(update_authority)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+    writable(mint)?;
+    check_mint22a(mint, token_program)?;
+
+    check_data_len(data, 43)?; //1+10+32=43
+    let field = data[0];
+    log!("field: {}", field);
+    if field > FIELD_KEY {
+      return Err(Ee::FunctionSelector.into());
+    }
+    let key = *to10bytes(&data[1..11])?;
+    if field == FIELD_KEY && trim_zeros(&key).is_empty() {
+      return Err(Ee::MetadataStrLen.into());
+    }
+    let value = *to32bytes(&data[11..43])?;
+    log!("value: {}", &value);
+
+    Ok(Self {
+      update_authority,
+      mint,
+      token_program,
+      system_program,
+      rent_sysvar,
+      field,
+      key,
+      value,
+    })
+  }
+}
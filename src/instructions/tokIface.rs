@@ -0,0 +1,267 @@
+use pinocchio::{
+  cpi::{invoke_signed_with_bounds, Signer},
+  error::ProgramError,
+  instruction::{InstructionAccount, InstructionView},
+  AccountView, ProgramResult,
+};
+use pinocchio_token_2022::state::Mint as Mint22;
+
+use crate::{
+  ata_balc, ata_balc22, check_ata, check_ata22, check_decimals, check_hook_accounts, check_mint0a,
+  check_mint22a, rent_exempt_mint, rent_exempt_mint22, rent_exempt_tokacct, rent_exempt_tokacct22,
+  transfer_hook_program, Ee, MAX_HOOK_ACCOUNTS,
+};
+
+//----------------== Token Interface
+// One implementation for both the legacy Token program and Token2022, selected by the token_program account
+
+/// true for Token2022, false for the legacy Token program
+pub fn is_token22(token_program: &AccountView) -> Result<bool, ProgramError> {
+  let program = token_program.address();
+  if program.eq(&pinocchio_token_2022::ID) {
+    return Ok(true);
+  }
+  if program.eq(&pinocchio_token::ID) {
+    return Ok(false);
+  }
+  Err(Ee::TokenProgram.into())
+}
+pub fn check_mint_iface(mint: &AccountView, token_program: &AccountView) -> ProgramResult {
+  if is_token22(token_program)? {
+    return check_mint22a(mint, token_program);
+  }
+  check_mint0a(mint, token_program)
+}
+pub fn check_decimals_iface(
+  mint: &AccountView,
+  token_program: &AccountView,
+  decimals: u8,
+) -> ProgramResult {
+  if is_token22(token_program)? {
+    let mint_info = Mint22::from_account_view(mint)?;
+    if decimals != mint_info.decimals() {
+      return Ee::DecimalsValue.e();
+    }
+    return Ok(());
+  }
+  check_decimals(mint, decimals)
+}
+/// NFT mint: 0 decimals and a supply of 1
+pub fn check_nft_iface(mint: &AccountView, token_program: &AccountView) -> ProgramResult {
+  let (decimals, supply) = if is_token22(token_program)? {
+    let mint_info = Mint22::from_account_view(mint)?;
+    (mint_info.decimals(), mint_info.supply())
+  } else {
+    let mint_info = pinocchio_token::state::Mint::from_account_view(mint)?;
+    (mint_info.decimals(), mint_info.supply())
+  };
+  if decimals != 0 || supply != 1 {
+    return Ee::EscrowNft.e();
+  }
+  Ok(())
+}
+/// which_mint: 0 MintX, 1 MintY, for the legacy error codes
+pub fn rent_exempt_mint_iface(
+  mint: &AccountView,
+  rent_sysvar: &AccountView,
+  token_program: &AccountView,
+  which_mint: u8,
+) -> ProgramResult {
+  if is_token22(token_program)? {
+    return rent_exempt_mint22(mint, rent_sysvar);
+  }
+  rent_exempt_mint(mint, rent_sysvar, which_mint)
+}
+pub fn check_ata_iface(
+  ata: &AccountView,
+  owner: &AccountView,
+  mint: &AccountView,
+  token_program: &AccountView,
+) -> ProgramResult {
+  if is_token22(token_program)? {
+    return check_ata22(ata, owner, mint);
+  }
+  check_ata(ata, owner, mint)
+}
+pub fn ata_balc_iface(
+  from_ata: &AccountView,
+  amount: u64,
+  token_program: &AccountView,
+) -> ProgramResult {
+  if is_token22(token_program)? {
+    return ata_balc22(from_ata, amount);
+  }
+  ata_balc(from_ata, amount)
+}
+pub fn rent_exempt_tokacct_iface(
+  ata: &AccountView,
+  rent_sysvar: &AccountView,
+  token_program: &AccountView,
+) -> ProgramResult {
+  if is_token22(token_program)? {
+    return rent_exempt_tokacct22(ata, rent_sysvar);
+  }
+  rent_exempt_tokacct(ata, rent_sysvar)
+}
+
+/// CloseAccount through either token program
+pub struct CloseAccountIface<'a> {
+  pub account: &'a AccountView,
+  pub destination: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub token_program: &'a AccountView,
+}
+impl CloseAccountIface<'_> {
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    if is_token22(self.token_program)? {
+      return pinocchio_token_2022::instructions::CloseAccount {
+        account: self.account,
+        destination: self.destination,
+        authority: self.authority,
+        token_program: self.token_program.address(),
+      }
+      .invoke_signed(signers);
+    }
+    pinocchio_token::instructions::CloseAccount {
+      account: self.account,
+      destination: self.destination,
+      authority: self.authority,
+    }
+    .invoke_signed(signers)
+  }
+}
+
+/// ApproveChecked through either token program
+pub struct ApproveIface<'a> {
+  pub source: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub delegate: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub amount: u64,
+  pub decimals: u8,
+  pub token_program: &'a AccountView,
+}
+impl ApproveIface<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    if is_token22(self.token_program)? {
+      return pinocchio_token_2022::instructions::ApproveChecked {
+        source: self.source,
+        mint: self.mint,
+        delegate: self.delegate,
+        authority: self.authority,
+        amount: self.amount,
+        decimals: self.decimals,
+        token_program: self.token_program.address(),
+      }
+      .invoke();
+    }
+    pinocchio_token::instructions::ApproveChecked {
+      source: self.source,
+      mint: self.mint,
+      delegate: self.delegate,
+      authority: self.authority,
+      amount: self.amount,
+      decimals: self.decimals,
+    }
+    .invoke()
+  }
+}
+
+/// Revoke through either token program
+pub struct RevokeIface<'a> {
+  pub source: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub token_program: &'a AccountView,
+}
+impl RevokeIface<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    if is_token22(self.token_program)? {
+      return pinocchio_token_2022::instructions::Revoke {
+        source: self.source,
+        authority: self.authority,
+        token_program: self.token_program.address(),
+      }
+      .invoke();
+    }
+    pinocchio_token::instructions::Revoke {
+      source: self.source,
+      authority: self.authority,
+    }
+    .invoke()
+  }
+}
+
+/// TransferChecked through either token program.
+/// hook_accounts are the instruction's remaining accounts, forwarded when the mint has a TransferHook
+pub struct TransferIface<'a> {
+  pub from: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub to: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub amount: u64,
+  pub decimals: u8,
+  pub token_program: &'a AccountView,
+  pub hook_accounts: &'a [AccountView],
+}
+impl TransferIface<'_> {
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    if is_token22(self.token_program)? {
+      if let Some(hook_program) = transfer_hook_program(self.mint)? {
+        check_hook_accounts(self.mint, &hook_program, self.hook_accounts)?;
+        return self.invoke_hooked(signers);
+      }
+      return pinocchio_token_2022::instructions::TransferChecked {
+        from: self.from,
+        mint: self.mint,
+        to: self.to,
+        authority: self.authority,
+        amount: self.amount,
+        decimals: self.decimals,
+        token_program: self.token_program.address(),
+      }
+      .invoke_signed(signers);
+    }
+    pinocchio_token::instructions::TransferChecked {
+      from: self.from,
+      mint: self.mint,
+      to: self.to,
+      authority: self.authority,
+      amount: self.amount,
+      decimals: self.decimals,
+    }
+    .invoke_signed(signers)
+  }
+  /// TransferChecked with the hook accounts appended after the 4 base accounts
+  fn invoke_hooked(&self, signers: &[Signer]) -> ProgramResult {
+    let n = self.hook_accounts.len();
+    // unused slots repeat the mint and are cut off below
+    let views: [&AccountView; 4 + MAX_HOOK_ACCOUNTS] = core::array::from_fn(|i| match i {
+      0 => self.from,
+      1 => self.mint,
+      2 => self.to,
+      3 => self.authority,
+      _ if i - 4 < n => &self.hook_accounts[i - 4],
+      _ => self.mint,
+    });
+    let accounts: [InstructionAccount; 4 + MAX_HOOK_ACCOUNTS] = core::array::from_fn(|i| match i {
+      0 | 2 => InstructionAccount::writable(views[i].address()),
+      1 => InstructionAccount::readonly(views[i].address()),
+      3 => InstructionAccount::readonly_signer(views[i].address()),
+      _ => InstructionAccount::new(views[i].address(), views[i].is_writable(), false),
+    });
+    // [ix, amount, decimals]
+    let mut data = [0u8; 10];
+    data[0] = 12;
+    data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+    data[9] = self.decimals;
+    let ix = InstructionView {
+      program_id: self.token_program.address(),
+      accounts: &accounts[..n + 4],
+      data: &data,
+    };
+    invoke_signed_with_bounds::<{ 4 + MAX_HOOK_ACCOUNTS }>(&ix, &views[..n + 4], signers)
+  }
+}
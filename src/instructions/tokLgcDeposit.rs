@@ -9,23 +9,26 @@ use pinocchio_log::log;
 use pinocchio_system::instructions::CreateAccount;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_data_len, check_decimals, check_mint0a, check_pda,
-  check_rent_sysvar, check_sysprog, derive_pda1, executable, instructions::check_signer,
-  none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee, PROG_ADDR,
-  VAULT_SEED, VAULT_SIZE,
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_pda, check_rent_sysvar, check_sysprog, derive_pda1, executable,
+  instructions::check_signer, net_received, none_zero_u64, parse_u64, rent_exempt_mint_iface,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee, TransferIface, Vault,
+  PROG_ADDR, VAULT_SEED, VAULT_SIZE,
 };
 
-/// TokLgc: Users to Deposit Tokens
+/// TokLgc: Users to Deposit Tokens. Serves both Token and Token2022 via token_program
 pub struct TokLgcDeposit<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub vault: &'a AccountView,
   pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
   pub decimals: u8,
   pub amount: u64,
 }
@@ -39,10 +42,12 @@ impl<'a> TokLgcDeposit<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
       decimals,
       amount,
     } = self;
@@ -66,7 +71,7 @@ impl<'a> TokLgcDeposit<'a> {
       let rent = Rent::from_account_view(rent_sysvar)?;
       let needed_lamports = rent.try_minimum_balance(VAULT_SIZE)?;
 
-      log!("TokLgcDeposit 6e"); //1002240
+      log!("TokLgcDeposit 6e"); //1259760
       CreateAccount {
         from: user, //keypair
         to: vault,
@@ -75,6 +80,7 @@ impl<'a> TokLgcDeposit<'a> {
         owner: &PROG_ADDR,
       }
       .invoke_signed(&[seed_signer])?;
+      Vault::from_account_view(vault)?.set_bump(bump);
     }
     check_pda(vault)?;
     log!("TokLgcDeposit 7: vault is verified");
@@ -93,20 +99,26 @@ impl<'a> TokLgcDeposit<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, vault, mint)?;
-      rent_exempt_tokacct(to_ata, rent_sysvar)?;
+      check_ata_iface(to_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(to_ata, rent_sysvar, token_program)?;
     }
     log!("ToATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let balc_before = tok_acct_amount(to_ata)?;
+    TransferIface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: user,
       amount,
       decimals,
+      token_program,
+      hook_accounts,
     }
     .invoke()?;
+    let received = net_received(to_ata, balc_before)?;
+    none_zero_u64(received)?;
+    Config::from_account_view(config_pda)?.add_token_balance(mint.address(), received)?;
     Ok(())
   }
 }
@@ -118,7 +130,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, mint, config_pda, token_program, system_program, atoken_program, rent_sysvar] =
+    let [user, from_ata, to_ata, vault, mint, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -130,7 +142,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     check_rent_sysvar(rent_sysvar)?;
 
     writable(from_ata)?;
-    check_ata(from_ata, user, mint)?;
+    check_ata_iface(from_ata, user, mint, token_program)?;
     writable(to_ata)?;
     writable(vault)?;
     writable(config_pda)?;
@@ -143,19 +155,20 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
 
     log!("TokLgcDeposit try_from 9");
     config_pda.check_borrow_mut()?;
     let config: &mut Config = Config::from_account_view(&config_pda)?;
+    config.check_address(config_pda)?;
 
     if !config.mints().contains(&mint.address()) {
       return Err(Ee::MintNotAccepted.into());
     }
     log!("TokLgcDeposit try_from 10");
-    rent_exempt_mint(mint, rent_sysvar, 0)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -163,10 +176,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
       decimals,
       amount,
     })
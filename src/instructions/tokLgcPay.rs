@@ -3,12 +3,13 @@ use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_data_len, check_decimals, check_mint0a,
-  check_rent_sysvar, check_sysprog, check_vault, executable, instructions::check_signer,
-  none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee,
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_rent_sysvar, check_sysprog, check_vault, executable,
+  instructions::check_signer, net_received, none_zero_u64, parse_u64, rent_exempt_mint_iface,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee, TransferIface,
 };
 
-/// TokLgc: Users to Pay Tokens to VaultAdmin
+/// TokLgc: Users to Pay Tokens to VaultAdmin. Serves both Token and Token2022 via token_program
 pub struct TokLgcPay<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
@@ -16,10 +17,12 @@ pub struct TokLgcPay<'a> {
   pub vault: &'a AccountView,
   pub mint: &'a AccountView,
   pub config_pda: &'a AccountView,
+  pub config: &'a mut Config,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
   pub decimals: u8,
   pub amount: u64,
 }
@@ -34,10 +37,12 @@ impl<'a> TokLgcPay<'a> {
       vault,
       mint,
       config_pda: _,
+      config,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
       decimals,
       amount,
     } = self;
@@ -57,20 +62,27 @@ impl<'a> TokLgcPay<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, vault, mint)?;
-      rent_exempt_tokacct(to_ata, rent_sysvar)?;
+      check_ata_iface(to_ata, vault, mint, token_program)?;
+      rent_exempt_tokacct_iface(to_ata, rent_sysvar, token_program)?;
     }
     log!("Vault ATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let balc_before = tok_acct_amount(to_ata)?;
+    TransferIface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: user,
       amount,
       decimals,
+      token_program,
+      hook_accounts,
     }
     .invoke()?;
+
+    //record what the vault received, which can be less than amount after transfer fees
+    let received = net_received(to_ata, balc_before)?;
+    config.add_token_balance(mint.address(), received)?;
     Ok(())
   }
 }
@@ -82,7 +94,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, mint, config_pda, token_program, system_program, atoken_program, rent_sysvar] =
+    let [user, from_ata, to_ata, vault, mint, config_pda, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -97,7 +109,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     writable(to_ata)?;
     writable(vault)?;
     writable(config_pda)?;
-    check_ata(from_ata, user, mint)?;
+    check_ata_iface(from_ata, user, mint, token_program)?;
     log!("TokLgcPay try_from 5");
 
     //1+8: u8 takes 1, u64 takes 8 bytes
@@ -107,11 +119,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
 
     log!("TokLgcPay try_from 9");
     config_pda.check_borrow_mut()?;
     let config: &mut Config = Config::from_account_view(&config_pda)?;
+    config.check_address(config_pda)?;
 
     if !config.mints().contains(&mint.address()) {
       return Err(Ee::MintNotAccepted.into());
@@ -119,9 +132,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     check_vault(vault, config.vault())?;
 
     log!("LgcPay try_from 10");
-    rent_exempt_mint(mint, rent_sysvar, 0)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -130,10 +143,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
       vault,
       mint,
       config_pda,
+      config,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
       decimals,
       amount,
     })
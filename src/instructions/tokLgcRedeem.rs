@@ -7,24 +7,26 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_data_len, check_decimals, check_mint0a,
-  check_rent_sysvar, check_sysprog, check_vault, executable, instructions::check_signer,
-  none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee,
-  VAULT_SEED,
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_rent_sysvar, check_sysprog, check_vault, executable,
+  instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint_iface,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee, TransferIface, VAULT_SEED,
 };
 
-/// TokLgc: Users to Redeem Tokens from VaultPDA
+/// TokLgc: Users to Redeem Tokens from VaultPDA. Serves both Token and Token2022 via token_program
 pub struct TokLgcRedeem<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub vault: &'a AccountView,
-  pub prog_owner: &'a Address,
+  pub config_pda: &'a AccountView,
+  pub creator: &'a Address,
   pub mint: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
   pub vault_bump: u8,
   pub decimals: u8,
   pub amount: u64,
@@ -38,12 +40,14 @@ impl<'a> TokLgcRedeem<'a> {
       from_ata,
       to_ata,
       vault,
-      prog_owner,
+      config_pda,
+      creator,
       mint,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
       vault_bump,
       decimals,
       amount,
@@ -64,30 +68,33 @@ impl<'a> TokLgcRedeem<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, user, mint)?;
-      rent_exempt_tokacct(to_ata, rent_sysvar)?;
+      check_ata_iface(to_ata, user, mint, token_program)?;
+      rent_exempt_tokacct_iface(to_ata, rent_sysvar, token_program)?;
     }
     log!("ToATA is found/verified");
 
     //let bump_array = [vault_bump];
     let signer_seeds = [
       Seed::from(VAULT_SEED),
-      Seed::from(prog_owner.as_ref()),
+      Seed::from(creator.as_ref()),
       Seed::from(core::slice::from_ref(&vault_bump)), //&bump_array
     ];
     log!("TokLgcRedeem 7c");
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("TokLgcRedeem 8 Transfer Tokens");
-    pinocchio_token::instructions::TransferChecked {
+    TransferIface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: vault,
       amount,
       decimals,
+      token_program,
+      hook_accounts,
     }
     .invoke_signed(&[seed_signer])?;
+    Config::from_account_view(config_pda)?.sub_token_balance(mint.address(), amount);
     Ok(())
   }
 }
@@ -99,7 +106,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, config_pda, mint, token_program, system_program, atoken_program, rent_sysvar] =
+    let [user, from_ata, to_ata, vault, config_pda, mint, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -112,7 +119,8 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
 
     writable(from_ata)?;
     writable(to_ata)?;
-    check_ata(from_ata, vault, mint)?;
+    writable(config_pda)?;
+    check_ata_iface(from_ata, vault, mint, token_program)?;
 
     //1+8: u8 takes 1, u64 takes 8 bytes
     check_data_len(data, 9)?;
@@ -121,38 +129,42 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
 
     log!("TokLgcPay try_from 9");
     config_pda.check_borrow_mut()?;
     let config: &mut Config = Config::from_account_view(&config_pda)?;
+    config.check_address(config_pda)?;
 
     if !config.mints().contains(&mint.address()) {
       return Err(Ee::MintNotAccepted.into());
     }
     check_vault(vault, config.vault())?;
-    /*let (expected_vault, vault_bump) = derive_pda1(config.prog_owner(), VAULT_SEED)?;
+    config.check_uncommitted(mint.address(), amount, tok_acct_amount(from_ata)?)?;
+    /*let (expected_vault, vault_bump) = derive_pda1(config.creator(), VAULT_SEED)?;
     log!("TokLgcPay try_from 9");
     if vault.address() != &expected_vault {
       return Err(Ee::VaultPDA.into());
     }*/
 
     log!("TokLgcRedeem try_from 12");
-    rent_exempt_mint(mint, rent_sysvar, 0)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
 
     Ok(Self {
       user,
       from_ata,
       to_ata,
       vault,
-      prog_owner: config.prog_owner(),
+      config_pda,
+      creator: config.creator(),
       mint,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
       vault_bump: config.vault_bump(),
       decimals,
       amount,
@@ -7,22 +7,26 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_data_len, check_decimals, check_mint0a,
-  check_rent_sysvar, check_sysprog, derive_pda1, executable, instructions::check_signer,
-  none_zero_u64, parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Ee, VAULT_SEED,
+  ata_balc_iface, check_ata_iface, check_atoken_gpvbd, check_data_len, check_decimals_iface,
+  check_mint_iface, check_pda, check_rent_sysvar, check_sysprog, check_vault_lock, derive_pda1,
+  executable, instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint_iface,
+  rent_exempt_tokacct_iface, tok_acct_amount, writable, Config, Ee, TransferIface, VAULT_SEED,
 };
 
-/// TokLgc: Users to Withdraw Tokens
+/// TokLgc: Users to Withdraw Tokens. Locked tokens stay in the vault. Serves both Token and Token2022 via token_program
 pub struct TokLgcWithdraw<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub vault: &'a AccountView,
   pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub lock: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub rent_sysvar: &'a AccountView,
+  pub hook_accounts: &'a [AccountView], //remaining accounts for TransferHook mints
   pub vault_bump: u8,
   pub decimals: u8,
   pub amount: u64,
@@ -37,10 +41,13 @@ impl<'a> TokLgcWithdraw<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
+      lock: _,
       token_program,
       system_program,
       atoken_program: _,
       rent_sysvar,
+      hook_accounts,
       vault_bump,
       decimals,
       amount,
@@ -61,8 +68,8 @@ impl<'a> TokLgcWithdraw<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, user, mint)?;
-      rent_exempt_tokacct(to_ata, rent_sysvar)?;
+      check_ata_iface(to_ata, user, mint, token_program)?;
+      rent_exempt_tokacct_iface(to_ata, rent_sysvar, token_program)?;
     }
     log!("ToATA is found/verified");
 
@@ -75,15 +82,18 @@ impl<'a> TokLgcWithdraw<'a> {
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("Transfer Tokens");
-    pinocchio_token::instructions::TransferChecked {
+    TransferIface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: vault,
       amount,
       decimals,
+      token_program,
+      hook_accounts,
     }
     .invoke_signed(&[seed_signer])?;
+    Config::from_account_view(config_pda)?.sub_token_balance(mint.address(), amount);
     Ok(())
   }
 }
@@ -95,7 +105,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, mint, token_program, system_program, atoken_program, rent_sysvar] =
+    let [user, from_ata, to_ata, vault, mint, config_pda, lock, token_program, system_program, atoken_program, rent_sysvar, hook_accounts @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -107,10 +117,11 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     check_rent_sysvar(rent_sysvar)?;
 
     writable(from_ata)?;
-    check_ata(from_ata, vault, mint)?;
+    check_ata_iface(from_ata, vault, mint, token_program)?;
     writable(to_ata)?;
-    //writable(config_pda)?;
-    //check_pda(config_pda)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    Config::from_account_view(config_pda)?.check_address(config_pda)?;
 
     //1+8: u8 takes 1, u64 takes 8 bytes
     check_data_len(data, 9)?;
@@ -120,17 +131,23 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_iface(from_ata, amount, token_program)?;
 
     let (expected_vault, vault_bump) = derive_pda1(user.address(), VAULT_SEED)?;
     if vault.address() != &expected_vault {
       return Err(Ee::VaultPDA.into());
     }
+    check_vault_lock(
+      user.address(),
+      mint.address().as_array(),
+      lock,
+      tok_acct_amount(from_ata)? - amount,
+    )?;
 
     log!("TokLgcWithdraw try_from 12");
-    rent_exempt_mint(mint, rent_sysvar, 0)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_iface(mint, rent_sysvar, token_program, 0)?;
+    check_decimals_iface(mint, token_program, decimals)?;
+    check_mint_iface(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -138,10 +155,13 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
+      lock,
       token_program,
       system_program,
       atoken_program,
       rent_sysvar,
+      hook_accounts,
       vault_bump,
       decimals,
       amount,
@@ -0,0 +1,64 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, close_pda, get_time, instructions::check_signer, writable, Ee, VaultLock,
+};
+
+/// User closes a fully vested SOL lock. The SOL is already in the user's vault, so only the lock rent goes back to its funder
+pub struct UnlockSol<'a> {
+  pub user: &'a AccountView, //signer
+  pub lock: &'a AccountView,
+  pub funder: &'a AccountView,
+}
+impl<'a> UnlockSol<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &30;
+
+  pub fn process(self) -> ProgramResult {
+    let UnlockSol {
+      user: _,
+      lock,
+      funder,
+    } = self;
+    log!("UnlockSol process()");
+    close_vested_lock(lock, funder)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for UnlockSol<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("UnlockSol try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [user, lock, funder] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(lock)?;
+    writable(funder)?;
+
+    let state = VaultLock::from_account_view(lock)?;
+    if state.user() != user.address() || state.funder() != funder.address() {
+      return Err(Ee::LockPDA.into());
+    }
+    if state.mint().as_array() != &VaultLock::SOL_MINT {
+      return Err(Ee::LockPDA.into());
+    }
+    Ok(Self { user, lock, funder })
+  }
+}
+
+/// Close a VaultLock once nothing is locked, so the user can be granted a new one
+pub fn close_vested_lock(lock: &AccountView, funder: &AccountView) -> ProgramResult {
+  let locked = VaultLock::from_account_view(lock)?.locked(get_time()?)?;
+  log!("locked: {}", locked);
+  if locked > 0 {
+    return Ee::LockedAmount.e();
+  }
+  log!("Close VaultLock");
+  close_pda(lock, funder)
+}
@@ -0,0 +1,60 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, close_vested_lock, instructions::check_signer, writable, Ee, VaultLock,
+};
+
+/// User closes a fully vested token lock. The tokens are already in the user's vault ATA, so only the lock rent goes back to its funder
+pub struct UnlockTok<'a> {
+  pub user: &'a AccountView, //signer
+  pub lock: &'a AccountView,
+  pub funder: &'a AccountView,
+  pub mint: &'a AccountView,
+}
+impl<'a> UnlockTok<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &31;
+
+  pub fn process(self) -> ProgramResult {
+    let UnlockTok {
+      user: _,
+      lock,
+      funder,
+      mint: _,
+    } = self;
+    log!("UnlockTok process()");
+    close_vested_lock(lock, funder)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for UnlockTok<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("UnlockTok try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [user, lock, funder, mint] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(lock)?;
+    writable(funder)?;
+
+    let state = VaultLock::from_account_view(lock)?;
+    if state.user() != user.address() || state.funder() != funder.address() {
+      return Err(Ee::LockPDA.into());
+    }
+    if state.mint() != mint.address() {
+      return Err(Ee::LockPDA.into());
+    }
+    Ok(Self {
+      user,
+      lock,
+      funder,
+      mint,
+    })
+  }
+}
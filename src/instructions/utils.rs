@@ -276,6 +276,147 @@ pub enum Ee {
   SimpleAcctOwner,
   #[error("SimpleAcctWriteAuthority")]
   SimpleAcctWriteAuthority,
+  //Token2022 Extensions
+  #[error("MetadataStrLen")]
+  MetadataStrLen,
+  #[error("Tok22ExtFlags")]
+  Tok22ExtFlags,
+  #[error("TransferFeeBps")]
+  TransferFeeBps,
+  #[error("DefaultAccountState")]
+  DefaultAccountState,
+  #[error("PermanentDelegate")]
+  PermanentDelegate,
+  #[error("TransferHookAccounts")]
+  TransferHookAccounts,
+  #[error("YieldPool")]
+  YieldPool,
+  #[error("YieldRate")]
+  YieldRate,
+  #[error("YieldPoolEmpty")]
+  YieldPoolEmpty,
+  #[error("LockPDA")]
+  LockPDA,
+  #[error("LockExists")]
+  LockExists,
+  #[error("LockSchedule")]
+  LockSchedule,
+  #[error("LockedAmount")]
+  LockedAmount,
+  #[error("DelegatePDA")]
+  DelegatePDA,
+  #[error("DelegateCap")]
+  DelegateCap,
+  #[error("MultisigPDA")]
+  MultisigPDA,
+  #[error("MultisigMembers")]
+  MultisigMembers,
+  #[error("OnlyMember")]
+  OnlyMember,
+  #[error("ProposalPDA")]
+  ProposalPDA,
+  #[error("ProposalExecuted")]
+  ProposalExecuted,
+  #[error("ProposalThreshold")]
+  ProposalThreshold,
+  #[error("TimelockPDA")]
+  TimelockPDA,
+  #[error("QueuedActionPDA")]
+  QueuedActionPDA,
+  #[error("TimelockEta")]
+  TimelockEta,
+  #[error("Timelocked")]
+  Timelocked,
+  #[error("CloseDest")]
+  CloseDest,
+  #[error("VaultAta")]
+  VaultAta,
+  #[error("VaultAtaNotEmpty")]
+  VaultAtaNotEmpty,
+  #[error("OpenEscrows")]
+  OpenEscrows,
+  #[error("MakerIndexPDA")]
+  MakerIndexPDA,
+  #[error("EscrowIdUsed")]
+  EscrowIdUsed,
+  #[error("EscrowAmend")]
+  EscrowAmend,
+  #[error("TakeMinX")]
+  TakeMinX,
+  #[error("TakeMaxY")]
+  TakeMaxY,
+  #[error("EscrowSeq")]
+  EscrowSeq,
+  #[error("EscrowSolLeg")]
+  EscrowSolLeg,
+  #[error("BasketPDA")]
+  BasketPDA,
+  #[error("BasketLegs")]
+  BasketLegs,
+  #[error("BasketTaken")]
+  BasketTaken,
+  #[error("EscrowNft")]
+  EscrowNft,
+  #[error("EscrowCollection")]
+  EscrowCollection,
+  #[error("EscrowDutch")]
+  EscrowDutch,
+  #[error("AuctionPDA")]
+  AuctionPDA,
+  #[error("AuctionBid")]
+  AuctionBid,
+  #[error("AuctionEnded")]
+  AuctionEnded,
+  #[error("AuctionNotEnded")]
+  AuctionNotEnded,
+  #[error("BidPDA")]
+  BidPDA,
+  #[error("EscrowTrigger")]
+  EscrowTrigger,
+  #[error("MilestonePDA")]
+  MilestonePDA,
+  #[error("MilestoneIndex")]
+  MilestoneIndex,
+  #[error("MilestoneAuth")]
+  MilestoneAuth,
+  #[error("MilestoneDeadline")]
+  MilestoneDeadline,
+  #[error("SubscriptionPDA")]
+  SubscriptionPDA,
+  #[error("SubscriptionNotDue")]
+  SubscriptionNotDue,
+  #[error("SubscriptionEnded")]
+  SubscriptionEnded,
+  #[error("StreamPDA")]
+  StreamPDA,
+  #[error("StreamSchedule")]
+  StreamSchedule,
+  #[error("StreamNothingDue")]
+  StreamNothingDue,
+  #[error("DistributorPDA")]
+  DistributorPDA,
+  #[error("DistributorProof")]
+  DistributorProof,
+  #[error("DistributorClaimed")]
+  DistributorClaimed,
+  #[error("DistributorDeadline")]
+  DistributorDeadline,
+  #[error("InvoicePDA")]
+  InvoicePDA,
+  #[error("InvoicePaid")]
+  InvoicePaid,
+  #[error("InvoicePayer")]
+  InvoicePayer,
+  #[error("InvoiceOverdue")]
+  InvoiceOverdue,
+  #[error("MultisigGoverned")]
+  MultisigGoverned,
+  #[error("VaultCommitted")]
+  VaultCommitted,
+  #[error("OracleFeedId")]
+  OracleFeedId,
+  #[error("NonTransferableFee")]
+  NonTransferableFee,
   //Final variant
   #[error("NotMapped")]
   NotMapped,
@@ -418,6 +559,76 @@ impl TryFrom<u32> for Ee {
       119 => Ok(Ee::SimpleAcctDataLen),
       120 => Ok(Ee::SimpleAcctOwner),
       121 => Ok(Ee::SimpleAcctWriteAuthority),
+      122 => Ok(Ee::MetadataStrLen),
+      123 => Ok(Ee::Tok22ExtFlags),
+      124 => Ok(Ee::TransferFeeBps),
+      125 => Ok(Ee::DefaultAccountState),
+      126 => Ok(Ee::PermanentDelegate),
+      127 => Ok(Ee::TransferHookAccounts),
+      128 => Ok(Ee::YieldPool),
+      129 => Ok(Ee::YieldRate),
+      130 => Ok(Ee::YieldPoolEmpty),
+      131 => Ok(Ee::LockPDA),
+      132 => Ok(Ee::LockExists),
+      133 => Ok(Ee::LockSchedule),
+      134 => Ok(Ee::LockedAmount),
+      135 => Ok(Ee::DelegatePDA),
+      136 => Ok(Ee::DelegateCap),
+      137 => Ok(Ee::MultisigPDA),
+      138 => Ok(Ee::MultisigMembers),
+      139 => Ok(Ee::OnlyMember),
+      140 => Ok(Ee::ProposalPDA),
+      141 => Ok(Ee::ProposalExecuted),
+      142 => Ok(Ee::ProposalThreshold),
+      143 => Ok(Ee::TimelockPDA),
+      144 => Ok(Ee::QueuedActionPDA),
+      145 => Ok(Ee::TimelockEta),
+      146 => Ok(Ee::Timelocked),
+      147 => Ok(Ee::CloseDest),
+      148 => Ok(Ee::VaultAta),
+      149 => Ok(Ee::VaultAtaNotEmpty),
+      150 => Ok(Ee::OpenEscrows),
+      151 => Ok(Ee::MakerIndexPDA),
+      152 => Ok(Ee::EscrowIdUsed),
+      153 => Ok(Ee::EscrowAmend),
+      154 => Ok(Ee::TakeMinX),
+      155 => Ok(Ee::TakeMaxY),
+      156 => Ok(Ee::EscrowSeq),
+      157 => Ok(Ee::EscrowSolLeg),
+      158 => Ok(Ee::BasketPDA),
+      159 => Ok(Ee::BasketLegs),
+      160 => Ok(Ee::BasketTaken),
+      161 => Ok(Ee::EscrowNft),
+      162 => Ok(Ee::EscrowCollection),
+      163 => Ok(Ee::EscrowDutch),
+      164 => Ok(Ee::AuctionPDA),
+      165 => Ok(Ee::AuctionBid),
+      166 => Ok(Ee::AuctionEnded),
+      167 => Ok(Ee::AuctionNotEnded),
+      168 => Ok(Ee::BidPDA),
+      169 => Ok(Ee::EscrowTrigger),
+      170 => Ok(Ee::MilestonePDA),
+      171 => Ok(Ee::MilestoneIndex),
+      172 => Ok(Ee::MilestoneAuth),
+      173 => Ok(Ee::MilestoneDeadline),
+      174 => Ok(Ee::SubscriptionPDA),
+      175 => Ok(Ee::SubscriptionNotDue),
+      176 => Ok(Ee::SubscriptionEnded),
+      177 => Ok(Ee::StreamPDA),
+      178 => Ok(Ee::StreamSchedule),
+      179 => Ok(Ee::StreamNothingDue),
+      180 => Ok(Ee::DistributorPDA),
+      181 => Ok(Ee::DistributorProof),
+      182 => Ok(Ee::DistributorClaimed),
+      183 => Ok(Ee::DistributorDeadline),
+      184 => Ok(Ee::InvoicePDA),
+      185 => Ok(Ee::InvoicePaid),
+      186 => Ok(Ee::InvoicePayer),
+      187 => Ok(Ee::InvoiceOverdue),
+      188 => Ok(Ee::MultisigGoverned),
+      189 => Ok(Ee::VaultCommitted),
+      190 => Ok(Ee::OracleFeedId),
+      191 => Ok(Ee::NonTransferableFee),
       _ => Err(Ee::NotMapped.into()),
     }
   }
@@ -558,6 +769,76 @@ impl ToStr for Ee {
       Ee::SimpleAcctDataLen => "SimpleAcctDataLen",
       Ee::SimpleAcctOwner => "SimpleAcctOwner",
       Ee::SimpleAcctWriteAuthority => "SimpleAcctWriteAuthority",
+      Ee::MetadataStrLen => "MetadataStrLen",
+      Ee::Tok22ExtFlags => "Tok22ExtFlags",
+      Ee::TransferFeeBps => "TransferFeeBps",
+      Ee::DefaultAccountState => "DefaultAccountState",
+      Ee::PermanentDelegate => "PermanentDelegate",
+      Ee::TransferHookAccounts => "TransferHookAccounts",
+      Ee::YieldPool => "YieldPool",
+      Ee::YieldRate => "YieldRate",
+      Ee::YieldPoolEmpty => "YieldPoolEmpty",
+      Ee::LockPDA => "LockPDA",
+      Ee::LockExists => "LockExists",
+      Ee::LockSchedule => "LockSchedule",
+      Ee::LockedAmount => "LockedAmount",
+      Ee::DelegatePDA => "DelegatePDA",
+      Ee::DelegateCap => "DelegateCap",
+      Ee::MultisigPDA => "MultisigPDA",
+      Ee::MultisigMembers => "MultisigMembers",
+      Ee::OnlyMember => "OnlyMember",
+      Ee::ProposalPDA => "ProposalPDA",
+      Ee::ProposalExecuted => "ProposalExecuted",
+      Ee::ProposalThreshold => "ProposalThreshold",
+      Ee::TimelockPDA => "TimelockPDA",
+      Ee::QueuedActionPDA => "QueuedActionPDA",
+      Ee::TimelockEta => "TimelockEta",
+      Ee::Timelocked => "Timelocked",
+      Ee::CloseDest => "CloseDest",
+      Ee::VaultAta => "VaultAta",
+      Ee::VaultAtaNotEmpty => "VaultAtaNotEmpty",
+      Ee::OpenEscrows => "OpenEscrows",
+      Ee::MakerIndexPDA => "MakerIndexPDA",
+      Ee::EscrowIdUsed => "EscrowIdUsed",
+      Ee::EscrowAmend => "EscrowAmend",
+      Ee::TakeMinX => "TakeMinX",
+      Ee::TakeMaxY => "TakeMaxY",
+      Ee::EscrowSeq => "EscrowSeq",
+      Ee::EscrowSolLeg => "EscrowSolLeg",
+      Ee::BasketPDA => "BasketPDA",
+      Ee::BasketLegs => "BasketLegs",
+      Ee::BasketTaken => "BasketTaken",
+      Ee::EscrowNft => "EscrowNft",
+      Ee::EscrowCollection => "EscrowCollection",
+      Ee::EscrowDutch => "EscrowDutch",
+      Ee::AuctionPDA => "AuctionPDA",
+      Ee::AuctionBid => "AuctionBid",
+      Ee::AuctionEnded => "AuctionEnded",
+      Ee::AuctionNotEnded => "AuctionNotEnded",
+      Ee::BidPDA => "BidPDA",
+      Ee::EscrowTrigger => "EscrowTrigger",
+      Ee::MilestonePDA => "MilestonePDA",
+      Ee::MilestoneIndex => "MilestoneIndex",
+      Ee::MilestoneAuth => "MilestoneAuth",
+      Ee::MilestoneDeadline => "MilestoneDeadline",
+      Ee::SubscriptionPDA => "SubscriptionPDA",
+      Ee::SubscriptionNotDue => "SubscriptionNotDue",
+      Ee::SubscriptionEnded => "SubscriptionEnded",
+      Ee::StreamPDA => "StreamPDA",
+      Ee::StreamSchedule => "StreamSchedule",
+      Ee::StreamNothingDue => "StreamNothingDue",
+      Ee::DistributorPDA => "DistributorPDA",
+      Ee::DistributorProof => "DistributorProof",
+      Ee::DistributorClaimed => "DistributorClaimed",
+      Ee::DistributorDeadline => "DistributorDeadline",
+      Ee::InvoicePDA => "InvoicePDA",
+      Ee::InvoicePaid => "InvoicePaid",
+      Ee::InvoicePayer => "InvoicePayer",
+      Ee::InvoiceOverdue => "InvoiceOverdue",
+      Ee::MultisigGoverned => "MultisigGoverned",
+      Ee::VaultCommitted => "VaultCommitted",
+      Ee::OracleFeedId => "OracleFeedId",
+      Ee::NonTransferableFee => "NonTransferableFee",
       //Final Variant
       Ee::NotMapped => "NotMapped",
     }
@@ -616,8 +897,16 @@ pub fn check_mint0b(
 
 pub fn check_mint22a(mint: &AccountView, token_program: &AccountView) -> ProgramResult {
   //if !mint.owned_by(mint_authority)
-  if mint.data_len() != pinocchio_token_2022::state::Mint::BASE_LEN {
-    return Ee::MintDataLen.e();
+  //Mints with extensions are longer and carry the mint AccountType after the padding
+  let data_len = mint.data_len();
+  if data_len != Mint22::BASE_LEN {
+    if data_len <= TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET {
+      return Ee::MintDataLen.e();
+    }
+    if mint.try_borrow()?[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET] != TOKEN_2022_MINT_DISCRIMINATOR
+    {
+      return Ee::Tok22AcctDiscOffset.e();
+    }
   }
   if token_program.address().ne(&pinocchio_token_2022::ID) {
     return Ee::TokenProgram.e();
@@ -674,6 +963,35 @@ pub fn amount_from_token_acct(account: &AccountView) -> Result<u64, ProgramError
   let balance = parse_u64(&data[64..72])?;
   Ok(balance)
 }
+/// token amount of a legacy or Token2022 token account. Both share the first 165 bytes
+pub fn tok_acct_amount(account: &AccountView) -> Result<u64, ProgramError> {
+  if !account.owned_by(&pinocchio_token::ID) && !account.owned_by(&pinocchio_token_2022::ID) {
+    return Err(Ee::TokenAcctOwner.into());
+  }
+  if account.data_len() < pinocchio_token::state::TokenAccount::LEN {
+    return Err(Ee::TokenAcctDataLen.into());
+  }
+  let data = account.try_borrow()?;
+  parse_u64(&data[64..72])
+}
+/// the delegate of a token account of either token program, if any
+pub fn tok_acct_delegate(account: &AccountView) -> Result<Option<Address>, ProgramError> {
+  tok_acct_amount(account)?;
+  let data = account.try_borrow()?;
+  //COption<Address>: u32 tag then 32 bytes
+  if parse_u32(&data[72..76])? == 0 {
+    return Ok(None);
+  }
+  Ok(Some(Address::new_from_array(*to32bytes(&data[76..108])?)))
+}
+/// amount actually received by to_ata since balc_before, e.g. after a Token2022 transfer fee
+pub fn net_received(to_ata: &AccountView, balc_before: u64) -> Result<u64, ProgramError> {
+  let received = tok_acct_amount(to_ata)?
+    .checked_sub(balc_before)
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+  log!("net received: {}", received);
+  Ok(received)
+}
 pub fn ata_balc(from_ata: &AccountView, amount: u64) -> ProgramResult {
   let from_ata_info = TokenAccount::from_account_view(from_ata)?;
   if from_ata_info.amount() < amount {
@@ -773,6 +1091,25 @@ pub fn derive_pda1(user: &Address, bstr: &[u8]) -> Result<(Address, u8), Program
   Address::try_find_program_address(&[bstr, user.as_ref()], &PROG_ADDR)
     .ok_or_else(|| ProgramError::InvalidSeeds)
 }
+/// PDA from a seed string, a user and a second key such as a mint
+pub fn derive_pda2(
+  user: &Address,
+  key2: &[u8],
+  bstr: &[u8],
+) -> Result<(Address, u8), ProgramError> {
+  Address::try_find_program_address(&[bstr, user.as_ref(), key2], &PROG_ADDR)
+    .ok_or(ProgramError::InvalidSeeds)
+}
+/// PDA from a seed string, a user and two more keys such as a config and a mint
+pub fn derive_pda3(
+  user: &Address,
+  key2: &[u8],
+  key3: &[u8],
+  bstr: &[u8],
+) -> Result<(Address, u8), ProgramError> {
+  Address::try_find_program_address(&[bstr, user.as_ref(), key2, key3], &PROG_ADDR)
+    .ok_or(ProgramError::InvalidSeeds)
+}
 /*let pda = pubkey::create_program_address(
     &[PDA_SEED, &[self.datas.bump as u8]],
     &PROG_ADDR,
@@ -811,6 +1148,19 @@ pub fn check_atoken_gpvbd(account: &AccountView) -> ProgramResult {
   }
   Ok(())
 }
+/// Associated token account address of wallet for mint under token_program
+pub fn derive_ata(
+  wallet: &Address,
+  mint: &Address,
+  token_program: &Address,
+) -> Result<Address, ProgramError> {
+  Address::try_find_program_address(
+    &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+    &ATOKENGPVBD,
+  )
+  .map(|(ata, _bump)| ata)
+  .ok_or(ProgramError::InvalidSeeds)
+}
 pub fn check_rent_sysvar(account: &AccountView) -> ProgramResult {
   if account.address().ne(&RENT_ID) {
     return Ee::RentSysvar.e();
@@ -920,7 +1270,7 @@ pub fn rent_exempt(account: &AccountView, rent_sysvar: &AccountView) -> Result<(
 }
 pub fn rent_exempt_mint22(account: &AccountView, rent_sysvar: &AccountView) -> ProgramResult {
   let rent = Rent::from_account_view(rent_sysvar)?;
-  if !rent.is_exempt(account.lamports(), Mint22::BASE_LEN) {
+  if !rent.is_exempt(account.lamports(), account.data_len().max(Mint22::BASE_LEN)) {
     return Ee::NoRentExemptMint22.e();
   }
   Ok(())
@@ -951,7 +1301,10 @@ pub fn rent_exempt_tokacct(account: &AccountView, rent_sysvar: &AccountView) ->
 }
 pub fn rent_exempt_tokacct22(account: &AccountView, rent_sysvar: &AccountView) -> ProgramResult {
   let rent = Rent::from_account_view(rent_sysvar)?;
-  if !rent.is_exempt(account.lamports(), TokenAccount22::BASE_LEN) {
+  if !rent.is_exempt(
+    account.lamports(),
+    account.data_len().max(TokenAccount22::BASE_LEN),
+  ) {
     return Ee::NoRentExemptTokAcct22.e();
   }
   Ok(())
@@ -1025,6 +1378,10 @@ pub fn parse_u64(data: &[u8]) -> Result<u64, ProgramError> {
   // let amount = u64::from_le_bytes([data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7]]);
   Ok(amt)
 }
+pub fn parse_i64(data: &[u8]) -> Result<i64, ProgramError> {
+  let bytes: [u8; 8] = data.try_into().map_err(|_e| Ee::ByteSizeForU64)?;
+  Ok(i64::from_le_bytes(bytes))
+}
 pub fn parse_u32(data: &[u8]) -> Result<u32, ProgramError> {
   let bytes: [u8; 4] = data.try_into().or_else(|_e| Err(Ee::ByteSizeForU32))?;
 
@@ -1150,3 +1507,13 @@ pub fn get_time_i64() -> Result<i64, ProgramError> {
   log!("Solana time: {}", time);
   Ok(time)
 }
+/// Log fields as one "Program data:" line, base64 per field, for indexers to read as an event. A no-op off chain
+pub fn emit_event(fields: &[&[u8]]) {
+  #[cfg(target_os = "solana")]
+  // SAFETY: sol_log_data reads fields as a slice of byte slices
+  unsafe {
+    pinocchio::syscalls::sol_log_data(fields.as_ptr() as *const u8, fields.len() as u64);
+  }
+  #[cfg(not(target_os = "solana"))]
+  let _ = fields;
+}
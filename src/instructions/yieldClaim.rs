@@ -0,0 +1,128 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_data_len, check_rent_sysvar, derive_pda1, get_rent_exempt, get_time,
+  instructions::check_signer, none_zero_u64, writable, Config, Ee, Vault, YieldPool, VAULT_SEED,
+};
+
+/// Accrue vault yield up to now at the Config yield index. yield_accounts are the YieldPool and its Config PDA, or empty.
+/// The first YieldPool given is bound to the vault; later syncs need that same pool. A vault with no pool only moves its updated_at
+pub fn sync_vault_yield(vault: &mut Vault, yield_accounts: &[AccountView]) -> ProgramResult {
+  let now = get_time()?;
+  let [yield_pool, config_pda, ..] = yield_accounts else {
+    if !yield_accounts.is_empty() {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if vault.has_pool() {
+      return Ee::YieldPool.e();
+    }
+    vault.set_updated_at(now);
+    return Ok(());
+  };
+  let pool = YieldPool::from_account_view(yield_pool)?;
+  if pool.config() != config_pda.address() {
+    return Ee::YieldPool.e();
+  }
+  let index = Config::from_account_view(config_pda)?.yield_index(now)?;
+  if !vault.has_pool() {
+    log!("Bind vault to YieldPool");
+    vault.set_pool(yield_pool.address());
+    vault.set_yield_index(index);
+    vault.set_updated_at(now);
+    return Ok(());
+  }
+  if vault.pool() != yield_pool.address() {
+    return Ee::YieldPool.e();
+  }
+  vault.accrue(index, now)?;
+  log!("accrued: {}", vault.accrued());
+  Ok(())
+}
+
+/// User claims accrued SOL vault yield from the YieldPool
+pub struct ClaimYield<'a> {
+  pub user: &'a AccountView, //signer
+  pub vault: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub yield_pool: &'a AccountView,
+  pub yield_accounts: &'a [AccountView], //YieldPool and Config PDA
+}
+impl<'a> ClaimYield<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &27;
+
+  pub fn process(self) -> ProgramResult {
+    let ClaimYield {
+      user,
+      vault,
+      rent_sysvar,
+      yield_pool,
+      yield_accounts,
+    } = self;
+    log!("ClaimYield process()");
+    let state = Vault::from_account_view(vault)?;
+    sync_vault_yield(state, yield_accounts)?;
+    let accrued = state.accrued();
+    none_zero_u64(accrued)?;
+
+    // pay what the pool can afford above its rent minimum. The rest stays accrued
+    let pool_min_balc = get_rent_exempt(yield_pool, rent_sysvar, YieldPool::LEN)?;
+    let available = yield_pool.lamports().saturating_sub(pool_min_balc);
+    let paid = accrued.min(available);
+    if paid == 0 {
+      return Ee::YieldPoolEmpty.e();
+    }
+    yield_pool.set_lamports(yield_pool.lamports() - paid);
+    let sum_lam = user
+      .lamports()
+      .checked_add(paid)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    user.set_lamports(sum_lam);
+    state.set_accrued(accrued - paid);
+
+    let pool = YieldPool::from_account_view(yield_pool)?;
+    let total_paid = pool
+      .total_paid()
+      .checked_add(paid)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.set_total_paid(total_paid);
+    log!("{} lamports of yield claimed", paid);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ClaimYield<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ClaimYield try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    check_data_len(data, 0)?;
+
+    let [user, vault, rent_sysvar, yield_accounts @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [yield_pool, _config_pda] = yield_accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(vault)?;
+    writable(yield_pool)?;
+    check_rent_sysvar(rent_sysvar)?;
+    Vault::check(vault)?;
+    YieldPool::check(yield_pool)?;
+
+    let (expected_vault_pda, _bump) = derive_pda1(user.address(), VAULT_SEED)?;
+    if vault.address() != &expected_vault_pda {
+      return Err(Ee::VaultPDA.into());
+    }
+    Ok(Self {
+      user,
+      vault,
+      rent_sysvar,
+      yield_pool,
+      yield_accounts,
+    })
+  }
+}
@@ -0,0 +1,127 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_data_len, check_pda, check_rent_sysvar, check_sysprog, derive_pda1, get_time,
+  instructions::check_signer, parse_u16, parse_u64, writable, Config, Ee, YieldPool, PROG_ADDR,
+};
+
+/// Admin sets the Config SOL vault yield rate and funds the YieldPool rewards
+pub struct YieldFund<'a> {
+  pub admin: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub yield_pool: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub rent_sysvar: &'a AccountView,
+  pub rate_bps: u16,
+  pub amount: u64,
+}
+impl<'a> YieldFund<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &26;
+
+  pub fn process(self) -> ProgramResult {
+    let YieldFund {
+      admin,
+      config_pda,
+      yield_pool,
+      system_program: _,
+      rent_sysvar,
+      rate_bps,
+      amount,
+    } = self;
+    log!("YieldFund process()");
+
+    if yield_pool.lamports() == 0 {
+      let (expected_pool, bump) = derive_pda1(config_pda.address(), YieldPool::SEED)?;
+      if yield_pool.address() != &expected_pool {
+        return Ee::YieldPool.e();
+      }
+      log!("Make YieldPool");
+      let signer_seeds = [
+        Seed::from(YieldPool::SEED),
+        Seed::from(config_pda.address().as_ref()),
+        Seed::from(core::slice::from_ref(&bump)),
+      ];
+      let seed_signer = Signer::from(&signer_seeds);
+
+      let rent = Rent::from_account_view(rent_sysvar)?;
+      pinocchio_system::instructions::CreateAccount {
+        from: admin,
+        to: yield_pool,
+        lamports: rent.try_minimum_balance(YieldPool::LEN)?,
+        space: YieldPool::LEN as u64,
+        owner: &PROG_ADDR,
+      }
+      .invoke_signed(&[seed_signer])?;
+
+      let pool = YieldPool::from_account_view(yield_pool)?;
+      pool.set_config(config_pda.address());
+      pool.set_bump(bump);
+    }
+    let pool = YieldPool::from_account_view(yield_pool)?;
+    if pool.config() != config_pda.address() {
+      return Ee::YieldPool.e();
+    }
+    // yield up to now keeps the old rate, the new rate only applies from now on
+    Config::from_account_view(config_pda)?.set_yield_rate(rate_bps, get_time()?)?;
+    log!("rate_bps: {}", rate_bps);
+
+    if amount > 0 {
+      SystemTransfer {
+        from: admin,
+        to: yield_pool,
+        lamports: amount,
+      }
+      .invoke()?;
+      log!("{} lamports funded to YieldPool", amount);
+    }
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for YieldFund<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("YieldFund try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [admin, config_pda, yield_pool, system_program, rent_sysvar] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(admin)?;
+    writable(config_pda)?;
+    writable(yield_pool)?;
+    check_pda(config_pda)?;
+    check_sysprog(system_program)?;
+    check_rent_sysvar(rent_sysvar)?;
+
+    //2+8: u16 takes 2, u64 takes 8 bytes
+    check_data_len(data, 10)?;
+    let rate_bps = parse_u16(&data[0..2])?;
+    let amount = parse_u64(&data[2..10])?;
+    log!("rate_bps: {}, amount: {}", rate_bps, amount);
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.admin().ne(admin.address()) && config.prog_owner().ne(admin.address()) {
+      return Err(Ee::OnlyAdmin.into());
+    }
+    config.check_address(config_pda)?;
+    Ok(Self {
+      admin,
+      config_pda,
+      yield_pool,
+      system_program,
+      rent_sysvar,
+      rate_bps,
+      amount,
+    })
+  }
+}
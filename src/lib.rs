@@ -2,6 +2,8 @@
 - takes in the program ID, accounts, and instruction data, then reads the first byte as a discriminator to determine which method to call*/
 #![no_std]
 #![allow(unexpected_cfgs)]
+#[cfg(not(target_os = "solana"))]
+extern crate alloc; //for the off-chain MerkleTree helper
 use pinocchio::{entrypoint, error::ProgramError, AccountView, Address, ProgramResult};
 use pinocchio_pubkey::declare_id;
 
@@ -62,6 +64,62 @@ fn process_instruction(
     ConfigResize::DISCRIMINATOR => ConfigResize::try_from((data, accounts))?.process(),
     Config2Update::DISCRIMINATOR => Config2Update::try_from((data, accounts))?.process(),
     OraclesRead::DISCRIMINATOR => OraclesRead::try_from((data, accounts))?.process(),
+    Token2022UpdateMetadata::DISCRIMINATOR => {
+      Token2022UpdateMetadata::try_from((data, accounts))?.process()
+    }
+    Tok22HarvestFees::DISCRIMINATOR => Tok22HarvestFees::try_from((data, accounts))?.process(),
+    YieldFund::DISCRIMINATOR => YieldFund::try_from((data, accounts))?.process(),
+    ClaimYield::DISCRIMINATOR => ClaimYield::try_from((data, accounts))?.process(),
+    LockSol::DISCRIMINATOR => LockSol::try_from((data, accounts))?.process(),
+    LockTok::DISCRIMINATOR => LockTok::try_from((data, accounts))?.process(),
+    UnlockSol::DISCRIMINATOR => UnlockSol::try_from((data, accounts))?.process(),
+    UnlockTok::DISCRIMINATOR => UnlockTok::try_from((data, accounts))?.process(),
+    DelegateSet::DISCRIMINATOR => DelegateSet::try_from((data, accounts))?.process(),
+    DelegateRevoke::DISCRIMINATOR => DelegateRevoke::try_from((data, accounts))?.process(),
+    DelegateWithdrawSol::DISCRIMINATOR => {
+      DelegateWithdrawSol::try_from((data, accounts))?.process()
+    }
+    DelegateWithdrawTok::DISCRIMINATOR => {
+      DelegateWithdrawTok::try_from((data, accounts))?.process()
+    }
+    MultisigInit::DISCRIMINATOR => MultisigInit::try_from((data, accounts))?.process(),
+    ProposalMake::DISCRIMINATOR => ProposalMake::try_from((data, accounts))?.process(),
+    ProposalApprove::DISCRIMINATOR => ProposalApprove::try_from((data, accounts))?.process(),
+    ProposalExecute::DISCRIMINATOR => ProposalExecute::try_from((data, accounts))?.process(),
+    TimelockInit::DISCRIMINATOR => TimelockInit::try_from((data, accounts))?.process(),
+    TimelockQueue::DISCRIMINATOR => TimelockQueue::try_from((data, accounts))?.process(),
+    TimelockExecute::DISCRIMINATOR => TimelockExecute::try_from((data, accounts))?.process(),
+    TimelockCancel::DISCRIMINATOR => TimelockCancel::try_from((data, accounts))?.process(),
+    EscrowTokAmend::DISCRIMINATOR => EscrowTokAmend::try_from((data, accounts))?.process(),
+    EscrowSolMake::DISCRIMINATOR => EscrowSolMake::try_from((data, accounts))?.process(),
+    EscrowSolTake::DISCRIMINATOR => EscrowSolTake::try_from((data, accounts))?.process(),
+    EscrowSolClose::DISCRIMINATOR => EscrowSolClose::try_from((data, accounts))?.process(),
+    BasketMake::DISCRIMINATOR => BasketMake::try_from((data, accounts))?.process(),
+    BasketTake::DISCRIMINATOR => BasketTake::try_from((data, accounts))?.process(),
+    BasketClose::DISCRIMINATOR => BasketClose::try_from((data, accounts))?.process(),
+    AuctionMake::DISCRIMINATOR => AuctionMake::try_from((data, accounts))?.process(),
+    AuctionBid::DISCRIMINATOR => AuctionBid::try_from((data, accounts))?.process(),
+    AuctionSettle::DISCRIMINATOR => AuctionSettle::try_from((data, accounts))?.process(),
+    MilestoneMake::DISCRIMINATOR => MilestoneMake::try_from((data, accounts))?.process(),
+    MilestoneSettle::DISCRIMINATOR => MilestoneSettle::try_from((data, accounts))?.process(),
+    MilestoneDispute::DISCRIMINATOR => MilestoneDispute::try_from((data, accounts))?.process(),
+    MilestoneClose::DISCRIMINATOR => MilestoneClose::try_from((data, accounts))?.process(),
+    SubscriptionMake::DISCRIMINATOR => SubscriptionMake::try_from((data, accounts))?.process(),
+    SubscriptionPull::DISCRIMINATOR => SubscriptionPull::try_from((data, accounts))?.process(),
+    SubscriptionCancel::DISCRIMINATOR => SubscriptionCancel::try_from((data, accounts))?.process(),
+    StreamMake::DISCRIMINATOR => StreamMake::try_from((data, accounts))?.process(),
+    StreamWithdraw::DISCRIMINATOR => StreamWithdraw::try_from((data, accounts))?.process(),
+    StreamCancel::DISCRIMINATOR => StreamCancel::try_from((data, accounts))?.process(),
+    DistributorMake::DISCRIMINATOR => DistributorMake::try_from((data, accounts))?.process(),
+    DistributorClaim::DISCRIMINATOR => DistributorClaim::try_from((data, accounts))?.process(),
+    DistributorClawback::DISCRIMINATOR => {
+      DistributorClawback::try_from((data, accounts))?.process()
+    }
+    InvoiceMake::DISCRIMINATOR => InvoiceMake::try_from((data, accounts))?.process(),
+    InvoicePay::DISCRIMINATOR => InvoicePay::try_from((data, accounts))?.process(),
+    ConfigMigrate::DISCRIMINATOR => ConfigMigrate::try_from((data, accounts))?.process(),
+    EscrowMigrate::DISCRIMINATOR => EscrowMigrate::try_from((data, accounts))?.process(),
+    AuctionBidWithdraw::DISCRIMINATOR => AuctionBidWithdraw::try_from((data, accounts))?.process(),
     //UserInit::DISCRIMINATOR => UserInit::try_from((data, accounts))?.process(),
     _ => Err(Ee::MethodDiscriminator.into()),
   } //file names start with a lower case + Camel cases, but struct names start with Upper case + Camel cases!
@@ -1,34 +1,46 @@
 use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
 
-use crate::{none_zero_u64, Status};
+use crate::{none_zero_u64, Config, Status};
 
 //TODO: Bytemuck is a great library that makes it easy to read and write byte arrays as structs.
 #[derive(Clone, Debug)]
 #[repr(C)] //0..8 	Discriminator 	8 bytes
 pub struct Config2 {
-  mint0: Address,         // 32 bytes
-  mint1: Address,         // 32
-  mint2: Address,         // 32
-  mint3: Address,         // 32
-  vault: Address,         // 32
-  prog_owner: Address,    // 32
-  admin: Address,         // 32
-  str_u8array: [u8; 32],  // 32
-  fee: [u8; 8],           // 8 for u64,
-  sol_balance: [u8; 8],   // 8
-  token_balance: [u8; 8], // 8
-  updated_at: [u8; 4],    // 4 for u32
-  is_authorized: bool,    // 1
-  status: u8,             // 1
-  vault_bump: u8,         // 1
-  bump: u8,               // 1
-  new_u32: [u8; 4],       // 4 for u32
-  new_u64: [u8; 8],       // 8 for u64
-  new_account1: Address,  // 32
+  mint0: Address,               // 32 bytes
+  mint1: Address,               // 32
+  mint2: Address,               // 32
+  mint3: Address,               // 32
+  vault: Address,               // 32
+  prog_owner: Address,          // 32
+  admin: Address,               // 32
+  str_u8array: [u8; 32],        // 32
+  fee: [u8; 8],                 // 8 for u64,
+  sol_balance: [u8; 8],         // 8
+  token_balance: [u8; 8],       // 8
+  updated_at: [u8; 4],          // 4 for u32
+  is_authorized: bool,          // 1
+  status: u8,                   // 1
+  vault_bump: u8,               // 1
+  bump: u8,                     // 1
+  open_escrows: [u8; 8],        // 8 for u64
+  creator: Address,             // 32
+  timelock_delay: [u8; 4],      // 4 for u32
+  token_balances: [[u8; 8]; 4], // 32 for u64 per config mint
+  committed: [[u8; 8]; 4],      // 32 for u64 per config mint
+  yield_rate_bps: [u8; 2],      // 2 for u16
+  yield_index: [u8; 8],         // 8 for u64
+  yield_index_at: [u8; 4],      // 4 for u32
+  new_u32: [u8; 4],             // 4 for u32
+  new_u64: [u8; 8],             // 8 for u64
+  new_account1: Address,        // 32
 } // padding: [u8; 6] if the struct size needs to be aligned to 32 bytes.
+  // Config2 is a resized Config: it must start with every Config field, then its own fields
+const _: () = assert!(core::mem::offset_of!(Config2, new_u32) == Config::INIT_LEN);
 
 impl Config2 {
   pub const INIT_LEN: usize = core::mem::size_of::<Self>();
+  /// Config2 accounts made before open_escrows have new_u32.. right after bump
+  pub const LEGACY_LEN: usize = Config::LEGACY_LEN + Self::INIT_LEN - Config::INIT_LEN;
   pub const SEED: &[u8] = b"config";
   //Getters or Accessors: Safe Direct value copy, no reference created
   pub fn mint0(&self) -> &Address {
@@ -82,6 +94,21 @@ impl Config2 {
   pub fn updated_at(&self) -> u32 {
     u32::from_le_bytes(self.updated_at)
   }
+  pub fn open_escrows(&self) -> u64 {
+    u64::from_le_bytes(self.open_escrows)
+  }
+  pub fn creator(&self) -> &Address {
+    &self.creator
+  }
+  pub fn timelock_delay(&self) -> u32 {
+    u32::from_le_bytes(self.timelock_delay)
+  }
+  pub fn token_balances(&self) -> [u64; 4] {
+    self.token_balances.map(u64::from_le_bytes)
+  }
+  pub fn committed(&self) -> [u64; 4] {
+    self.committed.map(u64::from_le_bytes)
+  }
   pub fn new_u32(&self) -> u32 {
     u32::from_le_bytes(self.new_u32)
   }
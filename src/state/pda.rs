@@ -1,37 +1,49 @@
 use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
 
-use crate::{none_zero_u64, Ee, PROG_ADDR};
+use crate::{derive_pda1, none_zero_u32, none_zero_u64, u8_to_status, Ee, PROG_ADDR};
 
-//Vault to hold SOL and control Tokens, and has no struct to be declared
+//Vault to hold SOL and control Tokens. Its data is the Vault struct below
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
-pub const VAULT_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + size_of::<u64>(); //SOL amount
+/// vaults made before the Vault struct only had 16 bytes
+pub const VAULT_LEGACY_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + size_of::<u64>();
+pub const VAULT_SIZE: usize = Vault::LEN;
 
 // Anchor #[program] macro builds an 8-byte discriminator (size customizable since version 0.31) for every instruction and accounts struct. But Pinocchio uses one byte(u8) for instructions, nothing for accounts!
 //TODO: Bytemuck is a great library that makes it easy to read and write byte arrays as structs.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Config {
-  mint0: Address,         // 32 bytes
-  mint1: Address,         // 32
-  mint2: Address,         // 32
-  mint3: Address,         // 32
-  vault: Address,         // 32
-  prog_owner: Address,    // 32
-  admin: Address,         // 32
-  str_u8array: [u8; 32],  // 32
-  fee: [u8; 8],           // 8 for u64,
-  sol_balance: [u8; 8],   // 8
-  token_balance: [u8; 8], // 8
-  updated_at: [u8; 4],    // 4 for u32
-  is_authorized: bool,    // 1
-  status: u8,             // 1
-  vault_bump: u8,         // 1
-  bump: u8,               // 1
+  mint0: Address,               // 32 bytes
+  mint1: Address,               // 32
+  mint2: Address,               // 32
+  mint3: Address,               // 32
+  vault: Address,               // 32
+  prog_owner: Address,          // 32
+  admin: Address,               // 32
+  str_u8array: [u8; 32],        // 32
+  fee: [u8; 8],                 // 8 for u64,
+  sol_balance: [u8; 8],         // 8
+  token_balance: [u8; 8],       // 8
+  updated_at: [u8; 4],          // 4 for u32
+  is_authorized: bool,          // 1
+  status: u8,                   // 1
+  vault_bump: u8,               // 1
+  bump: u8,                     // 1
+  open_escrows: [u8; 8],        // 8 for u64
+  creator: Address,             // 32
+  timelock_delay: [u8; 4],      // 4 for u32, seconds a Timelock holds queued changes
+  token_balances: [[u8; 8]; 4], // 32 for u64 per config mint, in mint0..mint3 order
+  committed: [[u8; 8]; 4], // 32 for u64 per config mint, owed from the vault to open Streams and Distributors
+  yield_rate_bps: [u8; 2], // 2 for u16, annual SOL vault yield rate in basis points
+  yield_index: [u8; 8],    // 8 for u64, sum of yield_rate_bps * seconds up to yield_index_at
+  yield_index_at: [u8; 4], // 4 for u32
 } // padding: [u8; 6] if the struct size needs to be aligned to 32 bytes.
 
 impl Config {
   pub const INIT_LEN: usize = core::mem::size_of::<Self>();
+  /// Config accounts made before open_escrows end at bump; ConfigMigrate grows them
+  pub const LEGACY_LEN: usize = core::mem::offset_of!(Self, open_escrows);
   pub const SEED: &[u8] = b"config";
   //Getters or Accessors: Safe Direct value copy, no reference created
   pub fn mint0(&self) -> &Address {
@@ -85,6 +97,22 @@ impl Config {
   pub fn updated_at(&self) -> u32 {
     u32::from_le_bytes(self.updated_at)
   }
+  pub fn open_escrows(&self) -> u64 {
+    u64::from_le_bytes(self.open_escrows)
+  }
+  /// the prog_owner at InitConfig, which seeds the Config PDA and the vault. It never changes, so vault transfers sign with it after prog_owner moves to a Multisig or Timelock
+  pub fn creator(&self) -> &Address {
+    &self.creator
+  }
+  pub fn timelock_delay(&self) -> u32 {
+    u32::from_le_bytes(self.timelock_delay)
+  }
+  pub fn token_balances(&self) -> [u64; 4] {
+    self.token_balances.map(u64::from_le_bytes)
+  }
+  pub fn committed(&self) -> [u64; 4] {
+    self.committed.map(u64::from_le_bytes)
+  }
   /*pub fn expected_len(&self) -> u32 {
     u32::from_le_bytes(self.expected_len)
   }*/
@@ -105,6 +133,9 @@ impl Config {
         return Ee::VaultIsForeign.e();
       }
     }
+    if pda.data_len() < Self::INIT_LEN {
+      return Ee::ConfigDataLengh.e();
+    }
     // CHECK alignment for the most restrictive field (u64 in this case)... Alignment requirement checking can be removed ONLY IF you know all numbers are using u8 arrays
     /*if (pda.borrow_mut_data_unchecked().as_ptr() as usize) % core::mem::align_of::<Self>() != 0 { return Err();  }*/
     Ok(())
@@ -178,6 +209,15 @@ impl Config {
   pub fn set_updated_at(&mut self, amt: u32) {
     self.updated_at = amt.to_le_bytes();
   }
+  pub fn set_open_escrows(&mut self, amt: u64) {
+    self.open_escrows = amt.to_le_bytes();
+  }
+  pub fn set_creator(&mut self, addr: &Address) {
+    self.creator = addr.clone();
+  }
+  pub fn set_timelock_delay(&mut self, secs: u32) {
+    self.timelock_delay = secs.to_le_bytes();
+  }
   pub fn set_vault_bump(&mut self, amt: u8) {
     self.vault_bump = amt;
   }
@@ -190,6 +230,113 @@ impl Config {
   pub fn set_is_authorized(&mut self, boo: bool) {
     self.is_authorized = boo;
   }
+  /// Config PDA must be derived from its creator, not just owned by this program
+  pub fn check_address(&self, pda: &AccountView) -> ProgramResult {
+    let (expected_config, _bump) = derive_pda1(self.creator(), Self::SEED)?;
+    if pda.address() != &expected_config {
+      return Ee::ConfigPDA.e();
+    }
+    Ok(())
+  }
+  //----------== Token ledger
+  pub fn mint_index(&self, mint: &Address) -> Result<usize, ProgramError> {
+    self
+      .mints()
+      .iter()
+      .position(|m| *m == mint)
+      .ok_or(Ee::MintNotAccepted.into())
+  }
+  /// record net tokens received into the config vault or a user vault, per mint and in the token_balance total
+  pub fn add_token_balance(&mut self, mint: &Address, amt: u64) -> ProgramResult {
+    let index = self.mint_index(mint)?;
+    let balance = u64::from_le_bytes(self.token_balances[index])
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    let total = self
+      .token_balance()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.token_balances[index] = balance.to_le_bytes();
+    self.set_token_balance(total);
+    Ok(())
+  }
+  /// record tokens leaving a vault. Saturating, so tokens received before the per mint ledger, or of a mint no longer in the config, can still leave
+  pub fn sub_token_balance(&mut self, mint: &Address, amt: u64) {
+    if let Ok(index) = self.mint_index(mint) {
+      let balance = u64::from_le_bytes(self.token_balances[index]).saturating_sub(amt);
+      self.token_balances[index] = balance.to_le_bytes();
+    }
+    self.set_token_balance(self.token_balance().saturating_sub(amt));
+  }
+  //----------== Vault commitments
+  /// reserve amt of the vault ATA balance for a Stream or Distributor. All commitments of the mint must fit in vault_balance
+  pub fn commit(&mut self, mint: &Address, amt: u64, vault_balance: u64) -> ProgramResult {
+    let index = self.mint_index(mint)?;
+    let committed = u64::from_le_bytes(self.committed[index])
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    if committed > vault_balance {
+      return Ee::VaultCommitted.e();
+    }
+    self.committed[index] = committed.to_le_bytes();
+    Ok(())
+  }
+  /// free a commitment once paid out or cancelled. Saturating, so what was made before commitments can still pay out
+  pub fn release(&mut self, mint: &Address, amt: u64) {
+    if let Ok(index) = self.mint_index(mint) {
+      let committed = u64::from_le_bytes(self.committed[index]).saturating_sub(amt);
+      self.committed[index] = committed.to_le_bytes();
+    }
+  }
+  /// vault outflows that pay no commitment may only take what is not committed
+  pub fn check_uncommitted(&self, mint: &Address, amt: u64, vault_balance: u64) -> ProgramResult {
+    let committed = match self.mint_index(mint) {
+      Ok(index) => u64::from_le_bytes(self.committed[index]),
+      Err(_) => 0,
+    };
+    if vault_balance.saturating_sub(committed) < amt {
+      return Ee::VaultCommitted.e();
+    }
+    Ok(())
+  }
+  //----------== SOL vault yield
+  pub fn yield_rate_bps(&self) -> u16 {
+    u16::from_le_bytes(self.yield_rate_bps)
+  }
+  pub fn yield_index_at(&self) -> u32 {
+    u32::from_le_bytes(self.yield_index_at)
+  }
+  /// yield index at `now`: the stored index plus the current rate since yield_index_at
+  pub fn yield_index(&self, now: u32) -> Result<u64, ProgramError> {
+    let elapsed = now.saturating_sub(self.yield_index_at()) as u64;
+    (self.yield_rate_bps() as u64)
+      .checked_mul(elapsed)
+      .and_then(|v| v.checked_add(u64::from_le_bytes(self.yield_index)))
+      .ok_or(ProgramError::ArithmeticOverflow)
+  }
+  /// close the index at the old rate before the new rate takes over, so a rate change never reaches back
+  pub fn set_yield_rate(&mut self, rate_bps: u16, now: u32) -> ProgramResult {
+    if rate_bps > MAX_YIELD_BPS {
+      return Ee::YieldRate.e();
+    }
+    self.yield_index = self.yield_index(now)?.to_le_bytes();
+    self.yield_index_at = now.to_le_bytes();
+    self.yield_rate_bps = rate_bps.to_le_bytes();
+    Ok(())
+  }
+  //----------== Open escrow counter
+  pub fn add_open_escrow(&mut self) -> ProgramResult {
+    let count = self
+      .open_escrows()
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_open_escrows(count);
+    Ok(())
+  }
+  // saturating so an escrow made before a Config reset can still be closed
+  pub fn sub_open_escrow(&mut self) {
+    self.set_open_escrows(self.open_escrows().saturating_sub(1));
+  }
 }
 
 //#[repr(C)] keeps the struct layout the same across different architectures
@@ -221,20 +368,43 @@ impl From<u8> for Status {
 pub struct Escrow {
   maker: Address, //32; PDA needs at least 1 Address to keep PDA addresses from being exhausted by all users using u64. This also gives each user his own Escrow id.
   //taker: Address,   //32 hidden from maker
-  mint_x: Address,   //32
-  mint_y: Address,   //32
+  mint_x: Address,        //32
+  mint_y: Address,        //32
   amount_x: [u8; 8], //8 the offered amount from maker. This field gives taker easier way to view
   amount_y: [u8; 8], //8 the wanted amount to maker. The token_y price in mint_x = this Escrow PDA ATA_X amount / amount_y
   id: [u8; 8],       //8
-  decimal_x: u8,     //1
-  decimal_y: u8,     //1
-  bump: u8,          //1
+  seq: [u8; 4],      //4 bumped by each amendment so takers can tell the offer changed
+  end_amount_y: [u8; 8], //8 DUTCH_Y: amount_y decays linearly to this
+  start_time: [u8; 8], //8 DUTCH_Y: i64 unix time the decay starts
+  end_time: [u8; 8], //8 DUTCH_Y: i64 unix time the decay ends
+  feed_id: [u8; 32], //32 TRIGGER: the oracle price feed
+  trigger_price: [u8; 8], //8 TRIGGER: in whole units as read_oracle_pda returns
+  received_y: [u8; 8], //8 what Escrow ATA Y received at take, which can be less than the asked Y after transfer fees
+  config: Address,     //32 the Config whose open_escrows counts this offer
+  decimal_x: u8,       //1
+  decimal_y: u8,       //1
+  mode: u8,            //1 bit flags: NFT_X, COLLECTION_Y, DUTCH_Y, TRIGGER
+  oracle_vendor: u8,   //1 TRIGGER: as in read_oracle_pda
+  trigger_above: bool, //1 TRIGGER: takeable when the price is above trigger_price, or else below it
+  bump: u8,            //1
 }
 impl Escrow {
   pub const LEN: usize = core::mem::size_of::<Escrow>();
   //pub const LEN: usize = 32 + 32 + 32 + 8 +8+ 1;
+  /// Escrow made before seq: maker, mint_x, mint_y, amount_x, amount_y, id, decimal_x, decimal_y and bump. EscrowMigrate grows it to LEN
+  pub const LEGACY_LEN: usize = 32 * 3 + 8 * 3 + 3;
 
   pub const SEED: &[u8] = b"escrow";
+  /// A SOL leg has this as its mint. SOL is held as lamports in the Escrow PDA
+  pub const SOL_MINT: [u8; 32] = [0u8; 32];
+  /// mint_x is an NFT: 0 decimals, supply 1, amount_x 1
+  pub const NFT_X: u8 = 1;
+  /// mint_y is a Token2022 collection(group) mint; the taker delivers any NFT member of it
+  pub const COLLECTION_Y: u8 = 2;
+  /// Dutch auction: the asked Y decays linearly from amount_y at start_time to end_amount_y at end_time
+  pub const DUTCH_Y: u8 = 4;
+  /// Takeable only while the oracle price is past trigger_price, like a limit order
+  pub const TRIGGER: u8 = 8;
 
   pub fn maker(&self) -> &Address {
     &self.maker
@@ -248,6 +418,9 @@ impl Escrow {
   pub fn id(&self) -> u64 {
     u64::from_le_bytes(self.id)
   }
+  pub fn seq(&self) -> u32 {
+    u32::from_le_bytes(self.seq)
+  }
   pub fn amount_x(&self) -> u64 {
     u64::from_le_bytes(self.amount_x)
   }
@@ -260,9 +433,72 @@ impl Escrow {
   pub fn decimal_y(&self) -> u8 {
     self.decimal_y
   }
+  pub fn mode(&self) -> u8 {
+    self.mode
+  }
+  pub fn end_amount_y(&self) -> u64 {
+    u64::from_le_bytes(self.end_amount_y)
+  }
+  pub fn start_time(&self) -> i64 {
+    i64::from_le_bytes(self.start_time)
+  }
+  pub fn end_time(&self) -> i64 {
+    i64::from_le_bytes(self.end_time)
+  }
+  pub fn feed_id(&self) -> [u8; 32] {
+    self.feed_id
+  }
+  pub fn trigger_price(&self) -> u64 {
+    u64::from_le_bytes(self.trigger_price)
+  }
+  pub fn oracle_vendor(&self) -> u8 {
+    self.oracle_vendor
+  }
+  pub fn received_y(&self) -> u64 {
+    u64::from_le_bytes(self.received_y)
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  /// What the maker withdraws for Y. An Escrow taken before received_y existed kept it in amount_y
+  pub fn taken_y(&self) -> u64 {
+    match self.received_y() {
+      0 => self.amount_y(),
+      received_y => received_y,
+    }
+  }
+  pub fn trigger_above(&self) -> bool {
+    self.trigger_above
+  }
+  /// The asked Y at time. Fixed at amount_y unless DUTCH_Y
+  pub fn price_y(&self, time: i64) -> Result<u64, ProgramError> {
+    let start_y = self.amount_y();
+    if self.mode & Self::DUTCH_Y == 0 || time <= self.start_time() {
+      return Ok(start_y);
+    }
+    let end_y = self.end_amount_y();
+    if time >= self.end_time() {
+      return Ok(end_y);
+    }
+    let elapsed = (time - self.start_time()) as u128;
+    let duration = (self.end_time() - self.start_time()) as u128;
+    let drop = start_y.saturating_sub(end_y) as u128 * elapsed / duration;
+    u64::try_from(start_y as u128 - drop).map_err(|_| ProgramError::ArithmeticOverflow)
+  }
   pub fn bump(&self) -> u8 {
     self.bump
   }
+  /// For an offer with one SOL leg: true when X is SOL. mint is the token leg
+  pub fn sol_x(&self, mint: &Address) -> Result<bool, ProgramError> {
+    let sol = Address::new_from_array(Self::SOL_MINT);
+    if self.mint_x == sol && &self.mint_y == mint {
+      return Ok(true);
+    }
+    if self.mint_y == sol && &self.mint_x == mint {
+      return Ok(false);
+    }
+    Err(Ee::EscrowSolLeg.into())
+  }
   pub fn set_maker(&mut self, addr: &Address) {
     self.maker = addr.clone();
   }
@@ -272,10 +508,16 @@ impl Escrow {
   pub fn set_mint_y(&mut self, addr: &Address) {
     self.mint_y = addr.clone();
   }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
   pub fn set_id(&mut self, amt: u64) -> ProgramResult {
     self.id = amt.to_le_bytes();
     Ok(())
   }
+  pub fn set_seq(&mut self, seq: u32) {
+    self.seq = seq.to_le_bytes();
+  }
   pub fn set_amount_x(&mut self, amt: u64) -> ProgramResult {
     none_zero_u64(amt)?;
     self.amount_x = amt.to_le_bytes();
@@ -286,12 +528,61 @@ impl Escrow {
     self.amount_y = amt.to_le_bytes();
     Ok(())
   }
+  pub fn set_received_y(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.received_y = amt.to_le_bytes();
+    Ok(())
+  }
   pub fn set_decimal_x(&mut self, amt: u8) {
     self.decimal_x = amt;
   }
   pub fn set_decimal_y(&mut self, amt: u8) {
     self.decimal_y = amt;
   }
+  pub fn set_mode(&mut self, mode: u8) -> ProgramResult {
+    if mode & !(Self::NFT_X | Self::COLLECTION_Y | Self::DUTCH_Y | Self::TRIGGER) != 0 {
+      return Ee::EscrowNft.e();
+    }
+    self.mode = mode;
+    Ok(())
+  }
+  /// Dutch auction from amount_y down to end_amount_y over start_time..end_time
+  pub fn set_dutch(&mut self, end_amount_y: u64, start_time: i64, end_time: i64) -> ProgramResult {
+    none_zero_u64(end_amount_y)?;
+    if end_amount_y >= self.amount_y() || end_time <= start_time {
+      return Ee::EscrowDutch.e();
+    }
+    self.end_amount_y = end_amount_y.to_le_bytes();
+    self.start_time = start_time.to_le_bytes();
+    self.end_time = end_time.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_trigger(
+    &mut self,
+    oracle_vendor: u8,
+    trigger_above: bool,
+    trigger_price: u64,
+    feed_id: [u8; 32],
+  ) -> ProgramResult {
+    none_zero_u64(trigger_price)?;
+    self.oracle_vendor = oracle_vendor;
+    self.trigger_above = trigger_above;
+    self.trigger_price = trigger_price.to_le_bytes();
+    self.feed_id = feed_id;
+    Ok(())
+  }
+  /// For TRIGGER: the oracle price must be past trigger_price
+  pub fn check_trigger(&self, price: u64) -> ProgramResult {
+    let crossed = if self.trigger_above {
+      price > self.trigger_price()
+    } else {
+      price < self.trigger_price()
+    };
+    if !crossed {
+      return Ee::EscrowTrigger.e();
+    }
+    Ok(())
+  }
   pub fn set_bump(&mut self, amt: u8) {
     self.bump = amt;
   }
@@ -322,51 +613,1883 @@ impl Escrow {
   }
 }
 
+//------------== Basket escrow
+pub const MAX_BASKET_LEGS: usize = 4;
+
+/// Multi-asset escrow offer. Variable length: the header is followed by leg_count_x X legs, then leg_count_y Y legs. Each leg has its own Basket ATA
 #[derive(Clone, Debug)]
 #[repr(C)]
-pub struct User {
-  mint: Address,
-  token_balc: [u8; 8],
-  bump: u8, //1
+pub struct Basket {
+  maker: Address,  //32
+  config: Address, //32 the Config whose open_escrows counts this offer
+  id: [u8; 8],     //8 from the maker's MakerIndex, shared with Escrow ids
+  leg_count_x: u8, //1 offered mints
+  leg_count_y: u8, //1 wanted mints
+  taken: bool,     //1
+  bump: u8,        //1
 }
-impl User {
-  pub const LEN: usize = core::mem::size_of::<User>();
-  pub const SEED: &[u8] = b"user";
+/// One mint of a Basket. amount is what the Basket ATA holds for X legs, and what the maker wants for Y legs
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct BasketLeg {
+  mint: Address,   //32
+  amount: [u8; 8], //8
+  decimals: u8,    //1
+}
+impl BasketLeg {
+  pub const LEN: usize = core::mem::size_of::<BasketLeg>();
 
   pub fn mint(&self) -> &Address {
     &self.mint
   }
-  pub fn token_balc(&self) -> u64 {
-    u64::from_le_bytes(self.token_balc)
+  pub fn amount(&self) -> u64 {
+    u64::from_le_bytes(self.amount)
   }
-  pub fn bump(&self) -> u8 {
-    self.bump
+  pub fn decimals(&self) -> u8 {
+    self.decimals
   }
   pub fn set_mint(&mut self, addr: &Address) {
     self.mint = addr.clone();
   }
-  pub fn set_token_balc(&mut self, amt: u64) -> ProgramResult {
+  pub fn set_amount(&mut self, amt: u64) -> ProgramResult {
     none_zero_u64(amt)?;
-    self.token_balc = amt.to_le_bytes();
+    self.amount = amt.to_le_bytes();
     Ok(())
   }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+}
+impl Basket {
+  pub const LEN: usize = core::mem::size_of::<Basket>();
+  pub const SEED: &[u8] = b"basket";
+
+  /// Account size for the given leg counts. 1 to MAX_BASKET_LEGS legs each side
+  pub fn space(leg_count_x: u8, leg_count_y: u8) -> Result<usize, ProgramError> {
+    let (x, y) = (leg_count_x as usize, leg_count_y as usize);
+    if x == 0 || y == 0 || x > MAX_BASKET_LEGS || y > MAX_BASKET_LEGS {
+      return Err(Ee::BasketLegs.into());
+    }
+    Ok(Self::LEN + (x + y) * BasketLeg::LEN)
+  }
+  pub fn maker(&self) -> &Address {
+    &self.maker
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn leg_count_x(&self) -> u8 {
+    self.leg_count_x
+  }
+  pub fn leg_count_y(&self) -> u8 {
+    self.leg_count_y
+  }
+  pub fn taken(&self) -> bool {
+    self.taken
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_taken(&mut self, boo: bool) {
+    self.taken = boo;
+  }
+  /// Write the header of a newly made Basket PDA of Basket::space(leg_count_x, leg_count_y) bytes
+  #[allow(clippy::mut_from_ref)]
+  pub fn init<'a>(
+    pda: &'a AccountView,
+    maker: &Address,
+    config: &Address,
+    id: u64,
+    leg_count_x: u8,
+    leg_count_y: u8,
+    bump: u8,
+  ) -> Result<&'a mut Self, ProgramError> {
+    if pda.data_len() != Self::space(leg_count_x, leg_count_y)? {
+      return Err(Ee::BasketPDA.into());
+    }
+    let basket = Self::cast(pda)?;
+    basket.maker = maker.clone();
+    basket.config = config.clone();
+    basket.id = id.to_le_bytes();
+    basket.leg_count_x = leg_count_x;
+    basket.leg_count_y = leg_count_y;
+    basket.taken = false;
+    basket.bump = bump;
+    Ok(basket)
+  }
+  #[allow(clippy::mut_from_ref)]
+  fn cast(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    if pda.data_len() < Self::LEN {
+      return Err(Ee::BasketPDA.into());
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Err(Ee::BasketPDA.into());
+      }
+      Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self))
+    }
+  }
+  //For Basket PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    let basket = Self::cast(pda)?;
+    if pda.data_len() != Self::space(basket.leg_count_x, basket.leg_count_y)? {
+      return Err(Ee::BasketPDA.into());
+    }
+    Ok(basket)
+  }
+  /// X legs then Y legs, after the header
+  #[allow(clippy::mut_from_ref)]
+  pub fn legs(pda: &AccountView) -> Result<&mut [BasketLeg], ProgramError> {
+    let basket = Self::from_account_view(pda)?;
+    let count = basket.leg_count_x as usize + basket.leg_count_y as usize;
+    unsafe {
+      let ptr = pda.try_borrow_mut()?.as_ptr().add(Self::LEN) as *mut BasketLeg;
+      Ok(core::slice::from_raw_parts_mut(ptr, count))
+    }
+  }
+}
+
+//------------== Maker escrow index
+/// Per-maker escrow id allocator and open offer count. Escrow ids of a maker run from 1 to next_id - 1
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct MakerIndex {
+  maker: Address,      //32
+  next_id: [u8; 8],    //8 the next free escrow id
+  open_count: [u8; 8], //8 offers not yet canceled or withdrawn
+  bump: u8,            //1
+}
+impl MakerIndex {
+  pub const LEN: usize = core::mem::size_of::<MakerIndex>();
+  pub const SEED: &[u8] = b"maker_index";
+
+  pub fn maker(&self) -> &Address {
+    &self.maker
+  }
+  pub fn next_id(&self) -> u64 {
+    u64::from_le_bytes(self.next_id)
+  }
+  pub fn open_count(&self) -> u64 {
+    u64::from_le_bytes(self.open_count)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_maker(&mut self, addr: &Address) {
+    self.maker = addr.clone();
+  }
+  pub fn set_next_id(&mut self, id: u64) {
+    self.next_id = id.to_le_bytes();
+  }
+  pub fn set_open_count(&mut self, amt: u64) {
+    self.open_count = amt.to_le_bytes();
+  }
   pub fn set_bump(&mut self, amt: u8) {
-    self.bump = amt.clone();
+    self.bump = amt;
   }
-  //For Escrow PDA
+  /// id 0 takes next_id. A chosen id below next_id may have been used before, so it is refused
+  pub fn allocate(&mut self, id: u64) -> Result<u64, ProgramError> {
+    let id = if id == 0 { self.next_id() } else { id };
+    if id < self.next_id() {
+      return Err(Ee::EscrowIdUsed.into());
+    }
+    let next_id = id.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    let open_count = self
+      .open_count()
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_next_id(next_id);
+    self.set_open_count(open_count);
+    Ok(id)
+  }
+  pub fn close_offer(&mut self) {
+    self.set_open_count(self.open_count().saturating_sub(1));
+  }
+  /// Count an offer whose id was chosen before MakerIndex existed, and keep allocate from handing that id out again
+  pub fn adopt(&mut self, id: u64) -> ProgramResult {
+    if id >= self.next_id() {
+      self.set_next_id(id.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?);
+    }
+    let open_count = self
+      .open_count()
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_open_count(open_count);
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MakerIndexPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MakerIndexPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For MakerIndex PDA
+  #[allow(clippy::mut_from_ref)]
   pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Auction
+/// English auction of amount_x X for Y, from reserve_y up until end_time. Every bid is held in its Bid PDA; outbid ones stay there until topped up into a new bid or taken back with AuctionBidWithdraw
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Auction {
+  maker: Address,       //32
+  config: Address,      //32 the Config whose open_escrows counts this auction
+  mint_x: Address,      //32
+  mint_y: Address,      //32
+  high_bidder: Address, //32 zero before the first bid
+  amount_x: [u8; 8],    //8 held in the Auction ATA X
+  reserve_y: [u8; 8],   //8 the lowest acceptable bid
+  high_bid: [u8; 8],    //8 the highest bid so far
+  end_time: [u8; 8],    //8 i64 unix time bidding closes
+  id: [u8; 8],          //8 from the maker's MakerIndex, shared with Escrow ids
+  decimal_x: u8,        //1
+  decimal_y: u8,        //1
+  bump: u8,             //1
+}
+impl Auction {
+  pub const LEN: usize = core::mem::size_of::<Auction>();
+  pub const SEED: &[u8] = b"auction";
+
+  pub fn maker(&self) -> &Address {
+    &self.maker
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn mint_x(&self) -> &Address {
+    &self.mint_x
+  }
+  pub fn mint_y(&self) -> &Address {
+    &self.mint_y
+  }
+  pub fn high_bidder(&self) -> &Address {
+    &self.high_bidder
+  }
+  /// true once a bid is in
+  pub fn has_bid(&self) -> bool {
+    self.high_bidder != Address::new_from_array([0u8; 32])
+  }
+  pub fn amount_x(&self) -> u64 {
+    u64::from_le_bytes(self.amount_x)
+  }
+  pub fn reserve_y(&self) -> u64 {
+    u64::from_le_bytes(self.reserve_y)
+  }
+  pub fn high_bid(&self) -> u64 {
+    u64::from_le_bytes(self.high_bid)
+  }
+  pub fn end_time(&self) -> i64 {
+    i64::from_le_bytes(self.end_time)
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn decimal_x(&self) -> u8 {
+    self.decimal_x
+  }
+  pub fn decimal_y(&self) -> u8 {
+    self.decimal_y
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_maker(&mut self, addr: &Address) {
+    self.maker = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_mint_x(&mut self, addr: &Address) {
+    self.mint_x = addr.clone();
+  }
+  pub fn set_mint_y(&mut self, addr: &Address) {
+    self.mint_y = addr.clone();
+  }
+  pub fn set_amount_x(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.amount_x = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_reserve_y(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.reserve_y = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_end_time(&mut self, time: i64) {
+    self.end_time = time.to_le_bytes();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_decimal_x(&mut self, amt: u8) {
+    self.decimal_x = amt;
+  }
+  pub fn set_decimal_y(&mut self, amt: u8) {
+    self.decimal_y = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// Record a new highest bid. It must meet the reserve and beat the current highest bid
+  pub fn outbid(&mut self, bidder: &Address, amount: u64) -> ProgramResult {
+    if amount < self.reserve_y() || (self.has_bid() && amount <= self.high_bid()) {
+      return Ee::AuctionBid.e();
+    }
+    self.high_bidder = bidder.clone();
+    self.high_bid = amount.to_le_bytes();
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
     if pda.data_len() != Self::LEN {
-      return Err(Ee::EscrowDataLengh.into());
+      return Ee::AuctionPDA.e();
     }
     unsafe {
       if pda.owner().ne(&PROG_ADDR) {
-        return Err(Ee::ForeignPDA.into());
+        return Ee::AuctionPDA.e();
       }
     }
-    // Check alignment
-    if (pda.try_borrow()?.as_ptr() as usize) % core::mem::align_of::<Self>() != 0 {
-      return Err(Ee::UserDataLengh.into());
+    Ok(())
+  }
+  //For Auction PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+/// One bidder's Y locked for an Auction, held in the Bid ATA
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Bid {
+  auction: Address, //32
+  bidder: Address,  //32
+  amount: [u8; 8],  //8 what the Bid ATA received
+  bump: u8,         //1
+}
+impl Bid {
+  pub const LEN: usize = core::mem::size_of::<Bid>();
+  pub const SEED: &[u8] = b"bid";
+
+  pub fn auction(&self) -> &Address {
+    &self.auction
+  }
+  pub fn bidder(&self) -> &Address {
+    &self.bidder
+  }
+  pub fn amount(&self) -> u64 {
+    u64::from_le_bytes(self.amount)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_auction(&mut self, addr: &Address) {
+    self.auction = addr.clone();
+  }
+  pub fn set_bidder(&mut self, addr: &Address) {
+    self.bidder = addr.clone();
+  }
+  pub fn set_amount(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.amount = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::BidPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::BidPDA.e();
+      }
     }
+    Ok(())
+  }
+  //For Bid PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Milestone escrow
+pub const MAX_MILESTONES: usize = 8;
+
+/// Service payment escrow: the payer locks the sum of up to MAX_MILESTONES amounts, each released to the payee by the payer or the arbiter, or refunded to the payer. Unsettled funds revert to the payer after deadline
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Milestone {
+  payer: Address,                     //32
+  config: Address,                    //32 the Config whose open_escrows counts this escrow
+  payee: Address,                     //32
+  arbiter: Address,                   //32 settles disputes
+  mint: Address,                      //32
+  amounts: [[u8; 8]; MAX_MILESTONES], //64 u64 per milestone
+  deadline: [u8; 8],                  //8 i64 unix time
+  id: [u8; 8],                        //8 from the payer's MakerIndex, shared with Escrow ids
+  count: u8,                          //1 milestones in use
+  released: u8,                       //1 bit i: milestone i paid to the payee
+  refunded: u8,                       //1 bit i: milestone i paid back to the payer
+  disputed: u8,                       //1 bit i: milestone i waits for the arbiter
+  decimals: u8,                       //1
+  bump: u8,                           //1
+}
+impl Milestone {
+  pub const LEN: usize = core::mem::size_of::<Milestone>();
+  pub const SEED: &[u8] = b"milestone";
+
+  pub fn payer(&self) -> &Address {
+    &self.payer
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn payee(&self) -> &Address {
+    &self.payee
+  }
+  pub fn arbiter(&self) -> &Address {
+    &self.arbiter
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn amount(&self, index: u8) -> Result<u64, ProgramError> {
+    self.check_index(index)?;
+    Ok(u64::from_le_bytes(self.amounts[index as usize]))
+  }
+  pub fn deadline(&self) -> i64 {
+    i64::from_le_bytes(self.deadline)
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn count(&self) -> u8 {
+    self.count
+  }
+  pub fn released(&self) -> u8 {
+    self.released
+  }
+  pub fn refunded(&self) -> u8 {
+    self.refunded
+  }
+  pub fn disputed(&self) -> u8 {
+    self.disputed
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_payer(&mut self, addr: &Address) {
+    self.payer = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_payee(&mut self, addr: &Address) {
+    self.payee = addr.clone();
+  }
+  pub fn set_arbiter(&mut self, addr: &Address) {
+    self.arbiter = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  /// 1 to MAX_MILESTONES non-zero amounts
+  pub fn set_amounts(&mut self, amounts: &[u64]) -> ProgramResult {
+    if amounts.is_empty() || amounts.len() > MAX_MILESTONES {
+      return Ee::MilestoneIndex.e();
+    }
+    for (i, amt) in amounts.iter().enumerate() {
+      none_zero_u64(*amt)?;
+      self.amounts[i] = amt.to_le_bytes();
+    }
+    self.count = amounts.len() as u8;
+    Ok(())
+  }
+  pub fn set_deadline(&mut self, time: i64) {
+    self.deadline = time.to_le_bytes();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  fn check_index(&self, index: u8) -> ProgramResult {
+    if index >= self.count {
+      return Ee::MilestoneIndex.e();
+    }
+    Ok(())
+  }
+  /// Milestones released or refunded, as bits
+  pub fn settled(&self) -> u8 {
+    self.released | self.refunded
+  }
+  /// Milestones not yet released or refunded
+  pub fn open_count(&self) -> u32 {
+    self.count as u32 - self.settled().count_ones()
+  }
+  pub fn is_disputed(&self, index: u8) -> bool {
+    self.disputed & (1 << index) != 0
+  }
+  /// Fails unless milestone index exists and is not settled
+  pub fn check_open(&self, index: u8) -> ProgramResult {
+    self.check_index(index)?;
+    if self.settled() & (1 << index) != 0 {
+      return Ee::MilestoneIndex.e();
+    }
+    Ok(())
+  }
+  pub fn dispute(&mut self, index: u8) -> ProgramResult {
+    self.check_open(index)?;
+    self.disputed |= 1 << index;
+    Ok(())
+  }
+  /// Mark milestone index as released to the payee, or else refunded to the payer
+  pub fn settle(&mut self, index: u8, release: bool) -> ProgramResult {
+    self.check_open(index)?;
+    if release {
+      self.released |= 1 << index;
+    } else {
+      self.refunded |= 1 << index;
+    }
+    self.disputed &= !(1 << index);
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MilestonePDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MilestonePDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Milestone PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct User {
+  mint: Address,
+  token_balc: [u8; 8],
+  bump: u8, //1
+}
+impl User {
+  pub const LEN: usize = core::mem::size_of::<User>();
+  pub const SEED: &[u8] = b"user";
+
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn token_balc(&self) -> u64 {
+    u64::from_le_bytes(self.token_balc)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_token_balc(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.token_balc = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt.clone();
+  }
+  //For Escrow PDA
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    if pda.data_len() != Self::LEN {
+      return Err(Ee::EscrowDataLengh.into());
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Err(Ee::ForeignPDA.into());
+      }
+    }
+    // Check alignment
+    if (pda.try_borrow()?.as_ptr() as usize) % core::mem::align_of::<Self>() != 0 {
+      return Err(Ee::UserDataLengh.into());
+    }
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== SOL Vault yield
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+/// max annual yield rate: 100%
+pub const MAX_YIELD_BPS: u16 = 10_000;
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Vault {
+  principal: [u8; 8],   //8 SOL deposited minus SOL withdrawn
+  accrued: [u8; 8],     //8 yield accrued but not yet claimed
+  updated_at: [u8; 4],  //4 for u32, last accrual time
+  yield_index: [u8; 8], //8 Config yield index at the last accrual
+  pool: Address,        //32 YieldPool this vault accrues from. Zeros until first bound
+  bump: u8,             //1
+}
+impl Vault {
+  pub const LEN: usize = core::mem::size_of::<Vault>();
+
+  pub fn principal(&self) -> u64 {
+    u64::from_le_bytes(self.principal)
+  }
+  pub fn accrued(&self) -> u64 {
+    u64::from_le_bytes(self.accrued)
+  }
+  pub fn updated_at(&self) -> u32 {
+    u32::from_le_bytes(self.updated_at)
+  }
+  pub fn yield_index(&self) -> u64 {
+    u64::from_le_bytes(self.yield_index)
+  }
+  pub fn pool(&self) -> &Address {
+    &self.pool
+  }
+  pub fn has_pool(&self) -> bool {
+    self.pool.as_array() != &[0u8; 32]
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_principal(&mut self, amt: u64) {
+    self.principal = amt.to_le_bytes();
+  }
+  pub fn set_accrued(&mut self, amt: u64) {
+    self.accrued = amt.to_le_bytes();
+  }
+  pub fn set_updated_at(&mut self, time: u32) {
+    self.updated_at = time.to_le_bytes();
+  }
+  pub fn set_yield_index(&mut self, index: u64) {
+    self.yield_index = index.to_le_bytes();
+  }
+  pub fn set_pool(&mut self, addr: &Address) {
+    self.pool = addr.clone();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// add yield on principal since the last accrual: principal * (index - yield_index) / (10000 * SECONDS_PER_YEAR), where index is the Config yield index at `now`
+  pub fn accrue(&mut self, index: u64, now: u32) -> ProgramResult {
+    let index_delta = index.saturating_sub(self.yield_index()) as u128;
+    let earned = (self.principal() as u128)
+      .checked_mul(index_delta)
+      .ok_or(ProgramError::ArithmeticOverflow)?
+      / (10_000u128 * SECONDS_PER_YEAR as u128);
+    let earned = u64::try_from(earned).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let accrued = self
+      .accrued()
+      .checked_add(earned)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_accrued(accrued);
+    self.set_yield_index(index);
+    self.set_updated_at(now);
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::VaultDataLengh.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::VaultIsForeign.e();
+      }
+    }
+    Ok(())
+  }
+  //For Vault PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+/// Admin-funded reward pool paying SOL vault yield at the Config yield rate. Rewards are the lamports above rent
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct YieldPool {
+  config: Address,     //32
+  total_paid: [u8; 8], //8
+  bump: u8,            //1
+}
+impl YieldPool {
+  pub const LEN: usize = core::mem::size_of::<YieldPool>();
+  pub const SEED: &[u8] = b"yield";
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn total_paid(&self) -> u64 {
+    u64::from_le_bytes(self.total_paid)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_total_paid(&mut self, amt: u64) {
+    self.total_paid = amt.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::YieldPool.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::YieldPool.e();
+      }
+    }
+    Ok(())
+  }
+  //For YieldPool PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Vault locks
+/// Schedule of SOL or tokens locked in a user's vault. The funds sit in the vault, and WithdrawSol, TokLgcWithdraw and the delegate withdrawals must leave what is still locked.
+/// Vesting is linear from start_at to end_at with nothing vested before cliff_at. cliff_at == end_at makes a plain unlock time
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct VaultLock {
+  user: Address,     //32 beneficiary
+  mint: Address,     //32 zeros for SOL
+  funder: Address,   //32 gets the rent back on close
+  total: [u8; 8],    //8
+  start_at: [u8; 4], //4 for u32
+  cliff_at: [u8; 4], //4 for u32
+  end_at: [u8; 4],   //4 for u32
+  bump: u8,          //1
+}
+impl VaultLock {
+  pub const LEN: usize = core::mem::size_of::<VaultLock>();
+  pub const SEED: &[u8] = b"lock";
+  pub const SOL_MINT: [u8; 32] = [0u8; 32];
+
+  pub fn user(&self) -> &Address {
+    &self.user
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn funder(&self) -> &Address {
+    &self.funder
+  }
+  pub fn total(&self) -> u64 {
+    u64::from_le_bytes(self.total)
+  }
+  pub fn start_at(&self) -> u32 {
+    u32::from_le_bytes(self.start_at)
+  }
+  pub fn cliff_at(&self) -> u32 {
+    u32::from_le_bytes(self.cliff_at)
+  }
+  pub fn end_at(&self) -> u32 {
+    u32::from_le_bytes(self.end_at)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_user(&mut self, addr: &Address) {
+    self.user = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_funder(&mut self, addr: &Address) {
+    self.funder = addr.clone();
+  }
+  pub fn set_total(&mut self, amt: u64) {
+    self.total = amt.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// start_at <= cliff_at <= end_at, and end_at must be in the future
+  pub fn set_schedule(&mut self, start_at: u32, cliff_at: u32, end_at: u32) -> ProgramResult {
+    if cliff_at < start_at || end_at < cliff_at || end_at <= start_at {
+      return Ee::LockSchedule.e();
+    }
+    self.start_at = start_at.to_le_bytes();
+    self.cliff_at = cliff_at.to_le_bytes();
+    self.end_at = end_at.to_le_bytes();
+    Ok(())
+  }
+  /// amount vested at `now`
+  pub fn vested(&self, now: u32) -> Result<u64, ProgramError> {
+    if now < self.cliff_at() {
+      return Ok(0);
+    }
+    if now >= self.end_at() {
+      return Ok(self.total());
+    }
+    let elapsed = now.saturating_sub(self.start_at()) as u128;
+    let duration = self.end_at().saturating_sub(self.start_at()) as u128;
+    let vested = (self.total() as u128)
+      .checked_mul(elapsed)
+      .ok_or(ProgramError::ArithmeticOverflow)?
+      / duration;
+    u64::try_from(vested).map_err(|_| ProgramError::ArithmeticOverflow)
+  }
+  /// not vested yet, so it must stay in the vault
+  pub fn locked(&self, now: u32) -> Result<u64, ProgramError> {
+    Ok(self.total().saturating_sub(self.vested(now)?))
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::LockPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::LockPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For VaultLock PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Vault delegates
+/// Lets a delegate withdraw from a user's vault up to cap per period, for one mint or SOL
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct VaultDelegate {
+  user: Address,         //32 vault owner
+  delegate: Address,     //32
+  mint: Address,         //32 zeros for SOL
+  cap: [u8; 8],          //8 max amount per period
+  spent: [u8; 8],        //8 spent in the current period
+  period: [u8; 4],       //4 for u32, period length in seconds
+  period_start: [u8; 4], //4 for u32
+  bump: u8,              //1
+}
+impl VaultDelegate {
+  pub const LEN: usize = core::mem::size_of::<VaultDelegate>();
+  pub const SEED: &[u8] = b"delegate";
+
+  pub fn user(&self) -> &Address {
+    &self.user
+  }
+  pub fn delegate(&self) -> &Address {
+    &self.delegate
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn cap(&self) -> u64 {
+    u64::from_le_bytes(self.cap)
+  }
+  pub fn spent(&self) -> u64 {
+    u64::from_le_bytes(self.spent)
+  }
+  pub fn period(&self) -> u32 {
+    u32::from_le_bytes(self.period)
+  }
+  pub fn period_start(&self) -> u32 {
+    u32::from_le_bytes(self.period_start)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_user(&mut self, addr: &Address) {
+    self.user = addr.clone();
+  }
+  pub fn set_delegate(&mut self, addr: &Address) {
+    self.delegate = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_cap(&mut self, amt: u64) {
+    self.cap = amt.to_le_bytes();
+  }
+  pub fn set_spent(&mut self, amt: u64) {
+    self.spent = amt.to_le_bytes();
+  }
+  pub fn set_period(&mut self, secs: u32) {
+    self.period = secs.to_le_bytes();
+  }
+  pub fn set_period_start(&mut self, time: u32) {
+    self.period_start = time.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// count amount against the cap, starting a new period when the current one has ended
+  pub fn spend(&mut self, amount: u64, now: u32) -> ProgramResult {
+    let elapsed = now.saturating_sub(self.period_start());
+    if elapsed >= self.period() {
+      // keep periods aligned to the first one
+      let start = self.period_start() + elapsed - elapsed % self.period();
+      self.set_period_start(start);
+      self.set_spent(0);
+    }
+    let spent = self
+      .spent()
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    if spent > self.cap() {
+      return Ee::DelegateCap.e();
+    }
+    self.set_spent(spent);
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::DelegatePDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::DelegatePDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For VaultDelegate PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Subscriptions
+/// Recurring payment of amount per period from the user's ATA into the vault, pulled by anyone through a token delegate approval of this PDA. One per user and mint
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Subscription {
+  user: Address,         //32
+  config: Address,       //32 the Config whose vault is paid
+  mint: Address,         //32
+  amount: [u8; 8],       //8 pulled each period
+  paid_through: [u8; 8], //8 i64 unix time the paid periods cover; the next payment is due from then
+  period: [u8; 4],       //4 for u32, period length in seconds
+  max_periods: [u8; 4],  //4 for u32
+  paid_periods: [u8; 4], //4 for u32
+  decimals: u8,          //1
+  bump: u8,              //1
+}
+impl Subscription {
+  pub const LEN: usize = core::mem::size_of::<Subscription>();
+  pub const SEED: &[u8] = b"subscription";
+
+  pub fn user(&self) -> &Address {
+    &self.user
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn amount(&self) -> u64 {
+    u64::from_le_bytes(self.amount)
+  }
+  pub fn paid_through(&self) -> i64 {
+    i64::from_le_bytes(self.paid_through)
+  }
+  pub fn period(&self) -> u32 {
+    u32::from_le_bytes(self.period)
+  }
+  pub fn max_periods(&self) -> u32 {
+    u32::from_le_bytes(self.max_periods)
+  }
+  pub fn paid_periods(&self) -> u32 {
+    u32::from_le_bytes(self.paid_periods)
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_user(&mut self, addr: &Address) {
+    self.user = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_amount(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.amount = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_paid_through(&mut self, time: i64) {
+    self.paid_through = time.to_le_bytes();
+  }
+  pub fn set_period(&mut self, secs: u32) -> ProgramResult {
+    none_zero_u32(secs)?;
+    self.period = secs.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_max_periods(&mut self, count: u32) -> ProgramResult {
+    none_zero_u32(count)?;
+    self.max_periods = count.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_paid_periods(&mut self, count: u32) {
+    self.paid_periods = count.to_le_bytes();
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// Count one paid period, which must be due at time. A missed period stays due, so repeated pulls catch up
+  pub fn pay_period(&mut self, time: i64) -> ProgramResult {
+    if self.paid_periods() >= self.max_periods() {
+      return Ee::SubscriptionEnded.e();
+    }
+    if time < self.paid_through() {
+      return Ee::SubscriptionNotDue.e();
+    }
+    let paid_through = self
+      .paid_through()
+      .checked_add(self.period() as i64)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_paid_through(paid_through);
+    self.set_paid_periods(self.paid_periods() + 1);
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::SubscriptionPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::SubscriptionPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Subscription PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Payment streams
+/// Tokens vesting from the vault to a recipient at rate per second between start_at and end_at. They stay in the vault ATA until the recipient withdraws them. One per Config, recipient and mint
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Stream {
+  funder: Address,    //32 config admin or prog_owner; gets the rent back on cancel
+  config: Address,    //32 the Config whose vault pays the stream
+  recipient: Address, //32
+  mint: Address,      //32
+  rate: [u8; 8],      //8 tokens per second
+  withdrawn: [u8; 8], //8
+  start_at: [u8; 4],  //4 for u32
+  end_at: [u8; 4],    //4 for u32
+  decimals: u8,       //1
+  bump: u8,           //1
+}
+impl Stream {
+  pub const LEN: usize = core::mem::size_of::<Stream>();
+  pub const SEED: &[u8] = b"stream";
+
+  pub fn funder(&self) -> &Address {
+    &self.funder
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn recipient(&self) -> &Address {
+    &self.recipient
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn rate(&self) -> u64 {
+    u64::from_le_bytes(self.rate)
+  }
+  pub fn withdrawn(&self) -> u64 {
+    u64::from_le_bytes(self.withdrawn)
+  }
+  pub fn start_at(&self) -> u32 {
+    u32::from_le_bytes(self.start_at)
+  }
+  pub fn end_at(&self) -> u32 {
+    u32::from_le_bytes(self.end_at)
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_funder(&mut self, addr: &Address) {
+    self.funder = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_recipient(&mut self, addr: &Address) {
+    self.recipient = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_rate(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.rate = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_withdrawn(&mut self, amt: u64) {
+    self.withdrawn = amt.to_le_bytes();
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// start_at < end_at, end_at must be in the future, and the total rate * duration must fit in u64
+  pub fn set_schedule(&mut self, start_at: u32, end_at: u32, now: u32) -> ProgramResult {
+    if end_at <= start_at || end_at <= now {
+      return Ee::StreamSchedule.e();
+    }
+    self.start_at = start_at.to_le_bytes();
+    self.end_at = end_at.to_le_bytes();
+    self.total()?;
+    Ok(())
+  }
+  /// amount streamed over the whole schedule
+  pub fn total(&self) -> Result<u64, ProgramError> {
+    self
+      .rate()
+      .checked_mul(self.end_at().saturating_sub(self.start_at()) as u64)
+      .ok_or(ProgramError::ArithmeticOverflow)
+  }
+  /// amount accrued at `now`, including what has been withdrawn
+  pub fn accrued(&self, now: u32) -> Result<u64, ProgramError> {
+    let elapsed = now.min(self.end_at()).saturating_sub(self.start_at()) as u64;
+    self
+      .rate()
+      .checked_mul(elapsed)
+      .ok_or(ProgramError::ArithmeticOverflow)
+  }
+  /// Add what has accrued but not been withdrawn to the withdrawn total, and return it
+  pub fn withdraw(&mut self, now: u32) -> Result<u64, ProgramError> {
+    let accrued = self.accrued(now)?;
+    let amount = accrued.saturating_sub(self.withdrawn());
+    self.set_withdrawn(accrued);
+    Ok(amount)
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::StreamPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::StreamPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Stream PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Merkle airdrops
+pub const MAX_CLAIMS: usize = 1024;
+
+/// Merkle airdrop of total tokens held in the vault ATA. Each (index, claimant, amount) leaf under root can be claimed once before deadline, then the funder claws back the rest
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Distributor {
+  funder: Address,                    //32 config admin or prog_owner
+  config: Address,                    //32 the Config whose vault pays the claims
+  mint: Address,                      //32
+  root: [u8; 32],                     //32
+  total: [u8; 8],                     //8
+  claimed: [u8; 8],                   //8
+  deadline: [u8; 8],                  //8 for i64
+  id: [u8; 8],                        //8 for u64
+  count: [u8; 4],                     //4 for u32, number of leaves
+  decimals: u8,                       //1
+  bump: u8,                           //1
+  claimed_bits: [u8; MAX_CLAIMS / 8], //128 one bit per leaf index
+}
+impl Distributor {
+  pub const LEN: usize = core::mem::size_of::<Distributor>();
+  pub const SEED: &[u8] = b"distributor";
+
+  pub fn funder(&self) -> &Address {
+    &self.funder
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn root(&self) -> &[u8; 32] {
+    &self.root
+  }
+  pub fn total(&self) -> u64 {
+    u64::from_le_bytes(self.total)
+  }
+  pub fn claimed(&self) -> u64 {
+    u64::from_le_bytes(self.claimed)
+  }
+  pub fn deadline(&self) -> i64 {
+    i64::from_le_bytes(self.deadline)
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn count(&self) -> u32 {
+    u32::from_le_bytes(self.count)
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_funder(&mut self, addr: &Address) {
+    self.funder = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_root(&mut self, root: [u8; 32]) {
+    self.root = root;
+  }
+  pub fn set_total(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.total = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_deadline(&mut self, time: i64) {
+    self.deadline = time.to_le_bytes();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_count(&mut self, count: u32) -> ProgramResult {
+    if count == 0 || count as usize > MAX_CLAIMS {
+      return Ee::DistributorProof.e();
+    }
+    self.count = count.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn is_claimed(&self, index: u32) -> bool {
+    let i = index as usize;
+    self.claimed_bits[i / 8] & (1 << (i % 8)) != 0
+  }
+  /// Mark the leaf at index as claimed and add amount to the claimed total, which must stay within total
+  pub fn claim(&mut self, index: u32, amount: u64) -> ProgramResult {
+    if index >= self.count() {
+      return Ee::DistributorProof.e();
+    }
+    if self.is_claimed(index) {
+      return Ee::DistributorClaimed.e();
+    }
+    let claimed = self
+      .claimed()
+      .checked_add(amount)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    if claimed > self.total() {
+      return Ee::DistributorProof.e();
+    }
+    let i = index as usize;
+    self.claimed_bits[i / 8] |= 1 << (i % 8);
+    self.claimed = claimed.to_le_bytes();
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::DistributorPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::DistributorPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Distributor PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Invoices
+/// Payment request for amount of mint into the vault ATA, made by the config admin or prog_owner so a TokLgcPay-style payment can be reconciled by issuer and id. Paid once, by payer if it is set, by anyone otherwise
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Invoice {
+  issuer: Address,   //32 gets the rent
+  config: Address,   //32 the Config whose vault receives the payment
+  payer: Address,    //32 zeros for anyone
+  mint: Address,     //32
+  paid_by: Address,  //32
+  amount: [u8; 8],   //8
+  received: [u8; 8], //8 what the vault received, which can be less than amount after transfer fees
+  due_at: [u8; 8],   //8 for i64, no payment after this
+  paid_at: [u8; 8],  //8 for i64
+  id: [u8; 8],       //8 for u64
+  status: u8,        //1 OPEN or PAID
+  decimals: u8,      //1
+  bump: u8,          //1
+}
+impl Invoice {
+  pub const LEN: usize = core::mem::size_of::<Invoice>();
+  pub const SEED: &[u8] = b"invoice";
+  pub const ANY_PAYER: [u8; 32] = [0u8; 32];
+  pub const OPEN: u8 = 0;
+  pub const PAID: u8 = 1;
+  /// first field of the receipt event
+  pub const PAID_EVENT: &[u8] = b"InvoicePaid";
+
+  pub fn issuer(&self) -> &Address {
+    &self.issuer
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn payer(&self) -> &Address {
+    &self.payer
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn paid_by(&self) -> &Address {
+    &self.paid_by
+  }
+  pub fn amount(&self) -> u64 {
+    u64::from_le_bytes(self.amount)
+  }
+  pub fn received(&self) -> u64 {
+    u64::from_le_bytes(self.received)
+  }
+  pub fn due_at(&self) -> i64 {
+    i64::from_le_bytes(self.due_at)
+  }
+  pub fn paid_at(&self) -> i64 {
+    i64::from_le_bytes(self.paid_at)
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn status(&self) -> u8 {
+    self.status
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_issuer(&mut self, addr: &Address) {
+    self.issuer = addr.clone();
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_payer(&mut self, addr: &Address) {
+    self.payer = addr.clone();
+  }
+  pub fn set_mint(&mut self, addr: &Address) {
+    self.mint = addr.clone();
+  }
+  pub fn set_amount(&mut self, amt: u64) -> ProgramResult {
+    none_zero_u64(amt)?;
+    self.amount = amt.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_due_at(&mut self, time: i64) {
+    self.due_at = time.to_le_bytes();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// An open invoice can be paid by its payer, or by anyone if none is set, until due_at
+  pub fn check_payable(&self, payer: &Address, time: i64) -> ProgramResult {
+    if self.status != Self::OPEN {
+      return Ee::InvoicePaid.e();
+    }
+    if self.payer.as_ref() != Self::ANY_PAYER && self.payer.ne(payer) {
+      return Ee::InvoicePayer.e();
+    }
+    if time > self.due_at() {
+      return Ee::InvoiceOverdue.e();
+    }
+    Ok(())
+  }
+  pub fn set_paid(&mut self, payer: &Address, received: u64, time: i64) {
+    self.paid_by = payer.clone();
+    self.received = received.to_le_bytes();
+    self.paid_at = time.to_le_bytes();
+    self.status = Self::PAID;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::InvoicePDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::InvoicePDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Invoice PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+//------------== Multisig
+pub const MAX_MEMBERS: usize = 10;
+
+/// M-of-N multisig acting as a config prog_owner through proposals
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Multisig {
+  config: Address,                 //32
+  members: [Address; MAX_MEMBERS], //320
+  member_count: u8,                //1
+  threshold: u8,                   //1
+  bump: u8,                        //1
+}
+impl Multisig {
+  pub const LEN: usize = core::mem::size_of::<Multisig>();
+  pub const SEED: &[u8] = b"multisig";
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn members(&self) -> &[Address] {
+    &self.members[..self.member_count as usize]
+  }
+  pub fn threshold(&self) -> u8 {
+    self.threshold
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  /// member index, used as its bit in proposal approvals
+  pub fn member_index(&self, addr: &Address) -> Result<u8, ProgramError> {
+    self
+      .members()
+      .iter()
+      .position(|m| m == addr)
+      .map(|i| i as u8)
+      .ok_or(Ee::OnlyMember.into())
+  }
+  /// true when the config prog_owner is its Multisig, so changes must go through proposals
+  pub fn governs(config_pda: &AccountView, config: &Config) -> Result<bool, ProgramError> {
+    let (multisig, _) = derive_pda1(config_pda.address(), Self::SEED)?;
+    Ok(config.prog_owner() == &multisig)
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  /// 1 <= threshold <= member count <= MAX_MEMBERS, with no duplicate members
+  pub fn set_members(&mut self, members: &[AccountView], threshold: u8) -> ProgramResult {
+    let count = members.len();
+    if count == 0 || count > MAX_MEMBERS || threshold == 0 || threshold as usize > count {
+      return Ee::MultisigMembers.e();
+    }
+    for (i, member) in members.iter().enumerate() {
+      if members[..i].iter().any(|m| m.address() == member.address()) {
+        return Ee::MultisigMembers.e();
+      }
+      self.members[i] = member.address().clone();
+    }
+    self.member_count = count as u8;
+    self.threshold = threshold;
+    Ok(())
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MultisigPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MultisigPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Multisig PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+/// A stored config change, executed once approvals reach the multisig threshold
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Proposal {
+  multisig: Address,  //32
+  account1: Address,  //32 new admin or prog_owner
+  id: [u8; 8],        //8
+  num_u64: [u8; 8],   //8 new fee
+  approvals: [u8; 2], //2 bitmap by member index
+  action: u8,         //1 ProposalAction
+  num_u8: u8,         //1 new status
+  executed: bool,     //1
+  bump: u8,           //1
+}
+impl Proposal {
+  pub const LEN: usize = core::mem::size_of::<Proposal>();
+  pub const SEED: &[u8] = b"proposal";
+
+  pub fn multisig(&self) -> &Address {
+    &self.multisig
+  }
+  pub fn account1(&self) -> &Address {
+    &self.account1
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn num_u64(&self) -> u64 {
+    u64::from_le_bytes(self.num_u64)
+  }
+  pub fn approvals(&self) -> u16 {
+    u16::from_le_bytes(self.approvals)
+  }
+  pub fn approval_count(&self) -> u8 {
+    self.approvals().count_ones() as u8
+  }
+  pub fn action(&self) -> ProposalAction {
+    ProposalAction::from(self.action)
+  }
+  pub fn num_u8(&self) -> u8 {
+    self.num_u8
+  }
+  pub fn executed(&self) -> bool {
+    self.executed
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_multisig(&mut self, addr: &Address) {
+    self.multisig = addr.clone();
+  }
+  pub fn set_account1(&mut self, addr: &Address) {
+    self.account1 = addr.clone();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_num_u64(&mut self, amt: u64) {
+    self.num_u64 = amt.to_le_bytes();
+  }
+  pub fn approve(&mut self, member_index: u8) {
+    self.approvals = (self.approvals() | (1u16 << member_index)).to_le_bytes();
+  }
+  pub fn set_action(&mut self, action: u8) {
+    self.action = action;
+  }
+  pub fn set_num_u8(&mut self, amt: u8) {
+    self.num_u8 = amt;
+  }
+  pub fn set_executed(&mut self, boo: bool) {
+    self.executed = boo;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::ProposalPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::ProposalPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Proposal PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+/// Config changes a Proposal or a queued Timelock action can carry
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProposalAction {
+  Invalid = 0,
+  SetAdmin = 1,
+  SetProgOwner = 2,
+  SetFee = 3,
+  SetStatus = 4,
+  SetDelay = 5,    //Timelock only
+  CloseConfig = 6, //Timelock only
+}
+impl From<u8> for ProposalAction {
+  fn from(num: u8) -> Self {
+    match num {
+      1 => ProposalAction::SetAdmin,
+      2 => ProposalAction::SetProgOwner,
+      3 => ProposalAction::SetFee,
+      4 => ProposalAction::SetStatus,
+      5 => ProposalAction::SetDelay,
+      6 => ProposalAction::CloseConfig,
+      _ => ProposalAction::Invalid,
+    }
+  }
+}
+impl ProposalAction {
+  /// validate the stored arguments when the action is made
+  pub fn check(self, num_u8: u8, num_u64: u64) -> ProgramResult {
+    match self {
+      ProposalAction::SetAdmin | ProposalAction::SetProgOwner | ProposalAction::CloseConfig => {}
+      ProposalAction::SetFee => none_zero_u64(num_u64)?,
+      ProposalAction::SetStatus => {
+        u8_to_status(num_u8)?;
+      }
+      ProposalAction::SetDelay => {
+        u32::try_from(num_u64).map_err(|_| Ee::InputDataLen)?;
+      }
+      ProposalAction::Invalid => return Ee::FunctionSelector.e(),
+    }
+    Ok(())
+  }
+  /// apply a Config field change. SetDelay and CloseConfig are applied by the Timelock
+  pub fn apply(
+    self,
+    config: &mut Config,
+    account1: &Address,
+    num_u8: u8,
+    num_u64: u64,
+  ) -> ProgramResult {
+    match self {
+      ProposalAction::SetAdmin => config.set_admin(account1),
+      ProposalAction::SetProgOwner => config.set_prog_owner(account1),
+      ProposalAction::SetFee => config.set_fee(num_u64)?,
+      ProposalAction::SetStatus => config.set_status(num_u8),
+      _ => return Ee::FunctionSelector.e(),
+    }
+    Ok(())
+  }
+}
+
+//------------== Timelock
+/// Governs a config as its prog_owner. Config changes are queued by owner and run after the Config timelock_delay
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Timelock {
+  config: Address, //32
+  owner: Address,  //32 queues and cancels actions
+  bump: u8,        //1
+}
+impl Timelock {
+  pub const LEN: usize = core::mem::size_of::<Timelock>();
+  pub const SEED: &[u8] = b"timelock";
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn owner(&self) -> &Address {
+    &self.owner
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, addr: &Address) {
+    self.config = addr.clone();
+  }
+  pub fn set_owner(&mut self, addr: &Address) {
+    self.owner = addr.clone();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// true when the config prog_owner is its Timelock, so changes must go through the queue
+  pub fn governs(config_pda: &AccountView, config: &Config) -> Result<bool, ProgramError> {
+    let (timelock, _) = derive_pda1(config_pda.address(), Self::SEED)?;
+    Ok(config.prog_owner() == &timelock)
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::TimelockPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::TimelockPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For Timelock PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
+  }
+}
+
+/// A config change waiting in a Timelock queue until eta
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct QueuedAction {
+  timelock: Address, //32
+  account1: Address, //32 new admin or prog_owner
+  id: [u8; 8],       //8
+  num_u64: [u8; 8],  //8 new fee or delay
+  eta: [u8; 4],      //4 for u32
+  action: u8,        //1 ProposalAction
+  num_u8: u8,        //1 new status
+  bump: u8,          //1
+}
+impl QueuedAction {
+  pub const LEN: usize = core::mem::size_of::<QueuedAction>();
+  pub const SEED: &[u8] = b"queued";
+
+  pub fn timelock(&self) -> &Address {
+    &self.timelock
+  }
+  pub fn account1(&self) -> &Address {
+    &self.account1
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn num_u64(&self) -> u64 {
+    u64::from_le_bytes(self.num_u64)
+  }
+  pub fn eta(&self) -> u32 {
+    u32::from_le_bytes(self.eta)
+  }
+  pub fn action(&self) -> ProposalAction {
+    ProposalAction::from(self.action)
+  }
+  pub fn num_u8(&self) -> u8 {
+    self.num_u8
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_timelock(&mut self, addr: &Address) {
+    self.timelock = addr.clone();
+  }
+  pub fn set_account1(&mut self, addr: &Address) {
+    self.account1 = addr.clone();
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_num_u64(&mut self, amt: u64) {
+    self.num_u64 = amt.to_le_bytes();
+  }
+  pub fn set_eta(&mut self, time: u32) {
+    self.eta = time.to_le_bytes();
+  }
+  pub fn set_action(&mut self, action: u8) {
+    self.action = action;
+  }
+  pub fn set_num_u8(&mut self, amt: u8) {
+    self.num_u8 = amt;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::QueuedActionPDA.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::QueuedActionPDA.e();
+      }
+    }
+    Ok(())
+  }
+  //For QueuedAction PDA
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
     unsafe { Ok(&mut *(pda.try_borrow_mut()?.as_ptr() as *mut Self)) }
   }
 }
@@ -15,6 +15,17 @@ pub fn read_oracle_pda(
   };
   Ok(price)
 }
+/// Check an oracle vendor and feed id before an escrow stores them, so a typo does not make an offer that can never be taken
+pub fn check_oracle_feed(oracle_vendor: u8, feed_id: &[u8; 32]) -> Result<(), ProgramError> {
+  match oracle_vendor {
+    0 | 1 => {}
+    _ => return Err(Ee::OracleNum.into()),
+  }
+  if feed_id == &[0u8; 32] {
+    return Err(Ee::OracleFeedId.into());
+  }
+  Ok(())
+}
 pub const MAX_PRICE_AGE: u64 = 60; // in seconds
 
 pub fn pyth_network(pda: &AccountView, feed_id: [u8; 32]) -> Result<u64, ProgramError> {
@@ -2,17 +2,24 @@
 import { expect, test } from "bun:test";
 import { getBase58Decoder } from "@solana/kit";
 import type { Keypair, PublicKey } from "@solana/web3.js";
-import { decodeConfig2Dev, decodeConfigDev, Status } from "./decoder";
+import {
+	configAcctDecoder,
+	decodeConfig2Dev,
+	decodeConfigDev,
+	Status,
+} from "./decoder";
 import {
 	acctExists,
 	closeConfig,
 	configBump,
+	configMigrate,
 	configPDA,
 	configResize,
 	getJsTime,
 	initConfig,
 	initSolBalc,
 	readAcct,
+	setAta,
 	setLgcMint,
 	setTime,
 	svm,
@@ -263,11 +270,51 @@ test("updateConfig2", () => {
 	expect(decoded.newAccount1).toEqual(acct1);
 });
 
+test("migrate legacy Config2", () => {
+	ll("\n------== Migrate legacy Config2");
+	//rebuild the layout made before open_escrows and creator: Config fields up to bump, then new_u32, new_u64, new_account1
+	const configLen = configAcctDecoder.fixedSize;
+	const legacyLen = 244;
+	const config2Tail = 44;
+	const acct = svm.getAccount(configPDA)!;
+	const legacy = new Uint8Array(legacyLen + config2Tail);
+	legacy.set(acct.data.slice(0, legacyLen));
+	legacy.set(acct.data.slice(configLen, configLen + config2Tail), legacyLen);
+	svm.setAccount(configPDA, { ...acct, data: legacy });
+
+	//the creator must seed the Config PDA
+	configMigrate(user1Kp, configPDA, user1, "0x34");
+	configMigrate(user1Kp, configPDA, owner);
+	const rawAccount = svm.getAccount(configPDA);
+	expect(rawAccount?.data.byteLength).toEqual(configLen + config2Tail);
+	const decoded = decodeConfig2Dev(rawAccount?.data);
+	expect(decoded.progOwner).toEqual(progOwner);
+	expect(decoded.admin).toEqual(admin);
+	expect(decoded.fee).toEqual(numU64);
+	expect(decoded.bump).toEqual(configBump);
+	expect(decoded.openEscrows).toEqual(0n);
+	expect(decoded.creator).toEqual(owner);
+	expect(decoded.newU32).toEqual(432901);
+	expect(decoded.newU64).toEqual(137000000n);
+	expect(decoded.newAccount1).toEqual(user1);
+	//only legacy lengths can be migrated
+	configMigrate(user1Kp, configPDA, owner, "0x3c");
+});
+
 test("close configPDA", () => {
 	ll("\n------== Close configPDA");
 	signerKp = ownerKp;
 	dest = signerKp.publicKey;
-	closeConfig(signerKp, configPDA, dest);
+	//rent only goes to the prog_owner
+	closeConfig(signerKp, configPDA, admin, mints, false, "0x93");
+	//only the prog_owner can force
+	closeConfig(adminKp, configPDA, dest, mints, true, "0x2");
+
+	tokenAmount = 1000n;
+	setAta(mints[0]!, vaultO, tokenAmount);
+	closeConfig(signerKp, configPDA, dest, mints, false, "0x95");
+	setAta(mints[0]!, vaultO, 0n);
+	closeConfig(signerKp, configPDA, dest, mints);
 	const rawAccount = svm.getAccount(configPDA);
 	expect(rawAccount).toBeNull();
 });
@@ -9,11 +9,14 @@ import type {
 import {
 	fixDecoderSize,
 	getAddressDecoder,
+	getArrayDecoder,
 	getBooleanDecoder,
 	getBytesDecoder,
 	getEnumDecoder,
+	getI64Decoder,
 	getStructDecoder,
 	getU8Decoder,
+	getU16Decoder,
 	getU32Decoder,
 	getU64Decoder,
 	getUtf8Decoder,
@@ -55,6 +58,14 @@ export type ConfigAcct = {
 	status: Status;
 	vaultBump: number;
 	bump: number;
+	openEscrows: bigint;
+	creator: Address;
+	timelockDelay: number;
+	tokenBalances: bigint[];
+	committed: bigint[];
+	yieldRateBps: number;
+	yieldIndex: bigint;
+	yieldIndexAt: number;
 };
 export const configAcctDecoder: FixedSizeDecoder<ConfigAcct> = getStructDecoder(
 	[
@@ -76,6 +87,14 @@ export const configAcctDecoder: FixedSizeDecoder<ConfigAcct> = getStructDecoder(
 		//https://github.com/anza-xyz/kit/tree/main/packages/codecs-data-structures#enum-codec
 		["vaultBump", getU8Decoder()],
 		["bump", getU8Decoder()],
+		["openEscrows", getU64Decoder()],
+		["creator", getAddressDecoder()],
+		["timelockDelay", getU32Decoder()],
+		["tokenBalances", getArrayDecoder(getU64Decoder(), { size: 4 })],
+		["committed", getArrayDecoder(getU64Decoder(), { size: 4 })],
+		["yieldRateBps", getU16Decoder()],
+		["yieldIndex", getU64Decoder()],
+		["yieldIndexAt", getU32Decoder()],
 		//["padding", getArrayDecoder(getU64Decoder(), { size: 3 })],
 	],
 );
@@ -100,6 +119,14 @@ export const decodeConfig = (
 		ll("isAuthorized:", decoded.isAuthorized);
 		ll("status:", decoded.status);
 		ll("bump:", decoded.bump);
+		ll("openEscrows:", decoded.openEscrows);
+		ll("creator:", decoded.creator);
+		ll("timelockDelay:", decoded.timelockDelay);
+		ll("tokenBalances:", decoded.tokenBalances);
+		ll("committed:", decoded.committed);
+		ll("yieldRateBps:", decoded.yieldRateBps);
+		ll("yieldIndex:", decoded.yieldIndex);
+		ll("yieldIndexAt:", decoded.yieldIndexAt);
 	}
 	return decoded;
 };
@@ -125,6 +152,14 @@ export const decodeConfigDev = (
 		isAuthorized: decoded.isAuthorized,
 		status: decoded.status,
 		bump: decoded.bump,
+		openEscrows: decoded.openEscrows,
+		creator: new PublicKey(decoded.creator.toString()),
+		timelockDelay: decoded.timelockDelay,
+		tokenBalances: decoded.tokenBalances,
+		committed: decoded.committed,
+		yieldRateBps: decoded.yieldRateBps,
+		yieldIndex: decoded.yieldIndex,
+		yieldIndexAt: decoded.yieldIndexAt,
 	};
 	return decodedV1;
 };
@@ -144,6 +179,14 @@ export type ConfigAcctDev = {
 	isAuthorized: boolean;
 	status: Status;
 	bump: number;
+	openEscrows: bigint;
+	creator: PublicKey;
+	timelockDelay: number;
+	tokenBalances: bigint[];
+	committed: bigint[];
+	yieldRateBps: number;
+	yieldIndex: bigint;
+	yieldIndexAt: number;
 };
 //---------------== Config2PDA
 export type Config2Acct = {
@@ -163,6 +206,14 @@ export type Config2Acct = {
 	status: Status;
 	vaultBump: number;
 	bump: number;
+	openEscrows: bigint;
+	creator: Address;
+	timelockDelay: number;
+	tokenBalances: bigint[];
+	committed: bigint[];
+	yieldRateBps: number;
+	yieldIndex: bigint;
+	yieldIndexAt: number;
 	newU32: number;
 	newU64: bigint;
 	newAccount1: Address;
@@ -187,6 +238,14 @@ export const config2AcctDecoder: FixedSizeDecoder<Config2Acct> =
 		//https://github.com/anza-xyz/kit/tree/main/packages/codecs-data-structures#enum-codec
 		["vaultBump", getU8Decoder()],
 		["bump", getU8Decoder()],
+		["openEscrows", getU64Decoder()],
+		["creator", getAddressDecoder()],
+		["timelockDelay", getU32Decoder()],
+		["tokenBalances", getArrayDecoder(getU64Decoder(), { size: 4 })],
+		["committed", getArrayDecoder(getU64Decoder(), { size: 4 })],
+		["yieldRateBps", getU16Decoder()],
+		["yieldIndex", getU64Decoder()],
+		["yieldIndexAt", getU32Decoder()],
 		["newU32", getU32Decoder()],
 		["newU64", getU64Decoder()],
 		["newAccount1", getAddressDecoder()],
@@ -215,6 +274,14 @@ export const decodeConfig2 = (
 		ll("isAuthorized:", decoded.isAuthorized);
 		ll("status:", decoded.status);
 		ll("bump:", decoded.bump);
+		ll("openEscrows:", decoded.openEscrows);
+		ll("creator:", decoded.creator);
+		ll("timelockDelay:", decoded.timelockDelay);
+		ll("tokenBalances:", decoded.tokenBalances);
+		ll("committed:", decoded.committed);
+		ll("yieldRateBps:", decoded.yieldRateBps);
+		ll("yieldIndex:", decoded.yieldIndex);
+		ll("yieldIndexAt:", decoded.yieldIndexAt);
 		ll("newU32:", decoded.newU32);
 		ll("newU64:", decoded.newU64);
 		ll("newAccount1:", decoded.newAccount1);
@@ -245,6 +312,14 @@ export const decodeConfig2Dev = (
 		isAuthorized: decoded.isAuthorized,
 		status: decoded.status,
 		bump: decoded.bump,
+		openEscrows: decoded.openEscrows,
+		creator: new PublicKey(decoded.creator.toString()),
+		timelockDelay: decoded.timelockDelay,
+		tokenBalances: decoded.tokenBalances,
+		committed: decoded.committed,
+		yieldRateBps: decoded.yieldRateBps,
+		yieldIndex: decoded.yieldIndex,
+		yieldIndexAt: decoded.yieldIndexAt,
 		newU32: decoded.newU32,
 		newU64: decoded.newU64,
 		newAccount1: new PublicKey(decoded.newAccount1.toString()),
@@ -267,6 +342,14 @@ export type Config2AcctDev = {
 	isAuthorized: boolean;
 	status: Status;
 	bump: number;
+	openEscrows: bigint;
+	creator: PublicKey;
+	timelockDelay: number;
+	tokenBalances: bigint[];
+	committed: bigint[];
+	yieldRateBps: number;
+	yieldIndex: bigint;
+	yieldIndexAt: number;
 	newU32: number;
 	newU64: bigint;
 	newAccount1: PublicKey;
@@ -281,8 +364,19 @@ export type EscrowAcct = {
 	amountX: bigint;
 	amountY: bigint;
 	id: bigint;
+	seq: number;
+	endAmountY: bigint;
+	startTime: bigint;
+	endTime: bigint;
+	feedId: ReadonlyUint8Array;
+	triggerPrice: bigint;
+	receivedY: bigint;
+	config: Address;
 	decimalX: number;
 	decimalY: number;
+	mode: number;
+	oracleVendor: number;
+	triggerAbove: boolean;
 	bump: number;
 };
 export const escrowAcctDecoder: FixedSizeDecoder<EscrowAcct> = getStructDecoder(
@@ -293,8 +387,19 @@ export const escrowAcctDecoder: FixedSizeDecoder<EscrowAcct> = getStructDecoder(
 		["amountX", getU64Decoder()],
 		["amountY", getU64Decoder()],
 		["id", getU64Decoder()],
+		["seq", getU32Decoder()],
+		["endAmountY", getU64Decoder()],
+		["startTime", getI64Decoder()],
+		["endTime", getI64Decoder()],
+		["feedId", fixDecoderSize(getBytesDecoder(), 32)],
+		["triggerPrice", getU64Decoder()],
+		["receivedY", getU64Decoder()],
+		["config", getAddressDecoder()],
 		["decimalX", getU8Decoder()],
 		["decimalY", getU8Decoder()],
+		["mode", getU8Decoder()],
+		["oracleVendor", getU8Decoder()],
+		["triggerAbove", getBooleanDecoder()],
 		["bump", getU8Decoder()],
 	],
 );
@@ -310,8 +415,16 @@ export const decodeEscrow = (
 		ll("amountX:", decoded.amountX);
 		ll("amountY:", decoded.amountY);
 		ll("id:", decoded.id);
+		ll("seq:", decoded.seq);
+		ll("endAmountY:", decoded.endAmountY);
+		ll("startTime:", decoded.startTime);
+		ll("endTime:", decoded.endTime);
 		ll("decimalX:", decoded.decimalX);
 		ll("decimalY:", decoded.decimalY);
+		ll("mode:", decoded.mode);
+		ll("triggerPrice:", decoded.triggerPrice);
+		ll("receivedY:", decoded.receivedY);
+		ll("config:", decoded.config);
 		ll("bump:", decoded.bump);
 	}
 	return decoded;
@@ -329,8 +442,19 @@ export const decodeEscrowDev = (
 		amountX: decoded.amountX,
 		amountY: decoded.amountY,
 		id: decoded.id,
+		seq: decoded.seq,
+		endAmountY: decoded.endAmountY,
+		startTime: decoded.startTime,
+		endTime: decoded.endTime,
+		feedId: decoded.feedId,
+		triggerPrice: decoded.triggerPrice,
+		receivedY: decoded.receivedY,
+		config: new PublicKey(decoded.config.toString()),
 		decimalX: decoded.decimalX,
 		decimalY: decoded.decimalY,
+		mode: decoded.mode,
+		oracleVendor: decoded.oracleVendor,
+		triggerAbove: decoded.triggerAbove,
 		bump: decoded.bump,
 	};
 	return decodedV1;
@@ -342,10 +466,476 @@ export type EscrowAcctDev = {
 	amountX: bigint;
 	amountY: bigint;
 	id: bigint;
+	seq: number;
+	endAmountY: bigint;
+	startTime: bigint;
+	endTime: bigint;
+	feedId: ReadonlyUint8Array;
+	triggerPrice: bigint;
+	receivedY: bigint;
+	config: PublicKey;
 	decimalX: number;
 	decimalY: number;
+	mode: number;
+	oracleVendor: number;
+	triggerAbove: boolean;
 	bump: number;
 };
+//---------------== MakerIndexPDA
+export type MakerIndexAcct = {
+	maker: Address;
+	nextId: bigint;
+	openCount: bigint;
+	bump: number;
+};
+export const makerIndexAcctDecoder: FixedSizeDecoder<MakerIndexAcct> =
+	getStructDecoder([
+		["maker", getAddressDecoder()],
+		["nextId", getU64Decoder()],
+		["openCount", getU64Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeMakerIndex = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = makerIndexAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("maker:", decoded.maker);
+		ll("nextId:", decoded.nextId);
+		ll("openCount:", decoded.openCount);
+	}
+	return decoded;
+};
+//---------------== BasketPDA
+export type BasketAcct = {
+	maker: Address;
+	config: Address;
+	id: bigint;
+	legCountX: number;
+	legCountY: number;
+	taken: boolean;
+	bump: number;
+};
+export type BasketLeg = {
+	mint: Address;
+	amount: bigint;
+	decimals: number;
+};
+export const basketAcctDecoder: FixedSizeDecoder<BasketAcct> =
+	getStructDecoder([
+		["maker", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["id", getU64Decoder()],
+		["legCountX", getU8Decoder()],
+		["legCountY", getU8Decoder()],
+		["taken", getBooleanDecoder()],
+		["bump", getU8Decoder()],
+	]);
+export const basketLegDecoder: FixedSizeDecoder<BasketLeg> = getStructDecoder([
+	["mint", getAddressDecoder()],
+	["amount", getU64Decoder()],
+	["decimals", getU8Decoder()],
+]);
+/** the header, then X legs followed by Y legs */
+export const decodeBasket = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = basketAcctDecoder.decode(bytes);
+	const headerLen = basketAcctDecoder.fixedSize;
+	const legLen = basketLegDecoder.fixedSize;
+	const legs: BasketLeg[] = [];
+	for (let i = 0; i < decoded.legCountX + decoded.legCountY; i++) {
+		legs.push(basketLegDecoder.decode(bytes, headerLen + i * legLen));
+	}
+	if (isVerbose) {
+		ll("maker:", decoded.maker);
+		ll("id:", decoded.id);
+		ll("taken:", decoded.taken);
+		ll("legs:", legs);
+	}
+	return {
+		...decoded,
+		legsX: legs.slice(0, decoded.legCountX),
+		legsY: legs.slice(decoded.legCountX),
+	};
+};
+//---------------== AuctionPDA
+export type AuctionAcct = {
+	maker: Address;
+	config: Address;
+	mintX: Address;
+	mintY: Address;
+	highBidder: Address;
+	amountX: bigint;
+	reserveY: bigint;
+	highBid: bigint;
+	endTime: bigint;
+	id: bigint;
+	decimalX: number;
+	decimalY: number;
+	bump: number;
+};
+export const auctionAcctDecoder: FixedSizeDecoder<AuctionAcct> =
+	getStructDecoder([
+		["maker", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["mintX", getAddressDecoder()],
+		["mintY", getAddressDecoder()],
+		["highBidder", getAddressDecoder()],
+		["amountX", getU64Decoder()],
+		["reserveY", getU64Decoder()],
+		["highBid", getU64Decoder()],
+		["endTime", getI64Decoder()],
+		["id", getU64Decoder()],
+		["decimalX", getU8Decoder()],
+		["decimalY", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeAuction = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = auctionAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("maker:", decoded.maker);
+		ll("highBidder:", decoded.highBidder);
+		ll("amountX:", decoded.amountX);
+		ll("reserveY:", decoded.reserveY);
+		ll("highBid:", decoded.highBid);
+		ll("endTime:", decoded.endTime);
+		ll("id:", decoded.id);
+	}
+	return decoded;
+};
+export type BidAcct = {
+	auction: Address;
+	bidder: Address;
+	amount: bigint;
+	bump: number;
+};
+export const bidAcctDecoder: FixedSizeDecoder<BidAcct> = getStructDecoder([
+	["auction", getAddressDecoder()],
+	["bidder", getAddressDecoder()],
+	["amount", getU64Decoder()],
+	["bump", getU8Decoder()],
+]);
+export const decodeBid = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = bidAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("auction:", decoded.auction);
+		ll("bidder:", decoded.bidder);
+		ll("amount:", decoded.amount);
+	}
+	return decoded;
+};
+//---------------== MilestonePDA
+export type MilestoneAcct = {
+	payer: Address;
+	config: Address;
+	payee: Address;
+	arbiter: Address;
+	mint: Address;
+	amounts: bigint[];
+	deadline: bigint;
+	id: bigint;
+	count: number;
+	released: number;
+	refunded: number;
+	disputed: number;
+	decimals: number;
+	bump: number;
+};
+export const milestoneAcctDecoder: FixedSizeDecoder<MilestoneAcct> =
+	getStructDecoder([
+		["payer", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["payee", getAddressDecoder()],
+		["arbiter", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["amounts", getArrayDecoder(getU64Decoder(), { size: 8 })],
+		["deadline", getI64Decoder()],
+		["id", getU64Decoder()],
+		["count", getU8Decoder()],
+		["released", getU8Decoder()],
+		["refunded", getU8Decoder()],
+		["disputed", getU8Decoder()],
+		["decimals", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeMilestone = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = milestoneAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("payer:", decoded.payer);
+		ll("payee:", decoded.payee);
+		ll("arbiter:", decoded.arbiter);
+		ll("amounts:", decoded.amounts.slice(0, decoded.count));
+		ll("deadline:", decoded.deadline);
+		ll("id:", decoded.id);
+		ll("released:", decoded.released);
+		ll("refunded:", decoded.refunded);
+		ll("disputed:", decoded.disputed);
+	}
+	return decoded;
+};
+//---------------== SubscriptionPDA
+export type SubscriptionAcct = {
+	user: Address;
+	config: Address;
+	mint: Address;
+	amount: bigint;
+	paidThrough: bigint;
+	period: number;
+	maxPeriods: number;
+	paidPeriods: number;
+	decimals: number;
+	bump: number;
+};
+export const subscriptionAcctDecoder: FixedSizeDecoder<SubscriptionAcct> =
+	getStructDecoder([
+		["user", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["amount", getU64Decoder()],
+		["paidThrough", getI64Decoder()],
+		["period", getU32Decoder()],
+		["maxPeriods", getU32Decoder()],
+		["paidPeriods", getU32Decoder()],
+		["decimals", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeSubscription = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = subscriptionAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("user:", decoded.user);
+		ll("amount:", decoded.amount);
+		ll("paidThrough:", decoded.paidThrough);
+		ll("period:", decoded.period);
+		ll("paidPeriods:", decoded.paidPeriods, "of", decoded.maxPeriods);
+	}
+	return decoded;
+};
+//---------------== StreamPDA
+export type StreamAcct = {
+	funder: Address;
+	config: Address;
+	recipient: Address;
+	mint: Address;
+	rate: bigint;
+	withdrawn: bigint;
+	startAt: number;
+	endAt: number;
+	decimals: number;
+	bump: number;
+};
+export const streamAcctDecoder: FixedSizeDecoder<StreamAcct> =
+	getStructDecoder([
+		["funder", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["recipient", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["rate", getU64Decoder()],
+		["withdrawn", getU64Decoder()],
+		["startAt", getU32Decoder()],
+		["endAt", getU32Decoder()],
+		["decimals", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeStream = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = streamAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("funder:", decoded.funder);
+		ll("recipient:", decoded.recipient);
+		ll("rate:", decoded.rate);
+		ll("withdrawn:", decoded.withdrawn);
+		ll("startAt:", decoded.startAt, ", endAt:", decoded.endAt);
+	}
+	return decoded;
+};
+//---------------== DistributorPDA
+export type DistributorAcct = {
+	funder: Address;
+	config: Address;
+	mint: Address;
+	root: ReadonlyUint8Array;
+	total: bigint;
+	claimed: bigint;
+	deadline: bigint;
+	id: bigint;
+	count: number;
+	decimals: number;
+	bump: number;
+	claimedBits: ReadonlyUint8Array;
+};
+export const distributorAcctDecoder: FixedSizeDecoder<DistributorAcct> =
+	getStructDecoder([
+		["funder", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["root", fixDecoderSize(getBytesDecoder(), 32)],
+		["total", getU64Decoder()],
+		["claimed", getU64Decoder()],
+		["deadline", getI64Decoder()],
+		["id", getU64Decoder()],
+		["count", getU32Decoder()],
+		["decimals", getU8Decoder()],
+		["bump", getU8Decoder()],
+		["claimedBits", fixDecoderSize(getBytesDecoder(), 128)],
+	]);
+export const decodeDistributor = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = distributorAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("funder:", decoded.funder);
+		ll("id:", decoded.id);
+		ll("claimed:", decoded.claimed, "of", decoded.total);
+		ll("deadline:", decoded.deadline);
+		ll("count:", decoded.count);
+	}
+	return decoded;
+};
+//---------------== InvoicePDA
+export type InvoiceAcct = {
+	issuer: Address;
+	config: Address;
+	payer: Address;
+	mint: Address;
+	paidBy: Address;
+	amount: bigint;
+	received: bigint;
+	dueAt: bigint;
+	paidAt: bigint;
+	id: bigint;
+	status: number;
+	decimals: number;
+	bump: number;
+};
+export const invoiceAcctDecoder: FixedSizeDecoder<InvoiceAcct> =
+	getStructDecoder([
+		["issuer", getAddressDecoder()],
+		["config", getAddressDecoder()],
+		["payer", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["paidBy", getAddressDecoder()],
+		["amount", getU64Decoder()],
+		["received", getU64Decoder()],
+		["dueAt", getI64Decoder()],
+		["paidAt", getI64Decoder()],
+		["id", getU64Decoder()],
+		["status", getU8Decoder()],
+		["decimals", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeInvoice = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = invoiceAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("issuer:", decoded.issuer);
+		ll("id:", decoded.id);
+		ll("payer:", decoded.payer);
+		ll("amount:", decoded.amount);
+		ll("dueAt:", decoded.dueAt);
+		ll("status:", decoded.status, ", paidBy:", decoded.paidBy);
+	}
+	return decoded;
+};
+//---------------== VaultPDA
+export type VaultAcct = {
+	principal: bigint;
+	accrued: bigint;
+	updatedAt: number;
+	yieldIndex: bigint;
+	pool: Address;
+	bump: number;
+};
+export const vaultAcctDecoder: FixedSizeDecoder<VaultAcct> = getStructDecoder([
+	["principal", getU64Decoder()],
+	["accrued", getU64Decoder()],
+	["updatedAt", getU32Decoder()],
+	["yieldIndex", getU64Decoder()],
+	["pool", getAddressDecoder()],
+	["bump", getU8Decoder()],
+]);
+export const decodeVault = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = vaultAcctDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("principal:", decoded.principal);
+		ll("accrued:", decoded.accrued);
+		ll("updatedAt:", decoded.updatedAt);
+		ll("yieldIndex:", decoded.yieldIndex);
+		ll("pool:", decoded.pool);
+		ll("bump:", decoded.bump);
+	}
+	return decoded;
+};
+//---------------== VaultLock PDA
+export type VaultLockAcct = {
+	user: Address;
+	mint: Address;
+	funder: Address;
+	total: bigint;
+	startAt: number;
+	cliffAt: number;
+	endAt: number;
+	bump: number;
+};
+export const vaultLockDecoder: FixedSizeDecoder<VaultLockAcct> =
+	getStructDecoder([
+		["user", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["funder", getAddressDecoder()],
+		["total", getU64Decoder()],
+		["startAt", getU32Decoder()],
+		["cliffAt", getU32Decoder()],
+		["endAt", getU32Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const decodeVaultLock = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+	isVerbose = true,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = vaultLockDecoder.decode(bytes);
+	if (isVerbose) {
+		ll("user:", decoded.user);
+		ll("mint:", decoded.mint);
+		ll("total:", decoded.total);
+		ll("startAt:", decoded.startAt);
+		ll("cliffAt:", decoded.cliffAt);
+		ll("endAt:", decoded.endAt);
+	}
+	return decoded;
+};
 //---------------==
 export type DecodedAccount = {
 	executable: boolean;
@@ -0,0 +1,135 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { Status } from "./decoder";
+import {
+	ataBalCk,
+	day,
+	delegateRevoke,
+	delegateSet,
+	delegateWithdrawSol,
+	delegateWithdrawTok,
+	depositSol,
+	findVaultDelegate,
+	getAta,
+	initConfig,
+	setAtaCheck,
+	setLgcMint,
+	svm,
+	vault3,
+	warpTime,
+} from "./litesvm-utils";
+import { as6zBn, as9zBn, ll } from "./utils";
+import {
+	admin,
+	hackerKp,
+	owner,
+	pyusdMint,
+	usdcMint,
+	usdgMint,
+	usdtMint,
+	user1Kp,
+	user3,
+	user3Kp,
+} from "./web3jsSetup";
+
+const botKp = new Keypair();
+const bot = botKp.publicKey;
+svm.airdrop(bot, as9zBn(1));
+const solDelegate = findVaultDelegate(user3).pda;
+const tokDelegate = findVaultDelegate(user3, usdcMint).pda;
+
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	setLgcMint(usdcMint);
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		user1Kp,
+		[usdcMint, usdtMint, pyusdMint, usdgMint],
+		owner,
+		admin,
+		true,
+		Status.Active,
+		111000000n,
+		"Vault delegates",
+	);
+});
+
+test("User3 sets a SOL delegate with a daily cap", () => {
+	ll("\n------== User3 sets a SOL delegate");
+	depositSol(user3Kp, vault3, as9zBn(3));
+	delegateSet(user3Kp, solDelegate, bot, PublicKey.default, as9zBn(1), day);
+});
+
+test("Delegate withdraws SOL within the cap per period", () => {
+	ll("\n------== Delegate withdraws SOL");
+	const amount = as9zBn(0.6);
+	const balcBf = svm.getBalance(bot)!;
+	delegateWithdrawSol(botKp, user3, vault3, solDelegate, bot, amount);
+	expect(svm.getBalance(bot)! - balcBf).toBeLessThanOrEqual(amount);
+
+	delegateWithdrawSol(botKp, user3, vault3, solDelegate, bot, amount, "0x88");
+	const hacker = hackerKp.publicKey;
+	delegateWithdrawSol(hackerKp, user3, vault3, solDelegate, hacker, 1n, "0x87");
+
+	warpTime(day);
+	delegateWithdrawSol(botKp, user3, vault3, solDelegate, bot, amount);
+});
+
+test("User3 revokes the SOL delegate", () => {
+	ll("\n------== User3 revokes the SOL delegate");
+	delegateRevoke(hackerKp, solDelegate, "0x87");
+	delegateRevoke(user3Kp, solDelegate);
+	delegateWithdrawSol(botKp, user3, vault3, solDelegate, bot, 1n, "0x87");
+});
+
+test("Delegate withdraws tokens within the cap per period", () => {
+	ll("\n------== Delegate withdraws tokens");
+	setLgcMint(usdcMint);
+	const vaultAta = getAta(usdcMint, vault3);
+	const botAta = getAta(usdcMint, bot);
+	setAtaCheck(usdcMint, vault3, as6zBn(500), "Vault3 USDC");
+	setAtaCheck(usdcMint, bot, 0n, "Bot USDC");
+	delegateSet(user3Kp, tokDelegate, bot, usdcMint, as6zBn(100), day);
+
+	const amount = as6zBn(70);
+	delegateWithdrawTok(
+		botKp,
+		user3,
+		vaultAta,
+		botAta,
+		vault3,
+		tokDelegate,
+		usdcMint,
+		6,
+		amount,
+	);
+	ataBalCk(botAta, amount, "Bot");
+	delegateWithdrawTok(
+		botKp,
+		user3,
+		vaultAta,
+		botAta,
+		vault3,
+		tokDelegate,
+		usdcMint,
+		6,
+		amount,
+		"0x88",
+	);
+	warpTime(day);
+	delegateWithdrawTok(
+		botKp,
+		user3,
+		vaultAta,
+		botAta,
+		vault3,
+		tokDelegate,
+		usdcMint,
+		6,
+		amount,
+	);
+	ataBalCk(vaultAta, as6zBn(360), "Vault3");
+});
@@ -1,43 +1,80 @@
 /** biome-ignore-all lint/style/noNonNullAssertion: <> */
 import { expect, test } from "bun:test";
-import type { Keypair, PublicKey } from "@solana/web3.js";
+import { type Keypair, PublicKey } from "@solana/web3.js";
 import type { AccountInfoBytes } from "litesvm";
-import { decodeConfigDev, decodeEscrowDev, Status } from "./decoder";
+import {
+	decodeAuction,
+	decodeBasket,
+	decodeConfigDev,
+	decodeEscrowDev,
+	decodeMakerIndex,
+	decodeMilestone,
+	Status,
+} from "./decoder";
 import {
 	acctExists,
 	acctIsNull,
+	amendTokEscrow,
 	ataBalCk,
 	ataBalc,
+	bidAuction,
 	cancelTokEscrow,
+	closeBasket,
+	closeMilestone,
+	closeSolEscrow,
 	configBump,
 	configPDA,
+	disputeMilestone,
+	escrowMigrate,
+	findAuction,
+	findBasket,
+	findBid,
 	findEscrow,
+	findMakerIndex,
+	findMilestone,
+	findPdaV1,
 	getAta,
 	initConfig,
 	initSolBalc,
 	lgcInitAta,
 	lgcInitMint,
 	lgcMintToken,
+	makeAuction,
+	makeBasket,
+	makeMilestone,
+	makeSolEscrow,
 	makeTokEscrow,
 	type PdaOut,
 	readAcct,
+	setAta,
 	setAtaCheck,
 	setLgcMint,
+	setPriceFeedPda,
+	setTime,
+	settleAuction,
+	settleMilestone,
 	svm,
+	takeBasket,
+	takeSolEscrow,
 	takeTokEscrow,
 	vault1,
 	vaultO,
+	warpTime,
+	withdrawBid,
 	withdrawTokEscrow,
 } from "./litesvm-utils";
-import { bigintAmt, ll, zero } from "./utils";
+import { bigintAmt, ll, numToBytes, zero } from "./utils";
 import {
 	admin,
 	adminKp,
 	dgcAuthorityKp,
 	dragonCoin,
 	dragonCoinKp,
+	hacker,
+	hackerKp,
 	owner,
 	ownerKp,
+	pythPricefeedBTCUSD,
 	pyusdMint,
 	usdcMint,
 	usdgMint,
@@ -78,6 +115,7 @@ let escrowAtaX: PublicKey;
 let escrowAtaY: PublicKey;
 let escrowU1_1: PublicKey;
 let _escrowU2_2: PublicKey;
+let _escrowU1_2: PublicKey;
 let rawAccount: AccountInfoBytes | null;
 let _amtvBalcX: bigint;
 let prevBalcY: bigint;
@@ -93,6 +131,19 @@ let amt: bigint;
 const initDgcBalc = bigintAmt(9000, decDgc);
 const _initUsdcBalc = bigintAmt(1000, 6);
 
+const openEscrowsCk = (count: bigint) => {
+	const decoded = decodeConfigDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.openEscrows).toEqual(count);
+};
+const makerIndexU1 = findMakerIndex(user1).pda;
+//a Config that counts none of the offers below
+const hackerConfig = findPdaV1(hacker, "HackerConfig", "config").pda;
+const makerIndexCk = (openCount: bigint, nextId: bigint) => {
+	const decoded = decodeMakerIndex(svm.getAccount(makerIndexU1)?.data);
+	expect(decoded.openCount).toEqual(openCount);
+	expect(decoded.nextId).toEqual(nextId);
+};
+
 const adminBalc = svm.getBalance(admin);
 ll("admin SOL:", adminBalc);
 expect(adminBalc).toStrictEqual(initSolBalc);
@@ -156,6 +207,20 @@ test("InitConfig", () => {
 	expect(decoded.isAuthorized).toEqual(isAuthorized);
 	expect(decoded.status).toEqual(status);
 	expect(decoded.bump).toEqual(configBump);
+	expect(decoded.openEscrows).toEqual(0n);
+	expect(decoded.creator).toEqual(progOwner);
+
+	initConfig(
+		hackerKp,
+		mints,
+		hacker,
+		hacker,
+		isAuthorized,
+		status,
+		fee,
+		"Hacker Config",
+	);
+	acctExists(hackerConfig);
 });
 
 test("Set USDT Mint and ATAs", () => {
@@ -232,6 +297,7 @@ test("Make Token Escrow", () => {
 		mintY,
 		escrowPDA,
 		configPDA,
+		makerIndexU1,
 		decimalX,
 		amountX,
 		decimalY,
@@ -246,11 +312,15 @@ test("Make Token Escrow", () => {
 	expect(decoded.amountY).toEqual(amountY);
 	expect(decoded.amountX).toEqual(amountX);
 	expect(decoded.id).toEqual(id);
+	expect(decoded.config).toEqual(configPDA);
+	expect(decoded.seq).toEqual(0);
 	expect(decoded.decimalX).toEqual(decimalX);
 	expect(decoded.decimalY).toEqual(decimalY);
 	expect(decoded.bump).toEqual(escrowOut.bump);
 	ataBalCk(escrowAtaX, amountX, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 2n);
 });
 test("Take Token Escrow", () => {
 	ll("\n------== Take Token Escrow");
@@ -262,7 +332,6 @@ test("Take Token Escrow", () => {
 	decimalY = decDgc;
 	amountX = bigintAmt(326, decimalX);
 	amountY = bigintAmt(2100, decimalY);
-	id = BigInt(1);
 	escrowPDA = escrowU1_1;
 
 	signer = signerKp.publicKey;
@@ -272,25 +341,37 @@ test("Take Token Escrow", () => {
 	takerAtaY = getAta(mintY, signer);
 	escrowAtaY = getAta(mintY, escrowPDA);
 	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
-	takeTokEscrow(
-		signerKp,
-		takerAtaX,
-		takerAtaY,
-		escrowAtaX,
-		escrowAtaY,
-		mintX,
-		mintY,
-		escrowPDA,
-		configPDA,
-		decimalX,
-		amountX,
-		decimalY,
-		amountY,
-		id,
-	);
+	const take = (minX: bigint, maxY: bigint, seq: number, expectedError = "") =>
+		takeTokEscrow(
+			signerKp,
+			takerAtaX,
+			takerAtaY,
+			escrowAtaX,
+			escrowAtaY,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			decimalX,
+			minX,
+			decimalY,
+			maxY,
+			seq,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+		);
+	//taker limits: min X to receive, max Y to pay, and the offer seq
+	take(amountX + 1n, amountY, 0, "0x9a");
+	take(amountX, amountY - 1n, 0, "0x9b");
+	take(amountX, amountY, 1, "0x9c");
+	take(amountX, amountY, 0);
 	const rawAccountData = readAcct(escrowPDA);
 
-	const _decoded = decodeEscrowDev(rawAccountData);
+	const decoded = decodeEscrowDev(rawAccountData);
+	expect(decoded.amountY).toEqual(amountY);
+	expect(decoded.receivedY).toEqual(amountY);
 	ataBalCk(escrowAtaX, zero, "Escrow X");
 	ataBalCk(escrowAtaY, amountY, "Escrow Y");
 	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
@@ -322,6 +403,7 @@ test("Withdraw TokenY on Escrow", () => {
 		mintY,
 		escrowPDA,
 		configPDA,
+		makerIndexU1,
 	);
 	ataBalCk(escrowAtaX, zero, "Escrow X");
 	ataBalCk(escrowAtaY, zero, "Escrow Y");
@@ -333,6 +415,8 @@ test("Withdraw TokenY on Escrow", () => {
 	expect(rawAccount).toBeNull();
 	rawAccount = svm.getAccount(escrowPDA);
 	expect(rawAccount).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 2n);
 });
 
 test("Make & Cancel Token Escrow", () => {
@@ -347,12 +431,39 @@ test("Make & Cancel Token Escrow", () => {
 	id = BigInt(1);
 	signer = signerKp.publicKey;
 	escrowOut = findEscrow(signer, id);
-	escrowU1_1 = escrowOut.pda;
-	escrowPDA = escrowU1_1;
+	escrowPDA = escrowOut.pda;
 
 	escrowAtaX = getAta(mintX, escrowPDA);
 	makerAtaX = getAta(mintX, signer);
 	prevBalcX = ataBalc(makerAtaX, "makerAtaX");
+	//id 1 was used by the first offer
+	makeTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		undefined,
+		undefined,
+		[],
+		"0x98",
+	);
+
+	//id 0 takes the next id from the MakerIndex
+	escrowOut = findEscrow(signer, 2n);
+	_escrowU1_2 = escrowOut.pda;
+	escrowPDA = _escrowU1_2;
+	escrowAtaX = getAta(mintX, escrowPDA);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	id = 0n;
 	makeTokEscrow(
 		signerKp,
 		makerAtaX,
@@ -361,6 +472,7 @@ test("Make & Cancel Token Escrow", () => {
 		mintY,
 		escrowPDA,
 		configPDA,
+		makerIndexU1,
 		decimalX,
 		amountX,
 		decimalY,
@@ -369,7 +481,83 @@ test("Make & Cancel Token Escrow", () => {
 	);
 	ataBalCk(escrowAtaX, amountX, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
+	expect(decodeEscrowDev(readAcct(escrowPDA)).id).toEqual(2n);
+	expect(decodeEscrowDev(readAcct(escrowPDA)).config).toEqual(configPDA);
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 3n);
 
+	//amend the price with a top up, then withdraw part of X
+	const topUpX = bigintAmt(15, decimalX);
+	const withdrawX = bigintAmt(50, decimalX);
+	amountY = bigintAmt(3000, decimalY);
+	amendTokEscrow(
+		user2Kp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		escrowPDA,
+		amountY,
+		topUpX,
+		zero,
+		"0x5",
+	);
+	amendTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		escrowPDA,
+		amountY,
+		topUpX,
+		withdrawX,
+		"0x99",
+	);
+	amendTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		escrowPDA,
+		amountY,
+		topUpX,
+		zero,
+	);
+	amountX += topUpX;
+	amendTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		escrowPDA,
+		amountY,
+		zero,
+		withdrawX,
+	);
+	amountX -= withdrawX;
+	const decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.amountX).toEqual(amountX);
+	expect(decoded.amountY).toEqual(amountY);
+	expect(decoded.seq).toEqual(2);
+	ataBalCk(escrowAtaX, amountX, "Escrow");
+	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
+
+	//only the Config counted at make can be uncounted
+	cancelTokEscrow(
+		signerKp,
+		makerAtaX,
+		makerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		hackerConfig,
+		makerIndexU1,
+		undefined,
+		undefined,
+		[],
+		"0x34",
+	);
 	cancelTokEscrow(
 		signerKp,
 		makerAtaX,
@@ -380,6 +568,7 @@ test("Make & Cancel Token Escrow", () => {
 		mintY,
 		escrowPDA,
 		configPDA,
+		makerIndexU1,
 	);
 	ataBalCk(escrowAtaX, zero, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX, "user1 ");
@@ -389,4 +578,879 @@ test("Make & Cancel Token Escrow", () => {
 	expect(rawAccount).toBeNull();
 	rawAccount = svm.getAccount(escrowPDA);
 	expect(rawAccount).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 3n);
+});
+
+test("Make, Take & Close SOL Escrows", () => {
+	ll("\n------== Make, Take & Close SOL Escrows");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const taker = user2Kp;
+
+	//user1 offers USDC for SOL
+	mint = usdcMint;
+	decimals = 6;
+	amountX = bigintAmt(50, decimals);
+	amountY = bigintAmt(2, 9);
+	escrowPDA = findEscrow(signer, 3n).pda;
+	const escrowAta = getAta(mint, escrowPDA);
+	makerAtaX = getAta(mint, signer);
+	prevBalcX = ataBalc(makerAtaX, "makerAtaX");
+	makeSolEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+		false,
+		decimals,
+		amountX,
+		amountY,
+		zero,
+	);
+	let decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.id).toEqual(3n);
+	expect(decoded.mintX).toEqual(mint);
+	expect(decoded.mintY).toEqual(PublicKey.default);
+	expect(decoded.decimalY).toEqual(9);
+	ataBalCk(escrowAta, amountX, "Escrow");
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 4n);
+	const escrowRent = svm.getBalance(escrowPDA)!;
+
+	takerAtaX = getAta(mint, taker.publicKey);
+	const takerBalcX = ataBalc(takerAtaX, "takerAtaX");
+	takeSolEscrow(
+		taker,
+		takerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX,
+		amountY,
+		1,
+		"0x9c",
+	);
+	takeSolEscrow(
+		taker,
+		takerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX,
+		amountY - 1n,
+		0,
+		"0x9b",
+	);
+	takeSolEscrow(
+		taker,
+		takerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX,
+		amountY,
+		0,
+	);
+	ataBalCk(takerAtaX, takerBalcX + amountX, "Taker");
+	expect(svm.getBalance(escrowPDA)!).toEqual(escrowRent + amountY);
+
+	closeSolEscrow(
+		taker,
+		makerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+		"0x97",
+	);
+	const makerBalcBf = svm.getBalance(signer)!;
+	closeSolEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAta,
+		mint,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+	);
+	expect(svm.getBalance(signer)! - makerBalcBf).toBeGreaterThan(amountY);
+	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
+	expect(svm.getAccount(escrowAta)).toBeNull();
+	expect(svm.getAccount(escrowPDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 4n);
+
+	//user1 offers SOL for DragonCoin
+	mint = dragonCoin;
+	decimals = decDgc;
+	amountX = bigintAmt(3, 9);
+	amountY = bigintAmt(700, decimals);
+	escrowPDA = findEscrow(signer, 4n).pda;
+	const escrowAtaDgc = getAta(mint, escrowPDA);
+	makerAtaY = getAta(mint, signer);
+	makeSolEscrow(
+		signerKp,
+		makerAtaY,
+		escrowAtaDgc,
+		mint,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+		true,
+		decimals,
+		amountX,
+		amountY,
+		zero,
+	);
+	decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.mintX).toEqual(PublicKey.default);
+	expect(decoded.mintY).toEqual(mint);
+	expect(svm.getBalance(escrowPDA)!).toEqual(escrowRent + amountX);
+
+	takerAtaY = getAta(mint, taker.publicKey);
+	prevBalcY = ataBalc(takerAtaY, "takerAtaY");
+	takeSolEscrow(
+		taker,
+		takerAtaY,
+		escrowAtaDgc,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX + 1n,
+		amountY,
+		0,
+		"0x9a",
+	);
+	const takerSolBf = svm.getBalance(taker.publicKey)!;
+	takeSolEscrow(
+		taker,
+		takerAtaY,
+		escrowAtaDgc,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX,
+		amountY,
+		0,
+	);
+	expect(svm.getBalance(taker.publicKey)! - takerSolBf).toBeLessThanOrEqual(
+		amountX,
+	);
+	expect(svm.getBalance(escrowPDA)!).toEqual(escrowRent);
+	ataBalCk(takerAtaY, prevBalcY - amountY, "Taker");
+	//the SOL is gone, so a second take fails
+	takeSolEscrow(
+		taker,
+		takerAtaY,
+		escrowAtaDgc,
+		mint,
+		escrowPDA,
+		configPDA,
+		amountX,
+		amountY,
+		0,
+		"0x4d",
+	);
+
+	closeSolEscrow(
+		signerKp,
+		makerAtaY,
+		escrowAtaDgc,
+		mint,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+	);
+	ataBalCk(makerAtaY, amountY, "user1 ");
+	expect(svm.getAccount(escrowPDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 5n);
+});
+
+test("Make, Take & Close Basket", () => {
+	ll("\n------== Make, Take & Close Basket");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const taker = user2Kp;
+	setAtaCheck(usdtMint, signer, bigintAmt(500, 6), "User1 USDT");
+	setAtaCheck(pyusdMint, taker.publicKey, bigintAmt(500, 6), "User2 PYUSD");
+	const legsX = [
+		{ mint: usdcMint, amount: bigintAmt(10, 6), decimals: 6 },
+		{ mint: usdtMint, amount: bigintAmt(20, 6), decimals: 6 },
+	];
+	const legsY = [
+		{ mint: dragonCoin, amount: bigintAmt(100, decDgc), decimals: decDgc },
+		{ mint: pyusdMint, amount: bigintAmt(5, 6), decimals: 6 },
+	];
+	const mints = [...legsX, ...legsY].map((leg) => leg.mint);
+	const basketPDA = findBasket(signer, 5n).pda;
+	const balcOf = (user: PublicKey) =>
+		mints.map((mint) => ataBalc(getAta(mint, user), "balc", false));
+	const makerBf = balcOf(signer);
+	const takerBf = balcOf(taker.publicKey);
+
+	//every mint must be distinct
+	makeBasket(
+		signerKp,
+		basketPDA,
+		configPDA,
+		makerIndexU1,
+		legsX,
+		[{ ...legsY[0]!, mint: usdcMint, decimals: 6 }],
+		zero,
+		"0x9f",
+	);
+	makeBasket(signerKp, basketPDA, configPDA, makerIndexU1, legsX, legsY, zero);
+	const decoded = decodeBasket(readAcct(basketPDA));
+	expect(decoded.maker.toString()).toEqual(signer.toBase58());
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	expect(decoded.id).toEqual(5n);
+	expect(decoded.taken).toEqual(false);
+	expect(decoded.legsX.map((leg) => leg.amount)).toEqual(
+		legsX.map((leg) => leg.amount),
+	);
+	expect(decoded.legsY.map((leg) => leg.amount)).toEqual(
+		legsY.map((leg) => leg.amount),
+	);
+	for (const leg of legsX) {
+		ataBalCk(getAta(leg.mint, basketPDA), leg.amount, "Basket");
+	}
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 6n);
+
+	closeBasket(taker, basketPDA, configPDA, makerIndexU1, mints, "0x97");
+	takeBasket(taker, basketPDA, configPDA, [...mints].reverse(), "0x9f");
+	takeBasket(taker, basketPDA, configPDA, mints);
+	expect(decodeBasket(readAcct(basketPDA)).taken).toEqual(true);
+	const takerAf = balcOf(taker.publicKey);
+	legsX.forEach((leg, i) => {
+		expect(takerAf[i]).toEqual(takerBf[i]! + leg.amount);
+	});
+	legsY.forEach((leg, i) => {
+		const j = legsX.length + i;
+		expect(takerAf[j]).toEqual(takerBf[j]! - leg.amount);
+	});
+	takeBasket(taker, basketPDA, configPDA, mints, "0xa0");
+
+	closeBasket(signerKp, basketPDA, hackerConfig, makerIndexU1, mints, "0x34");
+	closeBasket(signerKp, basketPDA, configPDA, makerIndexU1, mints);
+	const makerAf = balcOf(signer);
+	legsX.forEach((leg, i) => {
+		expect(makerAf[i]).toEqual(makerBf[i]! - leg.amount);
+	});
+	legsY.forEach((leg, i) => {
+		const j = legsX.length + i;
+		expect(makerAf[j]).toEqual(makerBf[j]! + leg.amount);
+	});
+	for (const mint of mints) {
+		expect(svm.getAccount(getAta(mint, basketPDA))).toBeNull();
+	}
+	expect(svm.getAccount(basketPDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 6n);
+});
+test("Make, Take & Withdraw NFT Escrow", () => {
+	ll("\n------== Make, Take & Withdraw NFT Escrow");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const taker = user2Kp;
+	const nftMint = PublicKey.unique();
+	setLgcMint(nftMint, 0, 1n);
+	setAtaCheck(nftMint, signer, 1n, "User1 NFT");
+	setAtaCheck(usdtMint, taker.publicKey, bigintAmt(500, 6), "User2 USDT");
+	mintX = nftMint;
+	mintY = usdtMint;
+	amountY = bigintAmt(50, 6);
+	escrowPDA = findEscrow(signer, 6n).pda;
+	escrowAtaX = getAta(mintX, escrowPDA);
+	makerAtaX = getAta(mintX, signer);
+	const make = (
+		mint: PublicKey,
+		decimals: number,
+		mode: number,
+		expectedError = "",
+	) =>
+		makeTokEscrow(
+			signerKp,
+			getAta(mint, signer),
+			getAta(mint, escrowPDA),
+			mint,
+			mintY,
+			escrowPDA,
+			configPDA,
+			makerIndexU1,
+			decimals,
+			1n,
+			6,
+			amountY,
+			zero,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+			mode,
+		);
+	//USDC is not an NFT
+	make(usdcMint, 6, 1, "0xa1");
+	//collection offers need Token2022
+	make(nftMint, 0, 3, "0xa2");
+	make(nftMint, 0, 1);
+	const decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.mode).toEqual(1);
+	expect(decoded.amountX).toEqual(1n);
+	ataBalCk(escrowAtaX, 1n, "Escrow NFT");
+	makerIndexCk(1n, 7n);
+
+	takerAtaX = getAta(mintX, taker.publicKey);
+	takerAtaY = getAta(mintY, taker.publicKey);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	takeTokEscrow(
+		taker,
+		takerAtaX,
+		takerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		0,
+		1n,
+		6,
+		amountY,
+		0,
+	);
+	ataBalCk(takerAtaX, 1n, "Taker NFT");
+	ataBalCk(escrowAtaY, amountY, "Escrow Y");
+
+	makerAtaY = getAta(mintY, signer);
+	prevBalcY = ataBalc(makerAtaY, "makerAtaY");
+	withdrawTokEscrow(
+		signerKp,
+		makerAtaX,
+		makerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+	);
+	ataBalCk(makerAtaY, prevBalcY + amountY, "user1 Y");
+	expect(svm.getAccount(escrowPDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 7n);
+});
+test("Make, Take & Withdraw Dutch Escrow", () => {
+	ll("\n------== Make, Take & Withdraw Dutch Escrow");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const taker = user2Kp;
+	mintX = usdcMint;
+	mintY = usdtMint;
+	amountX = bigintAmt(100, 6);
+	amountY = bigintAmt(100, 6);
+	const now = svm.getClock().unixTimestamp;
+	const dutch = {
+		endAmountY: bigintAmt(50, 6),
+		startTime: now + 100n,
+		endTime: now + 1100n,
+	};
+	escrowPDA = findEscrow(signer, 7n).pda;
+	makerAtaX = getAta(mintX, signer);
+	escrowAtaX = getAta(mintX, escrowPDA);
+	const make = (endAmountY: bigint, expectedError = "") =>
+		makeTokEscrow(
+			signerKp,
+			makerAtaX,
+			escrowAtaX,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			makerIndexU1,
+			6,
+			amountX,
+			6,
+			amountY,
+			zero,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+			4,
+			{ ...dutch, endAmountY },
+		);
+	//the price must decay
+	make(amountY, "0xa3");
+	make(dutch.endAmountY);
+	const decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.mode).toEqual(4);
+	expect(decoded.endAmountY).toEqual(dutch.endAmountY);
+	expect(decoded.startTime).toEqual(dutch.startTime);
+	expect(decoded.endTime).toEqual(dutch.endTime);
+
+	takerAtaX = getAta(mintX, taker.publicKey);
+	takerAtaY = getAta(mintY, taker.publicKey);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	const take = (maxY: bigint, expectedError = "") =>
+		takeTokEscrow(
+			taker,
+			takerAtaX,
+			takerAtaY,
+			escrowAtaX,
+			escrowAtaY,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			6,
+			amountX,
+			6,
+			maxY,
+			0,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+		);
+	//before startTime the start price holds
+	take(dutch.endAmountY, "0x9b");
+	//halfway through the decay: 100 - (100 - 50) * 500 / 1000
+	warpTime(600);
+	const priceY = bigintAmt(75, 6);
+	take(priceY - 1n, "0x9b");
+	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
+	take(priceY);
+	const taken = decodeEscrowDev(readAcct(escrowPDA));
+	expect(taken.amountY).toEqual(decoded.amountY);
+	expect(taken.receivedY).toEqual(priceY);
+	ataBalCk(escrowAtaY, priceY, "Escrow Y");
+	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
+
+	makerAtaY = getAta(mintY, signer);
+	prevBalcY = ataBalc(makerAtaY, "makerAtaY");
+	withdrawTokEscrow(
+		signerKp,
+		makerAtaX,
+		makerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		makerIndexU1,
+	);
+	ataBalCk(makerAtaY, prevBalcY + priceY, "user1 Y");
+	expect(svm.getAccount(escrowPDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 8n);
+});
+test("Make, Bid & Settle Auction", () => {
+	ll("\n------== Make, Bid & Settle Auction");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const bidder1 = user2Kp;
+	const bidder2 = adminKp;
+	setAtaCheck(usdtMint, bidder1.publicKey, bigintAmt(100, 6), "User2 USDT");
+	setAtaCheck(usdtMint, bidder2.publicKey, bigintAmt(100, 6), "Admin USDT");
+	mintX = usdcMint;
+	mintY = usdtMint;
+	amountX = bigintAmt(10, 6);
+	const reserveY = bigintAmt(20, 6);
+	const now = svm.getClock().unixTimestamp;
+	const endTime = now + 1000n;
+	const auctionPDA = findAuction(signer, 8n).pda;
+	const make = (endTime: bigint, expectedError = "") =>
+		makeAuction(
+			signerKp,
+			mintX,
+			mintY,
+			auctionPDA,
+			configPDA,
+			makerIndexU1,
+			6,
+			amountX,
+			6,
+			reserveY,
+			endTime,
+			zero,
+			expectedError,
+		);
+	make(now, "0xa6");
+	make(endTime);
+	let decoded = decodeAuction(readAcct(auctionPDA));
+	expect(decoded.amountX).toEqual(amountX);
+	expect(decoded.reserveY).toEqual(reserveY);
+	expect(decoded.endTime).toEqual(endTime);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	ataBalCk(getAta(mintX, auctionPDA), amountX, "Auction X");
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 9n);
+
+	const usdtOf = (user: PublicKey) =>
+		ataBalc(getAta(mintY, user), "USDT", false);
+	const bidder1Bf = usdtOf(bidder1.publicKey);
+	//below the reserve
+	bidAuction(bidder1, mintY, auctionPDA, reserveY - 1n, "0xa5");
+	bidAuction(bidder1, mintY, auctionPDA, reserveY);
+	const bid1PDA = findBid(auctionPDA, bidder1.publicKey).pda;
+	ataBalCk(getAta(mintY, bid1PDA), reserveY, "Bid1 Y");
+	expect(usdtOf(bidder1.publicKey)).toEqual(bidder1Bf - reserveY);
+
+	const highBid = bigintAmt(30, 6);
+	//the highest bid cannot be withdrawn
+	withdrawBid(bidder1, mintY, auctionPDA, 6, "0xa5");
+	//must beat the highest bid
+	bidAuction(bidder2, mintY, auctionPDA, reserveY, "0xa5");
+	bidAuction(bidder2, mintY, auctionPDA, highBid);
+	decoded = decodeAuction(readAcct(auctionPDA));
+	expect(decoded.highBidder.toString()).toEqual(bidder2.publicKey.toBase58());
+	expect(decoded.highBid).toEqual(highBid);
+	//the outbid bid waits in its Bid PDA to be withdrawn
+	ataBalCk(getAta(mintY, bid1PDA), reserveY, "Bid1 Y");
+	expect(usdtOf(bidder1.publicKey)).toEqual(bidder1Bf - reserveY);
+	withdrawBid(bidder2, mintY, auctionPDA, 6, "0xa5");
+
+	//the outbid bidder bids again by adding to its Bid PDA, and the total must beat the highest bid
+	bidAuction(bidder1, mintY, auctionPDA, highBid - reserveY, "0xa5");
+	const bid1 = reserveY + bigintAmt(15, 6);
+	bidAuction(bidder1, mintY, auctionPDA, bid1 - reserveY);
+	ataBalCk(getAta(mintY, bid1PDA), bid1, "Bid1 Y");
+	decoded = decodeAuction(readAcct(auctionPDA));
+	expect(decoded.highBidder.toString()).toEqual(bidder1.publicKey.toBase58());
+	expect(decoded.highBid).toEqual(bid1);
+	const winBid = highBid + bigintAmt(10, 6);
+	bidAuction(bidder2, mintY, auctionPDA, winBid - highBid);
+	expect(decodeAuction(readAcct(auctionPDA)).highBid).toEqual(winBid);
+
+	const settle = (
+		winner: PublicKey,
+		expectedError = "",
+		config = configPDA,
+	) =>
+		settleAuction(
+			bidder1,
+			signer,
+			winner,
+			mintX,
+			mintY,
+			auctionPDA,
+			config,
+			makerIndexU1,
+			expectedError,
+		);
+	settle(bidder2.publicKey, "0xa7");
+	warpTime(1000);
+	bidAuction(bidder1, mintY, auctionPDA, highBid * 2n, "0xa6");
+	settle(bidder1.publicKey, "0xa5");
+	settle(bidder2.publicKey, "0x34", hackerConfig);
+	const makerBf = usdtOf(signer);
+	settle(bidder2.publicKey);
+	ataBalCk(getAta(mintX, bidder2.publicKey), amountX, "Winner X");
+	expect(usdtOf(signer)).toEqual(makerBf + winBid);
+	expect(svm.getAccount(auctionPDA)).toBeNull();
+	const bid2PDA = findBid(auctionPDA, bidder2.publicKey).pda;
+	expect(svm.getAccount(bid2PDA)).toBeNull();
+	//the outbid bidder withdraws after the Auction is closed
+	withdrawBid(bidder1, mintY, auctionPDA, 6);
+	expect(usdtOf(bidder1.publicKey)).toEqual(bidder1Bf);
+	expect(svm.getAccount(bid1PDA)).toBeNull();
+	openEscrowsCk(0n);
+	makerIndexCk(0n, 9n);
+});
+test("Make & Take Oracle Trigger Escrow", () => {
+	ll("\n------== Make & Take Oracle Trigger Escrow");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	const taker = user2Kp;
+	mintX = usdcMint;
+	mintY = usdtMint;
+	amountX = bigintAmt(10, 6);
+	amountY = bigintAmt(10, 6);
+	const pricefeed = pythPricefeedBTCUSD;
+	setPriceFeedPda(pricefeed);
+	//PriceUpdateV2: price i64 at 73, exponent i32 at 89, publish_time i64 at 93
+	const feed = Buffer.from(pricefeed.json.account.data[0]!, "base64");
+	const publishTime = feed.readBigInt64LE(93);
+	const btcPrice = BigInt(
+		Math.floor(Number(feed.readBigInt64LE(73)) * 10 ** feed.readInt32LE(89)),
+	);
+	ll("btcPrice:", btcPrice, ", publishTime:", publishTime);
+	setTime(publishTime);
+
+	escrowPDA = findEscrow(signer, 9n).pda;
+	makerAtaX = getAta(mintX, signer);
+	escrowAtaX = getAta(mintX, escrowPDA);
+	//take when BTC rises above 1.1x the current price
+	const trigger = {
+		oracleVendor: pricefeed.vendor,
+		triggerAbove: true,
+		triggerPrice: (btcPrice * 11n) / 10n,
+		feedId: pricefeed.feedId,
+	};
+	const make = (tg: typeof trigger, expectedError = "") =>
+		makeTokEscrow(
+			signerKp,
+			makerAtaX,
+			escrowAtaX,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			makerIndexU1,
+			6,
+			amountX,
+			6,
+			amountY,
+			zero,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+			8,
+			undefined,
+			tg,
+		);
+	//an unknown oracle or an empty feed id could never be read at take
+	make({ ...trigger, oracleVendor: 9 }, "0x6e");
+	make({ ...trigger, feedId: "0".repeat(64) }, "0xbe");
+	make(trigger);
+	const decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.mode).toEqual(8);
+	expect(decoded.oracleVendor).toEqual(pricefeed.vendor);
+	expect(decoded.triggerAbove).toEqual(true);
+	expect(decoded.triggerPrice).toEqual(trigger.triggerPrice);
+
+	takerAtaX = getAta(mintX, taker.publicKey);
+	takerAtaY = getAta(mintY, taker.publicKey);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	const oracle = {
+		pubkey: pricefeed.addr,
+		isSigner: false,
+		isWritable: false,
+	};
+	const take = (expectedError = "") =>
+		takeTokEscrow(
+			taker,
+			takerAtaX,
+			takerAtaY,
+			escrowAtaX,
+			escrowAtaY,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			6,
+			amountX,
+			6,
+			amountY,
+			0,
+			undefined,
+			undefined,
+			[oracle],
+			expectedError,
+		);
+	//BTC has not risen to the trigger price
+	take("0xa9");
+	//a stale price is rejected
+	setTime(publishTime + 61n);
+	take("0x70");
+
+	//BTC rises 20%
+	setTime(publishTime);
+	feed.writeBigInt64LE((feed.readBigInt64LE(73) * 12n) / 10n, 73);
+	const acct = svm.getAccount(pricefeed.addr)!;
+	svm.setAccount(pricefeed.addr, { ...acct, data: new Uint8Array(feed) });
+	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
+	take();
+	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
+	ataBalCk(escrowAtaY, amountY, "Escrow Y");
+});
+test("Make, Settle & Close Milestone Escrow", () => {
+	ll("\n------== Make, Settle & Close Milestone Escrow");
+	const payerKp = user1Kp;
+	const payer = payerKp.publicKey;
+	const payeeKp = user2Kp;
+	const payee = payeeKp.publicKey;
+	const arbiterKp = adminKp;
+	const arbiter = arbiterKp.publicKey;
+	mint = usdcMint;
+	const amounts = [bigintAmt(10, 6), bigintAmt(20, 6), bigintAmt(30, 6)];
+	const deadline = svm.getClock().unixTimestamp + 1000n;
+	const milestonePDA = findMilestone(payer, 10n).pda;
+	openEscrowsCk(1n);
+	const make = (arbiter: PublicKey, expectedError = "") =>
+		makeMilestone(
+			payerKp,
+			mint,
+			payee,
+			arbiter,
+			milestonePDA,
+			configPDA,
+			makerIndexU1,
+			6,
+			deadline,
+			amounts,
+			zero,
+			expectedError,
+		);
+	//the arbiter must be neither party
+	make(payee, "0xac");
+	make(arbiter);
+	const decoded = decodeMilestone(readAcct(milestonePDA));
+	expect(decoded.count).toEqual(3);
+	expect(decoded.amounts.slice(0, 3)).toEqual(amounts);
+	expect(decoded.deadline).toEqual(deadline);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	ataBalCk(getAta(mint, milestonePDA), bigintAmt(60, 6), "Milestone ATA");
+	openEscrowsCk(2n);
+	makerIndexCk(2n, 11n);
+
+	const usdcOf = (user: PublicKey) =>
+		ataBalc(getAta(mint, user), "USDC", false);
+	const settle = (
+		signerKp: Keypair,
+		index: number,
+		release: boolean,
+		expectedError = "",
+	) =>
+		settleMilestone(
+			signerKp,
+			payer,
+			release ? payee : payer,
+			mint,
+			milestonePDA,
+			index,
+			release,
+			expectedError,
+		);
+	//the payee cannot release to itself
+	settle(payeeKp, 0, true, "0xac");
+	const payeeBf = usdcOf(payee);
+	settle(payerKp, 0, true);
+	expect(usdcOf(payee)).toEqual(payeeBf + amounts[0]!);
+	settle(payerKp, 0, true, "0xab");
+
+	//a disputed milestone waits for the arbiter
+	disputeMilestone(arbiterKp, milestonePDA, 1, "0xac");
+	disputeMilestone(payeeKp, milestonePDA, 1);
+	expect(decodeMilestone(readAcct(milestonePDA)).disputed).toEqual(2);
+	settle(payerKp, 1, true, "0xac");
+	const payerBf = usdcOf(payer);
+	settle(arbiterKp, 1, false);
+	expect(usdcOf(payer)).toEqual(payerBf + amounts[1]!);
+	const settled = decodeMilestone(readAcct(milestonePDA));
+	expect(settled.released).toEqual(1);
+	expect(settled.refunded).toEqual(2);
+	expect(settled.disputed).toEqual(0);
+
+	const close = (expectedError = "", config = configPDA) =>
+		closeMilestone(
+			payerKp,
+			mint,
+			milestonePDA,
+			config,
+			makerIndexU1,
+			expectedError,
+		);
+	close("0xad");
+	//after the deadline, the rest reverts to the payer
+	warpTime(1000);
+	settle(arbiterKp, 2, true, "0xad");
+	disputeMilestone(payeeKp, milestonePDA, 2, "0xad");
+	close("0x34", hackerConfig);
+	close();
+	expect(usdcOf(payer)).toEqual(payerBf + amounts[1]! + amounts[2]!);
+	expect(svm.getAccount(milestonePDA)).toBeNull();
+	expect(svm.getAccount(getAta(mint, milestonePDA))).toBeNull();
+	openEscrowsCk(1n);
+	makerIndexCk(1n, 11n);
+});
+test("Migrate & Cancel legacy Escrow", () => {
+	ll("\n------== Migrate & Cancel legacy Escrow");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	mintX = usdcMint;
+	mintY = usdtMint;
+	amountX = bigintAmt(10, 6);
+	amountY = bigintAmt(20, 6);
+	//an id above next_id, picked before MakerIndex existed
+	id = 100n;
+	escrowOut = findEscrow(signer, id);
+	escrowPDA = escrowOut.pda;
+	//the layout made before seq: maker, mint_x, mint_y, amount_x, amount_y, id, decimal_x, decimal_y, bump
+	const legacy = Buffer.concat([
+		signer.toBuffer(),
+		mintX.toBuffer(),
+		mintY.toBuffer(),
+		Buffer.copyBytesFrom(numToBytes(amountX)),
+		Buffer.copyBytesFrom(numToBytes(amountY)),
+		Buffer.copyBytesFrom(numToBytes(id)),
+		Buffer.from([6, 6, escrowOut.bump]),
+	]);
+	expect(legacy.byteLength).toEqual(123);
+	svm.setAccount(escrowPDA, {
+		lamports: Number(svm.minimumBalanceForRentExemption(BigInt(123))),
+		data: Uint8Array.from(legacy),
+		owner: vaultProgAddr,
+		executable: false,
+	});
+	escrowAtaX = setAta(mintX, escrowPDA, amountX).ata;
+	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaX = getAta(mintX, signer);
+	makerAtaY = getAta(mintY, signer);
+	const cancel = (expectedError = "") =>
+		cancelTokEscrow(
+			signerKp,
+			makerAtaX,
+			makerAtaY,
+			escrowAtaX,
+			escrowAtaY,
+			mintX,
+			mintY,
+			escrowPDA,
+			configPDA,
+			makerIndexU1,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+		);
+	//the legacy length cannot be read until migrated
+	cancel("0x40");
+	escrowMigrate(user2Kp, escrowPDA, configPDA, makerIndexU1, "0x5");
+	escrowMigrate(signerKp, escrowPDA, configPDA, makerIndexU1);
+	const decoded = decodeEscrowDev(readAcct(escrowPDA));
+	expect(decoded.maker).toEqual(signer);
+	expect(decoded.amountX).toEqual(amountX);
+	expect(decoded.amountY).toEqual(amountY);
+	expect(decoded.id).toEqual(id);
+	expect(decoded.config).toEqual(configPDA);
+	expect(decoded.seq).toEqual(0);
+	expect(decoded.receivedY).toEqual(zero);
+	expect(decoded.decimalX).toEqual(6);
+	expect(decoded.decimalY).toEqual(6);
+	expect(decoded.bump).toEqual(escrowOut.bump);
+	openEscrowsCk(2n);
+	makerIndexCk(2n, id + 1n);
+	//only legacy lengths can be migrated
+	escrowMigrate(signerKp, escrowPDA, configPDA, makerIndexU1, "0x40");
+
+	prevBalcX = ataBalc(makerAtaX, "makerAtaX");
+	cancel();
+	ataBalCk(makerAtaX, prevBalcX + amountX, "user1 X");
+	expect(svm.getAccount(escrowPDA)).toBeNull();
+	openEscrowsCk(1n);
+	makerIndexCk(1n, id + 1n);
 });
@@ -1,4 +1,5 @@
 import { expect } from "bun:test";
+import { createHash } from "node:crypto";
 import {
 	ACCOUNT_SIZE,
 	AccountLayout,
@@ -6,9 +7,11 @@ import {
 	getAssociatedTokenAddressSync,
 	MINT_SIZE,
 	MintLayout,
+	TOKEN_2022_PROGRAM_ID,
 	TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
+	type AccountMeta,
 	type Keypair,
 	LAMPORTS_PER_SOL,
 	PublicKey,
@@ -112,6 +115,78 @@ export const findEscrow = (
 	ll(`Escrow ${id}: ${pda.toBase58()}, bump: ${bump}`);
 	return { pda, bump };
 };
+export const findMakerIndex = (
+	maker: PublicKey,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("maker_index"), maker.toBuffer()],
+		progAddr,
+	);
+	ll(`MakerIndex: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const findBasket = (
+	maker: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("basket"),
+			maker.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Basket ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const findAuction = (
+	maker: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("auction"),
+			maker.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Auction ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const findBid = (
+	auction: PublicKey,
+	bidder: PublicKey,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("bid"), auction.toBuffer(), bidder.toBuffer()],
+		progAddr,
+	);
+	ll(`Bid: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
+export const findMilestone = (
+	payer: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("milestone"),
+			payer.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Milestone ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
 
 export const getSimpleAcct = (programId: PublicKey): PublicKey => {
 	const [publickey, _bump] = PublicKey.findProgramAddressSync(
@@ -183,16 +258,19 @@ export const initConfig = (
 	// 	if (mint === undefined) throw new Error("");
 	// }
 
+	//the Config and its vault are derived from the progOwner
+	const config = findPdaV1(progOwner, "ConfigPDA", "config").pda;
+	const vault = findPdaV1(progOwner, "Vault").pda;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: config, isSigner: false, isWritable: true },
 			{ pubkey: mints[0], isSigner: false, isWritable: false },
 			{ pubkey: mints[1], isSigner: false, isWritable: false },
 			{ pubkey: mints[2], isSigner: false, isWritable: false },
 			{ pubkey: mints[3], isSigner: false, isWritable: false },
-			{ pubkey: vaultO, isSigner: false, isWritable: false },
+			{ pubkey: vault, isSigner: false, isWritable: false },
 			{ pubkey: progOwner, isSigner: false, isWritable: false },
 			{ pubkey: progAdmin, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
@@ -210,6 +288,7 @@ export const updateConfig = (
 	numU32: number,
 	numU64: bigint,
 	//str: string,
+	expectedError = "",
 ) => {
 	const disc = 13;
 	const progAddr = vaultProgAddr;
@@ -231,12 +310,13 @@ export const updateConfig = (
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [signer], progAddr);
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
 };
 export const configResize = (
 	signer: Keypair,
 	configPDA: PublicKey,
 	newSize: bigint,
+	expectedError = "",
 ) => {
 	const disc = 19;
 	const progAddr = vaultProgAddr;
@@ -254,7 +334,56 @@ export const configResize = (
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [signer], progAddr);
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
+};
+export const configMigrate = (
+	signer: Keypair,
+	configPDA: PublicKey,
+	creator: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 69;
+	const progAddr = vaultProgAddr;
+	ll("configPDA:", configPDA.toBase58());
+	ll("creator:", creator.toBase58());
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: creator, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
+};
+export const escrowMigrate = (
+	maker: Keypair,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 70;
+	const progAddr = vaultProgAddr;
+	ll("escrowPDA:", escrowPDA.toBase58());
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
 };
 export const updateConfig2 = (
 	signer: Keypair,
@@ -291,10 +420,28 @@ export const updateConfig2 = (
 	});
 	sendTxns(blockhash, [ix], [signer], progAddr);
 };
+//[mint, vault_ata] pairs for each config mint
+export const vaultMintAtas = (
+	mints: PublicKey[],
+	vault = vaultO,
+	tokenProg = TOKEN_PROGRAM_ID,
+): AccountMeta[] =>
+	mints.flatMap((mint) => [
+		{ pubkey: mint, isSigner: false, isWritable: false },
+		{
+			pubkey: getAssociatedTokenAddressSync(mint, vault, true, tokenProg),
+			isSigner: false,
+			isWritable: false,
+		},
+	]);
 export const closeConfig = (
 	signer: Keypair,
 	configPDA: PublicKey,
 	dest: PublicKey,
+	mints: PublicKey[],
+	force = false,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
 ) => {
 	const disc = 14;
 	const progAddr = vaultProgAddr;
@@ -306,18 +453,20 @@ export const closeConfig = (
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: dest, isSigner: false, isWritable: false },
+			{ pubkey: dest, isSigner: false, isWritable: true },
+			...vaultMintAtas(mints, vaultO, tokenProg),
 		],
 		programId: progAddr,
-		data: Buffer.from([disc]),
+		data: Buffer.from([disc, boolToByte(force)]),
 	});
-	sendTxns(blockhash, [ix], [signer], progAddr);
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
 };
 
 export const depositSol = (
 	signer: Keypair,
 	userVault: PublicKey,
 	amount: bigint,
+	yieldPool?: PublicKey,
 ) => {
 	const disc = 0;
 	const progAddr = vaultProgAddr;
@@ -329,6 +478,7 @@ export const depositSol = (
 			{ pubkey: userVault, isSigner: false, isWritable: true },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...yieldPoolMeta(yieldPool),
 		],
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
@@ -340,6 +490,7 @@ export const withdrawSol = (
 	vaultPdaX: PublicKey,
 	amount: bigint,
 	expectedError = "",
+	yieldPool?: PublicKey,
 ) => {
 	const disc = 1;
 	const progAddr = vaultProgAddr;
@@ -350,449 +501,2477 @@ export const withdrawSol = (
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
 			{ pubkey: vaultPdaX, isSigner: false, isWritable: true },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			{
+				pubkey: findVaultLock(signer.publicKey).pda,
+				isSigner: false,
+				isWritable: false,
+			},
+			...yieldPoolMeta(yieldPool),
 		],
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
 	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
 };
-export const lgcInitMint = (
-	signer: Keypair,
-	mintKp: Keypair,
-	mintAuthority: PublicKey,
-	freezeAuthorityOpt: PublicKey,
-	decimals: number,
-	//argData: Uint8Array<ArrayBufferLike>,
-	tokenProg = TOKEN_PROGRAM_ID,
+//optional trailing YieldPool and its Config PDA for vault yield syncs
+const yieldPoolMeta = (yieldPool?: PublicKey): AccountMeta[] =>
+	yieldPool
+		? [
+				{ pubkey: yieldPool, isSigner: false, isWritable: true },
+				{ pubkey: configPDA, isSigner: false, isWritable: false },
+			]
+		: [];
+export const yieldFund = (
+	admin: Keypair,
+	configPda: PublicKey,
+	yieldPool: PublicKey,
+	rateBps: number,
+	amount: bigint,
+	expectedError = "",
 ) => {
-	const disc = 2;
+	const disc = 26;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimals);
+	const argData = [...numToBytes(rateBps, 16), ...numToBytes(amount)];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintAuthority, isSigner: false, isWritable: false },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
-			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
+			{ pubkey: admin.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: yieldPool, isSigner: false, isWritable: true },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, decimals]),
+		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [signer, mintKp], progAddr);
+	sendTxns(blockhash, [ix], [admin], progAddr, expectedError);
 };
-export const tok22InitMint = (
-	signer: Keypair,
-	mintKp: Keypair,
-	mintAuthority: PublicKey,
-	freezeAuthorityOpt: PublicKey,
-	decimals: number,
-	//argData: Uint8Array<ArrayBufferLike>,
-	tokenProg = TOKEN_PROGRAM_ID,
+export const claimYield = (
+	user: Keypair,
+	userVault: PublicKey,
+	yieldPool: PublicKey,
+	expectedError = "",
 ) => {
-	const disc = 2;
+	const disc = 27;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimals);
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintAuthority, isSigner: false, isWritable: false },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
-			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
-			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: user.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: userVault, isSigner: false, isWritable: true },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...yieldPoolMeta(yieldPool),
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, decimals]),
+		data: Buffer.from([disc]),
 	});
-	sendTxns(blockhash, [ix], [signer, mintKp], progAddr);
+	sendTxns(blockhash, [ix], [user], progAddr, expectedError);
 };
-export const lgcInitAta = (
-	signer: Keypair,
-	toWallet: PublicKey,
-	mint: PublicKey,
-	ata: PublicKey,
-	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
+//SOL locks use the zero address as mint
+export const findVaultLock = (
+	user: PublicKey,
+	mint = PublicKey.default,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("lock"), user.toBuffer(), mint.toBuffer()],
+		progAddr,
+	);
+	ll(`VaultLock: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const lockSol = (
+	funder: Keypair,
+	user: Keypair,
+	userVault: PublicKey,
+	lock: PublicKey,
+	amount: bigint,
+	cliffAt: bigint,
+	endAt: bigint,
+	expectedError = "",
+	yieldPool?: PublicKey,
 ) => {
-	const disc = 3;
+	const disc = 28;
 	const progAddr = vaultProgAddr;
+	checkBigint(amount, "amount");
+	const argData = [
+		...numToBytes(amount),
+		...numToBytes(cliffAt, 32),
+		...numToBytes(endAt, 32),
+	];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: toWallet, isSigner: false, isWritable: false },
-			{ pubkey: mint, isSigner: false, isWritable: false },
-			{ pubkey: ata, isSigner: false, isWritable: true },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: funder.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: user.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: userVault, isSigner: false, isWritable: true },
+			{ pubkey: lock, isSigner: false, isWritable: true },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...yieldPoolMeta(yieldPool),
 		],
 		programId: progAddr,
-		data: Buffer.from([disc]),
+		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [signer], progAddr);
+	sendTxns(blockhash, [ix], [funder, user], progAddr, expectedError);
 };
-export const lgcMintToken = (
-	mintAuthority: Keypair,
-	toWallet: PublicKey,
+export const lockTok = (
+	funder: Keypair,
+	fromAta: PublicKey,
+	vaultAta: PublicKey,
+	userVault: PublicKey,
+	lock: PublicKey,
+	user: Keypair,
 	mint: PublicKey,
-	ata: PublicKey,
 	decimals: number,
 	amount: bigint,
+	cliffAt: bigint,
+	endAt: bigint,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
 ) => {
-	const disc = 4;
+	const disc = 29;
 	const progAddr = vaultProgAddr;
 	checkDecimals(decimals);
 	checkBigint(amount, "amount");
-	const argData = [decimals, ...numToBytes(amount)];
+	const argData = [
+		decimals,
+		...numToBytes(amount),
+		...numToBytes(cliffAt, 32),
+		...numToBytes(endAt, 32),
+	];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: mintAuthority.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: toWallet, isSigner: false, isWritable: false },
-			{ pubkey: mint, isSigner: false, isWritable: true },
-			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: funder.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: vaultAta, isSigner: false, isWritable: true },
+			{ pubkey: userVault, isSigner: false, isWritable: false },
+			{ pubkey: lock, isSigner: false, isWritable: true },
+			{ pubkey: user.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
 		],
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [mintAuthority], progAddr);
+	sendTxns(blockhash, [ix], [funder, user], progAddr, expectedError);
 };
-
-export const lgcDeposit = (
-	userSigner: Keypair,
-	fromAta: PublicKey,
-	toAta: PublicKey,
-	userVault: PublicKey,
+export const unlockSol = (
+	user: Keypair,
+	lock: PublicKey,
+	funder: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 30;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: user.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: lock, isSigner: false, isWritable: true },
+			{ pubkey: funder, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [user], progAddr, expectedError);
+};
+export const unlockTok = (
+	user: Keypair,
+	lock: PublicKey,
+	funder: PublicKey,
 	mint: PublicKey,
-	configPda: PublicKey,
-	decimals: number,
-	amount: bigint,
-	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
+	expectedError = "",
 ) => {
-	const disc = 5;
+	const disc = 31;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimals);
-	checkBigint(amount, "amount");
-	const argData = [decimals, ...numToBytes(amount)];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: fromAta, isSigner: false, isWritable: true },
-			{ pubkey: toAta, isSigner: false, isWritable: true },
-			{ pubkey: userVault, isSigner: false, isWritable: true }, // true
+			{ pubkey: user.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: lock, isSigner: false, isWritable: true },
+			{ pubkey: funder, isSigner: false, isWritable: true },
 			{ pubkey: mint, isSigner: false, isWritable: false },
-			{ pubkey: configPda, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [user], progAddr, expectedError);
+};
+//SOL delegates use the zero address as mint
+export const findVaultDelegate = (
+	user: PublicKey,
+	mint = PublicKey.default,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("delegate"), user.toBuffer(), mint.toBuffer()],
+		progAddr,
+	);
+	ll(`VaultDelegate: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const delegateSet = (
+	user: Keypair,
+	delegatePda: PublicKey,
+	delegate: PublicKey,
+	mint: PublicKey,
+	cap: bigint,
+	period: number,
+	expectedError = "",
+) => {
+	const disc = 32;
+	const progAddr = vaultProgAddr;
+	const argData = [
+		...mint.toBytes(),
+		...numToBytes(cap),
+		...numToBytes(period, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: user.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: delegatePda, isSigner: false, isWritable: true },
+			{ pubkey: delegate, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [user], progAddr, expectedError);
+};
+export const delegateRevoke = (
+	user: Keypair,
+	delegatePda: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 33;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: user.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: delegatePda, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [user], progAddr, expectedError);
+};
+export const delegateWithdrawSol = (
+	delegate: Keypair,
+	user: PublicKey,
+	userVault: PublicKey,
+	delegatePda: PublicKey,
+	to: PublicKey,
+	amount: bigint,
+	expectedError = "",
+	yieldPool?: PublicKey,
+) => {
+	const disc = 34;
+	const progAddr = vaultProgAddr;
+	checkBigint(amount, "amount");
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: delegate.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: user, isSigner: false, isWritable: false },
+			{ pubkey: userVault, isSigner: false, isWritable: true },
+			{ pubkey: delegatePda, isSigner: false, isWritable: true },
+			{ pubkey: to, isSigner: false, isWritable: true },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			{ pubkey: findVaultLock(user).pda, isSigner: false, isWritable: false },
+			...yieldPoolMeta(yieldPool),
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...numToBytes(amount)]),
+	});
+	sendTxns(blockhash, [ix], [delegate], progAddr, expectedError);
+};
+export const delegateWithdrawTok = (
+	delegate: Keypair,
+	user: PublicKey,
+	fromAta: PublicKey,
+	toAta: PublicKey,
+	userVault: PublicKey,
+	delegatePda: PublicKey,
+	mint: PublicKey,
+	decimals: number,
+	amount: bigint,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 35;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: delegate.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: user, isSigner: false, isWritable: false },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: toAta, isSigner: false, isWritable: true },
+			{ pubkey: userVault, isSigner: false, isWritable: false },
+			{ pubkey: delegatePda, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{
+				pubkey: findVaultLock(user, mint).pda,
+				isSigner: false,
+				isWritable: false,
+			},
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [delegate], progAddr, expectedError);
+};
+//-------------== Subscriptions
+export const findSubscription = (
+	user: PublicKey,
+	mint: PublicKey,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("subscription"), user.toBuffer(), mint.toBuffer()],
+		progAddr,
+	);
+	ll(`Subscription: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const subscriptionMake = (
+	userSigner: Keypair,
+	mint: PublicKey,
+	subscriptionPda: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	amount: bigint,
+	period: number,
+	maxPeriods: number,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 58;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [
+		decimals,
+		...numToBytes(amount),
+		...numToBytes(period, 32),
+		...numToBytes(maxPeriods, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, userSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: subscriptionPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr, expectedError);
+};
+//anyone can pull a due payment
+export const subscriptionPull = (
+	cranker: Keypair,
+	user: PublicKey,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	subscriptionPda: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 59;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: cranker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: user, isSigner: false, isWritable: false },
+			{
+				pubkey: getAta(mint, user, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: subscriptionPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [cranker], progAddr, expectedError);
+};
+export const subscriptionCancel = (
+	userSigner: Keypair,
+	mint: PublicKey,
+	subscriptionPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 60;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, userSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: subscriptionPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr, expectedError);
+};
+//-------------== Payment streams
+export const findStream = (
+	recipient: PublicKey,
+	mint: PublicKey,
+	configPda = configPDA,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("stream"),
+			configPda.toBuffer(),
+			recipient.toBuffer(),
+			mint.toBuffer(),
+		],
+		progAddr,
+	);
+	ll(`Stream: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+//funder is the config admin or prog_owner
+export const streamMake = (
+	funderSigner: Keypair,
+	recipient: PublicKey,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	streamPda: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	rate: bigint,
+	startAt: number,
+	endAt: number,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 61;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(rate, "rate");
+	const argData = [
+		decimals,
+		...numToBytes(rate),
+		...numToBytes(startAt, 32),
+		...numToBytes(endAt, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: funderSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: recipient, isSigner: false, isWritable: false },
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: false,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: streamPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [funderSigner], progAddr, expectedError);
+};
+export const streamWithdraw = (
+	recipientSigner: Keypair,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	streamPda: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 62;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: recipientSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, recipientSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: streamPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [recipientSigner], progAddr, expectedError);
+};
+//pays the recipient what has accrued; the rest stays in the vault
+export const streamCancel = (
+	funderSigner: Keypair,
+	recipient: PublicKey,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	streamPda: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 63;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: funderSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: recipient, isSigner: false, isWritable: false },
+			{
+				pubkey: getAta(mint, recipient, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: streamPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [funderSigner], progAddr, expectedError);
+};
+//-------------== Merkle airdrops
+//hashing must match src/instructions/merkle.rs
+const sha256 = (...vals: Uint8Array[]) => {
+	const hasher = createHash("sha256");
+	for (const val of vals) hasher.update(val);
+	return new Uint8Array(hasher.digest());
+};
+export const claimLeaf = (
+	index: number,
+	claimant: PublicKey,
+	amount: bigint,
+): Uint8Array =>
+	sha256(
+		Uint8Array.from([0]),
+		Uint8Array.from(numToBytes(index, 32)),
+		claimant.toBytes(),
+		Uint8Array.from(numToBytes(amount)),
+	);
+const merkleNode = (a: Uint8Array, b: Uint8Array) =>
+	Buffer.compare(a, b) <= 0
+		? sha256(Uint8Array.from([1]), a, b)
+		: sha256(Uint8Array.from([1]), b, a);
+//an odd node at the end of a level moves up unpaired
+export const merkleLevels = (leaves: Uint8Array[]): Uint8Array[][] => {
+	const levels = [leaves];
+	let level = leaves;
+	while (level.length > 1) {
+		const next: Uint8Array[] = [];
+		for (let i = 0; i < level.length; i += 2) {
+			const left = level[i] as Uint8Array;
+			const right = level[i + 1];
+			next.push(right ? merkleNode(left, right) : left);
+		}
+		levels.push(next);
+		level = next;
+	}
+	return levels;
+};
+export const merkleRoot = (levels: Uint8Array[][]) =>
+	levels.at(-1)?.[0] ?? new Uint8Array(32);
+//sibling hashes from the leaf at index to the root
+export const merkleProof = (levels: Uint8Array[][], index: number) => {
+	const proof: Uint8Array[] = [];
+	let i = index;
+	for (const level of levels.slice(0, -1)) {
+		const sibling = level[i ^ 1];
+		if (sibling) proof.push(sibling);
+		i = Math.floor(i / 2);
+	}
+	return proof;
+};
+export const findDistributor = (
+	funder: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("distributor"),
+			funder.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Distributor ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+//funder is the config admin or prog_owner
+export const distributorMake = (
+	funderSigner: Keypair,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	distributorPda: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	id: bigint,
+	total: bigint,
+	deadline: bigint,
+	count: number,
+	root: Uint8Array,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 64;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(total, "total");
+	const argData = [
+		decimals,
+		...numToBytes(id),
+		...numToBytes(total),
+		...numToBytes(deadline),
+		...numToBytes(count, 32),
+		...root,
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: funderSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, funderSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: distributorPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [funderSigner], progAddr, expectedError);
+};
+export const distributorClaim = (
+	claimantSigner: Keypair,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	distributorPda: PublicKey,
+	configPda: PublicKey,
+	index: number,
+	amount: bigint,
+	proof: Uint8Array[],
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 65;
+	const progAddr = vaultProgAddr;
+	checkBigint(amount, "amount");
+	const argData = [
+		...numToBytes(index, 32),
+		...numToBytes(amount),
+		...proof.flatMap((hash) => [...hash]),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: claimantSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, claimantSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: distributorPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [claimantSigner], progAddr, expectedError);
+};
+//after the deadline, the unclaimed rest goes back to the funder
+export const distributorClawback = (
+	funderSigner: Keypair,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	distributorPda: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 66;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: funderSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, funderSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: distributorPda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [funderSigner], progAddr, expectedError);
+};
+//-------------== Invoices
+export const findInvoice = (
+	issuer: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("invoice"),
+			issuer.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Invoice ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+//issuer is the config admin or prog_owner. The default payer lets anyone pay
+export const invoiceMake = (
+	issuerSigner: Keypair,
+	mint: PublicKey,
+	invoicePda: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	id: bigint,
+	amount: bigint,
+	dueAt: bigint,
+	payer = PublicKey.default,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 67;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [
+		decimals,
+		...numToBytes(id),
+		...numToBytes(amount),
+		...numToBytes(dueAt),
+		...payer.toBytes(),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: issuerSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: invoicePda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [issuerSigner], progAddr, expectedError);
+};
+//returns the transaction result to read the InvoicePaid event
+export const invoicePay = (
+	payerSigner: Keypair,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	invoicePda: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 68;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: payerSigner.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, payerSigner.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, centralVault, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: invoicePda, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	return sendTxns(blockhash, [ix], [payerSigner], progAddr, expectedError);
+};
+export const multisigInit = (
+	progOwner: Keypair,
+	configPda: PublicKey,
+	multisig: PublicKey,
+	members: PublicKey[],
+	threshold: number,
+	expectedError = "",
+) => {
+	const disc = 36;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: multisig, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...members.map((pubkey) => ({
+				pubkey,
+				isSigner: false,
+				isWritable: false,
+			})),
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, threshold]),
+	});
+	sendTxns(blockhash, [ix], [progOwner], progAddr, expectedError);
+};
+export const ProposalAction = {
+	SetAdmin: 1,
+	SetProgOwner: 2,
+	SetFee: 3,
+	SetStatus: 4,
+	SetDelay: 5, //Timelock only
+	CloseConfig: 6, //Timelock only
+} as const;
+export const findProposal = (
+	multisig: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("proposal"),
+			multisig.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Proposal ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const proposalMake = (
+	member: Keypair,
+	multisig: PublicKey,
+	proposal: PublicKey,
+	id: bigint,
+	action: number,
+	numU8: number,
+	numU64: bigint,
+	account1: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 37;
+	const progAddr = vaultProgAddr;
+	const argData = [
+		...numToBytes(id),
+		action,
+		numU8,
+		...numToBytes(numU64),
+		...account1.toBytes(),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: member.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{ pubkey: proposal, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [member], progAddr, expectedError);
+};
+export const proposalApprove = (
+	member: Keypair,
+	multisig: PublicKey,
+	proposal: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 38;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: member.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{ pubkey: proposal, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [member], progAddr, expectedError);
+};
+export const proposalExecute = (
+	member: Keypair,
+	multisig: PublicKey,
+	proposal: PublicKey,
+	configPda: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 39;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: member.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{ pubkey: proposal, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [member], progAddr, expectedError);
+};
+export const timelockInit = (
+	progOwner: Keypair,
+	configPda: PublicKey,
+	timelock: PublicKey,
+	delay: number,
+	expectedError = "",
+) => {
+	const disc = 40;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: timelock, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...numToBytes(delay, 32)]),
+	});
+	sendTxns(blockhash, [ix], [progOwner], progAddr, expectedError);
+};
+export const findQueuedAction = (
+	timelock: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("queued"),
+			timelock.toBuffer(),
+			Buffer.copyBytesFrom(numToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`QueuedAction ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const timelockQueue = (
+	owner: Keypair,
+	timelock: PublicKey,
+	configPda: PublicKey,
+	queued: PublicKey,
+	id: bigint,
+	action: number,
+	numU8: number,
+	numU64: bigint,
+	account1: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 41;
+	const progAddr = vaultProgAddr;
+	const argData = [
+		...numToBytes(id),
+		action,
+		numU8,
+		...numToBytes(numU64),
+		...account1.toBytes(),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: owner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: timelock, isSigner: false, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: false },
+			{ pubkey: queued, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [owner], progAddr, expectedError);
+};
+export const timelockExecute = (
+	signer: Keypair,
+	timelock: PublicKey,
+	queued: PublicKey,
+	configPda: PublicKey,
+	owner: PublicKey,
+	expectedError = "",
+	mintAtas: AccountMeta[] = [], //for CloseConfig
+) => {
+	const disc = 42;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: timelock, isSigner: false, isWritable: false },
+			{ pubkey: queued, isSigner: false, isWritable: true },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: owner, isSigner: false, isWritable: true },
+			...mintAtas,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
+};
+export const timelockCancel = (
+	owner: Keypair,
+	timelock: PublicKey,
+	queued: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 43;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: owner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: timelock, isSigner: false, isWritable: false },
+			{ pubkey: queued, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [owner], progAddr, expectedError);
+};
+export const lgcInitMint = (
+	signer: Keypair,
+	mintKp: Keypair,
+	mintAuthority: PublicKey,
+	freezeAuthorityOpt: PublicKey,
+	decimals: number,
+	//argData: Uint8Array<ArrayBufferLike>,
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 2;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintAuthority, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, decimals]),
+	});
+	sendTxns(blockhash, [ix], [signer, mintKp], progAddr);
+};
+/** Optional Token2022 mint extensions, selected by flags */
+export const Tok22ExtFlag = {
+	TransferFee: 1,
+	InterestBearing: 1 << 1,
+	NonTransferable: 1 << 2,
+	PermanentDelegate: 1 << 3,
+	DefaultAccountState: 1 << 4,
+	MintCloseAuthority: 1 << 5,
+};
+export type Tok22MintExt = {
+	flags: number;
+	transferFeeBps: number;
+	maximumFee: bigint;
+	interestRate: number; //i16 basis points
+	defaultState: number; //1 Initialized, 2 Frozen
+	permanentDelegate: PublicKey;
+};
+export const tok22MintExtBytes = (ext: Tok22MintExt) => {
+	const rate = Buffer.alloc(2);
+	rate.writeInt16LE(ext.interestRate);
+	return [
+		ext.flags,
+		...numToBytes(ext.transferFeeBps, 16),
+		...numToBytes(ext.maximumFee),
+		...rate,
+		ext.defaultState,
+		...ext.permanentDelegate.toBytes(),
+	];
+};
+export const tok22InitMint = (
+	signer: Keypair,
+	mintKp: Keypair,
+	mintAuthorityKp: Keypair,
+	freezeAuthorityOpt: PublicKey,
+	decimals: number,
+	tokenName: string,
+	tokenSymbol: string,
+	tokenUri: string,
+	ext?: Tok22MintExt,
+	tokenProg = TOKEN_2022_PROGRAM_ID,
+	expectedError = "",
+) => {
+	const disc = 9;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	const argData = [
+		decimals,
+		...strToU8Fixed(tokenName, 10),
+		...strToU8Fixed(tokenSymbol, 6),
+		...strToU8Fixed(tokenUri, 32),
+		...(ext ? tok22MintExtBytes(ext) : []),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintAuthorityKp.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(
+		blockhash,
+		[ix],
+		[signer, mintKp, mintAuthorityKp],
+		progAddr,
+		expectedError,
+	);
+};
+/** field: 0 Name, 1 Symbol, 2 Uri, 3 custom Key */
+export const tok22UpdateMetadata = (
+	updateAuthorityKp: Keypair,
+	mint: PublicKey,
+	field: number,
+	key: string,
+	value: string,
+	tokenProg = TOKEN_2022_PROGRAM_ID,
+	expectedError = "",
+) => {
+	const disc = 24;
+	const progAddr = vaultProgAddr;
+	const argData = [
+		field,
+		...strToU8Fixed(key, 10),
+		...strToU8Fixed(value, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: updateAuthorityKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [updateAuthorityKp], progAddr, expectedError);
+};
+export const lgcInitAta = (
+	signer: Keypair,
+	toWallet: PublicKey,
+	mint: PublicKey,
+	ata: PublicKey,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+) => {
+	const disc = 3;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: toWallet, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [signer], progAddr);
+};
+export const lgcMintToken = (
+	mintAuthority: Keypair,
+	toWallet: PublicKey,
+	mint: PublicKey,
+	ata: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+) => {
+	const disc = 4;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: mintAuthority.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: toWallet, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [mintAuthority], progAddr);
+};
+
+export const tok22MintToken = (
+	mintAuthority: Keypair,
+	toWallet: PublicKey,
+	mint: PublicKey,
+	ata: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_2022_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+) => {
+	const disc = 11;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: mintAuthority.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: toWallet, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [mintAuthority], progAddr);
+};
+
+export const lgcDeposit = (
+	userSigner: Keypair,
+	fromAta: PublicKey,
+	toAta: PublicKey,
+	userVault: PublicKey,
+	mint: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 5;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: toAta, isSigner: false, isWritable: true },
+			{ pubkey: userVault, isSigner: false, isWritable: true }, // true
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr);
+};
+export const lgcWithdraw = (
+	userSigner: Keypair,
+	fromAta: PublicKey,
+	toAta: PublicKey,
+	userVault: PublicKey,
+	mint: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+	expectedError = "",
+) => {
+	const disc = 6;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: toAta, isSigner: false, isWritable: true },
+			{ pubkey: userVault, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{
+				pubkey: findVaultLock(userSigner.publicKey, mint).pda,
+				isSigner: false,
+				isWritable: false,
+			},
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr, expectedError);
+};
+export const lgcPay = (
+	userSigner: Keypair,
+	fromAta: PublicKey,
+	toAta: PublicKey,
+	centralVault: PublicKey,
+	mint: PublicKey,
+	configPda: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 7;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: toAta, isSigner: false, isWritable: true },
+			{ pubkey: centralVault, isSigner: false, isWritable: true }, // true
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr);
+};
+export const lgcRedeem = (
+	userSigner: Keypair,
+	fromAta: PublicKey,
+	toAta: PublicKey,
+	centralVault: PublicKey,
+	configPDA: PublicKey,
+	mint: PublicKey,
+	decimals: number,
+	amount: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+	expectedError = "",
+) => {
+	const disc = 8;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...numToBytes(amount)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: fromAta, isSigner: false, isWritable: true },
+			{ pubkey: toAta, isSigner: false, isWritable: true },
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [userSigner], progAddr, expectedError);
+};
+//Dutch auction: amountY decays linearly to endAmountY between startTime and endTime
+export type DutchIn = {
+	endAmountY: bigint;
+	startTime: bigint;
+	endTime: bigint;
+};
+//Trigger: takeable only while the oracle price is above/below triggerPrice
+export type TriggerIn = {
+	oracleVendor: number;
+	triggerAbove: boolean;
+	triggerPrice: bigint;
+	feedId: string; //hex string
+};
+export const makeTokEscrow = (
+	maker: Keypair,
+	makerAtaX: PublicKey,
+	escrowAtaX: PublicKey,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	decimalX: number,
+	amountX: bigint,
+	decimalY: number,
+	amountY: bigint,
+	id: bigint,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+	expectedError = "",
+	mode = 0, //1 NFT X, 2 Collection Y, 4 Dutch Y, 8 Trigger
+	dutch?: DutchIn,
+	trigger?: TriggerIn,
+) => {
+	const disc = 15;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimalX, "decimalX");
+	checkDecimals(decimalX, "decimalY");
+	checkBigint(amountX, "amountX");
+	checkBigint(amountY, "amountY");
+	if (id < zero) throw new Error("id invalid"); //0 takes the next id from the MakerIndex
+	const argData = [
+		decimalX,
+		...numToBytes(amountX),
+		decimalY,
+		...numToBytes(amountY),
+		...numToBytes(id),
+		mode,
+	];
+	if (dutch) {
+		argData.push(
+			...numToBytes(dutch.endAmountY),
+			...numToBytes(dutch.startTime),
+			...numToBytes(dutch.endTime),
+		);
+	}
+	if (trigger) {
+		argData.push(
+			trigger.oracleVendor,
+			trigger.triggerAbove ? 1 : 0,
+			...numToBytes(trigger.triggerPrice),
+			...decodeHexstrToUint8(trigger.feedId),
+		);
+	}
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
+};
+export const takeTokEscrow = (
+	taker: Keypair,
+	takerAtaX: PublicKey,
+	takerAtaY: PublicKey,
+	escrowAtaX: PublicKey,
+	escrowAtaY: PublicKey,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	decimalX: number,
+	minX: bigint,
+	decimalY: number,
+	maxY: bigint,
+	seq: number,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+	expectedError = "",
+) => {
+	const disc = 16;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimalX, "decimalX");
+	checkDecimals(decimalX, "decimalY");
+	checkBigint(maxY, "maxY");
+	const argData = [
+		decimalX,
+		...numToBytes(minX),
+		decimalY,
+		...numToBytes(maxY),
+		...numToBytes(seq, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: taker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: takerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: takerAtaY, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [taker], progAddr, expectedError);
+};
+export const withdrawTokEscrow = (
+	maker: Keypair,
+	makerAtaX: PublicKey,
+	makerAtaY: PublicKey,
+	escrowAtaX: PublicKey,
+	escrowAtaY: PublicKey,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 17;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr);
+};
+export const cancelTokEscrow = (
+	makerSigner: Keypair,
+	makerAtaX: PublicKey,
+	makerAtaY: PublicKey,
+	escrowAtaX: PublicKey,
+	escrowAtaY: PublicKey,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	tokenProg = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+	expectedError = "",
+) => {
+	const disc = 18;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: makerSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [makerSigner], progAddr, expectedError);
+};
+export const amendTokEscrow = (
+	maker: Keypair,
+	makerAtaX: PublicKey,
+	escrowAtaX: PublicKey,
+	mintX: PublicKey,
+	escrowPDA: PublicKey,
+	amountY: bigint,
+	topUpX: bigint,
+	withdrawX: bigint,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 44;
+	const progAddr = vaultProgAddr;
+	checkBigint(amountY, "amountY");
+	const argData = [
+		...numToBytes(amountY),
+		...numToBytes(topUpX),
+		...numToBytes(withdrawX),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
+};
+
+export const makeSolEscrow = (
+	maker: Keypair,
+	makerAta: PublicKey,
+	escrowAta: PublicKey,
+	mint: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	solX: boolean, //true: SOL for token Y; false: token X for SOL
+	decimals: number, //of the token leg
+	amountX: bigint,
+	amountY: bigint,
+	id: bigint,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 45;
+	const progAddr = vaultProgAddr;
+	checkDecimals(decimals, "decimals");
+	checkBigint(amountX, "amountX");
+	checkBigint(amountY, "amountY");
+	if (id < zero) throw new Error("id invalid"); //0 takes the next id from the MakerIndex
+	const argData = [
+		solX ? 1 : 0,
+		decimals,
+		...numToBytes(amountX),
+		...numToBytes(amountY),
+		...numToBytes(id),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAta, isSigner: false, isWritable: true },
+			{ pubkey: escrowAta, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
+};
+export const takeSolEscrow = (
+	taker: Keypair,
+	takerAta: PublicKey,
+	escrowAta: PublicKey,
+	mint: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	minX: bigint,
+	maxY: bigint,
+	seq: number,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 46;
+	const progAddr = vaultProgAddr;
+	checkBigint(maxY, "maxY");
+	const argData = [
+		...numToBytes(minX),
+		...numToBytes(maxY),
+		...numToBytes(seq, 32),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: taker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: takerAta, isSigner: false, isWritable: true },
+			{ pubkey: escrowAta, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [taker], progAddr, expectedError);
+};
+export const closeSolEscrow = (
+	maker: Keypair,
+	makerAta: PublicKey,
+	escrowAta: PublicKey,
+	mint: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+	hookAccounts: AccountMeta[] = [], //TransferHook accounts
+) => {
+	const disc = 47;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: makerAta, isSigner: false, isWritable: true },
+			{ pubkey: escrowAta, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...hookAccounts,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
+};
+
+export type BasketLegIn = {
+	mint: PublicKey;
+	amount: bigint;
+	decimals: number;
+};
+const writableKeys = (keys: PublicKey[]): AccountMeta[] =>
+	keys.map((pubkey, i) => ({ pubkey, isSigner: false, isWritable: i > 0 }));
+export const makeBasket = (
+	maker: Keypair,
+	basketPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	legsX: BasketLegIn[],
+	legsY: BasketLegIn[],
+	id: bigint,
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 48;
+	const progAddr = vaultProgAddr;
+	if (id < zero) throw new Error("id invalid"); //0 takes the next id from the MakerIndex
+	const legData: number[] = [];
+	for (const leg of [...legsX, ...legsY]) {
+		checkBigint(leg.amount, "leg amount");
+		checkDecimals(leg.decimals, "leg decimals");
+		legData.push(...numToBytes(leg.amount), leg.decimals);
+	}
+	const argData = [legsX.length, legsY.length, ...numToBytes(id), ...legData];
+	const legKeys = [
+		...legsX.flatMap((leg) =>
+			writableKeys([
+				leg.mint,
+				getAta(leg.mint, maker.publicKey, true, tokenProg),
+				getAta(leg.mint, basketPDA, true, tokenProg),
+			]),
+		),
+		...legsY.flatMap((leg) =>
+			writableKeys([leg.mint, getAta(leg.mint, basketPDA, true, tokenProg)]),
+		),
+	];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: basketPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...legKeys,
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
+};
+/** user is the taker for BasketTake, or the maker for BasketClose. mints are X legs then Y legs */
+const basketLegKeys = (
+	user: PublicKey,
+	basketPDA: PublicKey,
+	mints: PublicKey[],
+	tokenProg: PublicKey,
+) =>
+	mints.flatMap((mint) =>
+		writableKeys([
+			mint,
+			getAta(mint, basketPDA, true, tokenProg),
+			getAta(mint, user, true, tokenProg),
+		]),
+	);
+export const takeBasket = (
+	taker: Keypair,
+	basketPDA: PublicKey,
+	configPDA: PublicKey,
+	mints: PublicKey[],
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 49;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: taker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: basketPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...basketLegKeys(taker.publicKey, basketPDA, mints, tokenProg),
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [taker], progAddr, expectedError);
+};
+export const closeBasket = (
+	maker: Keypair,
+	basketPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	mints: PublicKey[],
+	expectedError = "",
+	tokenProg = TOKEN_PROGRAM_ID,
+) => {
+	const disc = 50;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: basketPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			...basketLegKeys(maker.publicKey, basketPDA, mints, tokenProg),
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, ...argData]),
+		data: Buffer.from([disc]),
 	});
-	sendTxns(blockhash, [ix], [userSigner], progAddr);
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
 };
-export const lgcWithdraw = (
-	userSigner: Keypair,
-	fromAta: PublicKey,
-	toAta: PublicKey,
-	userVault: PublicKey,
-	mint: PublicKey,
-	decimals: number,
-	amount: bigint,
+
+export const makeAuction = (
+	maker: Keypair,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	auctionPDA: PublicKey,
+	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	decimalX: number,
+	amountX: bigint,
+	decimalY: number,
+	reserveY: bigint,
+	endTime: bigint,
+	id: bigint,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 6;
+	const disc = 51;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimals);
-	checkBigint(amount, "amount");
-	const argData = [decimals, ...numToBytes(amount)];
+	checkBigint(amountX, "amountX");
+	checkBigint(reserveY, "reserveY");
+	const argData = [
+		decimalX,
+		...numToBytes(amountX),
+		decimalY,
+		...numToBytes(reserveY),
+		...numToBytes(endTime),
+		...numToBytes(id),
+	];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: fromAta, isSigner: false, isWritable: true },
-			{ pubkey: toAta, isSigner: false, isWritable: true },
-			{ pubkey: userVault, isSigner: false, isWritable: false },
-			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mintX, maker.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mintX, auctionPDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: auctionPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [userSigner], progAddr);
+	sendTxns(blockhash, [ix], [maker], progAddr, expectedError);
 };
-export const lgcPay = (
-	userSigner: Keypair,
-	fromAta: PublicKey,
-	toAta: PublicKey,
-	centralVault: PublicKey,
-	mint: PublicKey,
-	configPda: PublicKey,
-	decimals: number,
-	amount: bigint,
+export const bidAuction = (
+	bidder: Keypair,
+	mintY: PublicKey,
+	auctionPDA: PublicKey,
+	amount: bigint, //added to what the bidder's Bid PDA holds
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 7;
+	const disc = 52;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimals);
 	checkBigint(amount, "amount");
-	const argData = [decimals, ...numToBytes(amount)];
+	const bidPDA = findBid(auctionPDA, bidder.publicKey).pda;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: fromAta, isSigner: false, isWritable: true },
-			{ pubkey: toAta, isSigner: false, isWritable: true },
-			{ pubkey: centralVault, isSigner: false, isWritable: true }, // true
-			{ pubkey: mint, isSigner: false, isWritable: false },
-			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: bidder.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mintY, bidder.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: bidPDA, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(mintY, bidPDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: auctionPDA, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, ...argData]),
+		data: Buffer.from([disc, ...numToBytes(amount)]),
 	});
-	sendTxns(blockhash, [ix], [userSigner], progAddr);
+	sendTxns(blockhash, [ix], [bidder], progAddr, expectedError);
 };
-export const lgcRedeem = (
-	userSigner: Keypair,
-	fromAta: PublicKey,
-	toAta: PublicKey,
-	centralVault: PublicKey,
-	configPDA: PublicKey,
-	mint: PublicKey,
+/** an outbid bidder takes its bid back, also after the auction is settled */
+export const withdrawBid = (
+	bidder: Keypair,
+	mintY: PublicKey,
+	auctionPDA: PublicKey,
 	decimals: number,
-	amount: bigint,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 8;
+	const disc = 71;
 	const progAddr = vaultProgAddr;
 	checkDecimals(decimals);
-	checkBigint(amount, "amount");
-	const argData = [decimals, ...numToBytes(amount)];
+	const bidPDA = findBid(auctionPDA, bidder.publicKey).pda;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: userSigner.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: fromAta, isSigner: false, isWritable: true },
-			{ pubkey: toAta, isSigner: false, isWritable: true },
-			{ pubkey: centralVault, isSigner: false, isWritable: false },
-			{ pubkey: configPDA, isSigner: false, isWritable: false },
-			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: bidder.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mintY, bidder.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: bidPDA, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(mintY, bidPDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: auctionPDA, isSigner: false, isWritable: false },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
-			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, ...argData]),
+		data: Buffer.from([disc, decimals]),
 	});
-	sendTxns(blockhash, [ix], [userSigner], progAddr);
+	sendTxns(blockhash, [ix], [bidder], progAddr, expectedError);
 };
-export const makeTokEscrow = (
-	maker: Keypair,
-	makerAtaX: PublicKey,
-	escrowAtaX: PublicKey,
+/** winner is the highest bidder, or the maker when there is no bid */
+export const settleAuction = (
+	payer: Keypair,
+	maker: PublicKey,
+	winner: PublicKey,
 	mintX: PublicKey,
 	mintY: PublicKey,
-	escrowPDA: PublicKey,
+	auctionPDA: PublicKey,
 	configPDA: PublicKey,
-	decimalX: number,
-	amountX: bigint,
-	decimalY: number,
-	amountY: bigint,
-	id: bigint,
+	makerIndex: PublicKey,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 15;
+	const disc = 53;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimalX, "decimalX");
-	checkDecimals(decimalX, "decimalY");
-	checkBigint(amountX, "amountX");
-	checkBigint(amountY, "amountY");
-	checkBigint(id, "id");
-	const argData = [
-		decimalX,
-		...numToBytes(amountX),
-		decimalY,
-		...numToBytes(amountY),
-		...numToBytes(id),
-	];
+	const bidPDA = findBid(auctionPDA, winner).pda;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: payer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: maker, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(mintY, maker, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: winner, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(mintX, winner, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mintX, auctionPDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: bidPDA, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(mintY, bidPDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
 			{ pubkey: mintX, isSigner: false, isWritable: false },
 			{ pubkey: mintY, isSigner: false, isWritable: false },
-			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: auctionPDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
-		data: Buffer.from([disc, ...argData]),
+		data: Buffer.from([disc]),
 	});
-	sendTxns(blockhash, [ix], [maker], progAddr);
+	sendTxns(blockhash, [ix], [payer], progAddr, expectedError);
 };
-export const takeTokEscrow = (
-	taker: Keypair,
-	takerAtaX: PublicKey,
-	takerAtaY: PublicKey,
-	escrowAtaX: PublicKey,
-	escrowAtaY: PublicKey,
-	mintX: PublicKey,
-	mintY: PublicKey,
-	escrowPDA: PublicKey,
+
+//-------------== Milestone escrow
+export const makeMilestone = (
+	payer: Keypair,
+	mint: PublicKey,
+	payee: PublicKey,
+	arbiter: PublicKey,
+	milestonePDA: PublicKey,
 	configPDA: PublicKey,
-	decimalX: number,
-	amountX: bigint,
-	decimalY: number,
-	amountY: bigint,
+	makerIndex: PublicKey,
+	decimals: number,
+	deadline: bigint,
+	amounts: bigint[],
 	id: bigint,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 16;
+	const disc = 54;
 	const progAddr = vaultProgAddr;
-	checkDecimals(decimalX, "decimalX");
-	checkDecimals(decimalX, "decimalY");
-	checkBigint(amountX, "amountX");
-	checkBigint(amountY, "amountY");
-	checkBigint(id, "id");
+	checkDecimals(decimals, "decimals");
+	if (id < zero) throw new Error("id invalid"); //0 takes the next id from the MakerIndex
 	const argData = [
-		decimalX,
-		...numToBytes(amountX),
-		decimalY,
-		...numToBytes(amountY),
+		decimals,
+		...numToBytes(deadline),
 		...numToBytes(id),
+		amounts.length,
+		...amounts.flatMap((amount) => [...numToBytes(amount)]),
 	];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: taker.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: takerAtaX, isSigner: false, isWritable: true },
-			{ pubkey: takerAtaY, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
-			{ pubkey: mintX, isSigner: false, isWritable: false },
-			{ pubkey: mintY, isSigner: false, isWritable: false },
-			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: payer.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, payer.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, milestonePDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: payee, isSigner: false, isWritable: false },
+			{ pubkey: arbiter, isSigner: false, isWritable: false },
+			{ pubkey: milestonePDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(blockhash, [ix], [taker], progAddr);
+	sendTxns(blockhash, [ix], [payer], progAddr, expectedError);
 };
-export const withdrawTokEscrow = (
-	maker: Keypair,
-	makerAtaX: PublicKey,
-	makerAtaY: PublicKey,
-	escrowAtaX: PublicKey,
-	escrowAtaY: PublicKey,
-	mintX: PublicKey,
-	mintY: PublicKey,
-	escrowPDA: PublicKey,
-	configPDA: PublicKey,
+//release to the payee, or refund to the payer
+export const settleMilestone = (
+	signer: Keypair,
+	payer: PublicKey,
+	recipient: PublicKey,
+	mint: PublicKey,
+	milestonePDA: PublicKey,
+	index: number,
+	release: boolean,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 17;
+	const disc = 55;
 	const progAddr = vaultProgAddr;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: maker.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
-			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
-			{ pubkey: mintX, isSigner: false, isWritable: false },
-			{ pubkey: mintY, isSigner: false, isWritable: false },
-			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
-			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: payer, isSigner: false, isWritable: false },
+			{ pubkey: recipient, isSigner: false, isWritable: false },
+			{
+				pubkey: getAta(mint, recipient, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, milestonePDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: milestonePDA, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
-		data: Buffer.from([disc]),
+		data: Buffer.from([disc, index, release ? 1 : 0]),
 	});
-	sendTxns(blockhash, [ix], [maker], progAddr);
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
 };
-export const cancelTokEscrow = (
-	makerSigner: Keypair,
-	makerAtaX: PublicKey,
-	makerAtaY: PublicKey,
-	escrowAtaX: PublicKey,
-	escrowAtaY: PublicKey,
-	mintX: PublicKey,
-	mintY: PublicKey,
-	escrowPDA: PublicKey,
+export const disputeMilestone = (
+	signer: Keypair,
+	milestonePDA: PublicKey,
+	index: number,
+	expectedError = "",
+) => {
+	const disc = 56;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: milestonePDA, isSigner: false, isWritable: true },
+		],
+		programId: progAddr,
+		data: Buffer.from([disc, index]),
+	});
+	sendTxns(blockhash, [ix], [signer], progAddr, expectedError);
+};
+export const closeMilestone = (
+	payer: Keypair,
+	mint: PublicKey,
+	milestonePDA: PublicKey,
 	configPDA: PublicKey,
+	makerIndex: PublicKey,
+	expectedError = "",
 	tokenProg = TOKEN_PROGRAM_ID,
-	atokenProg = ATokenGPvbd,
 ) => {
-	const disc = 18;
+	const disc = 57;
 	const progAddr = vaultProgAddr;
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: makerSigner.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
-			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
-			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
-			{ pubkey: mintX, isSigner: false, isWritable: false },
-			{ pubkey: mintY, isSigner: false, isWritable: false },
-			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: payer.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: getAta(mint, payer.publicKey, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: getAta(mint, milestonePDA, true, tokenProg),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: milestonePDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: makerIndex, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
-			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 			{ pubkey: RentSysvar, isSigner: false, isWritable: false },
 		],
 		programId: progAddr,
 		data: Buffer.from([disc]),
 	});
-	sendTxns(blockhash, [ix], [makerSigner], progAddr);
+	sendTxns(blockhash, [ix], [payer], progAddr, expectedError);
 };
 
+export const tok22HarvestFees = (
+	adminSigner: Keypair,
+	configPda: PublicKey,
+	mint: PublicKey,
+	centralVault: PublicKey,
+	vaultAta: PublicKey,
+	sources: PublicKey[] = [],
+	tokenProg = TOKEN_2022_PROGRAM_ID,
+	expectedError = "",
+) => {
+	const disc = 25;
+	const progAddr = vaultProgAddr;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: adminSigner.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: centralVault, isSigner: false, isWritable: false },
+			{ pubkey: vaultAta, isSigner: false, isWritable: true },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			...sources.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+		],
+		programId: progAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(blockhash, [ix], [adminSigner], progAddr, expectedError);
+};
 export const oraclesRead = (
 	signer: Keypair,
 	configPDA: PublicKey,
@@ -1161,6 +3340,23 @@ export const sendTxns = (
 	const simRes = svm.simulateTransaction(tx);
 	const sendRes = svm.sendTransaction(tx);
 	checkLogs(simRes, sendRes, programId, expectedError);
+	return sendRes;
+};
+//fields of each "Program data:" line, as logged by emit_event
+export const eventsInLogs = (
+	sendRes: TransactionMetadata | FailedTransactionMetadata,
+): Buffer[][] => {
+	if (!(sendRes instanceof TransactionMetadata)) return [];
+	const prefix = "Program data: ";
+	return sendRes
+		.logs()
+		.filter((log) => log.startsWith(prefix))
+		.map((log) =>
+			log
+				.slice(prefix.length)
+				.split(" ")
+				.map((field) => Buffer.from(field, "base64")),
+		);
 };
 export const checkLogs = (
 	simRes: FailedTransactionMetadata | SimulatedTransactionInfo,
@@ -0,0 +1,174 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { Keypair } from "@solana/web3.js";
+import { decodeVaultLock, Status } from "./decoder";
+import {
+	acctIsNull,
+	ataBalc,
+	ataBalCk,
+	configPDA,
+	day,
+	findPdaV1,
+	findVaultLock,
+	getAta,
+	initConfig,
+	lgcWithdraw,
+	lockSol,
+	lockTok,
+	setAtaCheck,
+	setLgcMint,
+	svm,
+	unlockSol,
+	unlockTok,
+	vault3,
+	warpTime,
+	withdrawSol,
+} from "./litesvm-utils";
+import { as6zBn, as9zBn, ll } from "./utils";
+import {
+	admin,
+	adminKp,
+	hackerKp,
+	owner,
+	pyusdMint,
+	usdcMint,
+	usdgMint,
+	usdtMint,
+	user1Kp,
+	user3,
+	user3Kp,
+} from "./web3jsSetup";
+
+//a new user, so LockSol has to make the vault
+const user4Kp = new Keypair();
+const user4 = user4Kp.publicKey;
+svm.airdrop(user4, as9zBn(1));
+const vault4 = findPdaV1(user4, "Vault4").pda;
+const solLock = findVaultLock(user4).pda;
+const tokLock = findVaultLock(user3, usdcMint).pda;
+const now = () => svm.getClock().unixTimestamp;
+
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	setLgcMint(usdcMint);
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		user1Kp,
+		[usdcMint, usdtMint, pyusdMint, usdgMint],
+		owner,
+		admin,
+		true,
+		Status.Active,
+		111000000n,
+		"Vault locks",
+	);
+});
+
+test("Admin locks SOL in User4 vault with a cliff and linear vesting", () => {
+	ll("\n------== Admin locks SOL in User4 vault");
+	const amount = as9zBn(2);
+	const t0 = now();
+	lockSol(
+		adminKp,
+		user4Kp,
+		vault4,
+		solLock,
+		amount,
+		t0 + BigInt(30 * day),
+		t0 + BigInt(120 * day),
+	);
+	const lock = decodeVaultLock(svm.getAccount(solLock)?.data);
+	expect(lock.total).toStrictEqual(amount);
+	expect(svm.getBalance(vault4)! > amount).toBe(true);
+
+	//one open lock per user and mint
+	lockSol(
+		adminKp,
+		user4Kp,
+		vault4,
+		solLock,
+		amount,
+		t0 + BigInt(day),
+		t0 + BigInt(2 * day),
+		"0x84",
+	);
+	//nothing can leave the vault before the cliff
+	withdrawSol(user4Kp, vault4, 1n, "0x86");
+	unlockSol(user4Kp, solLock, admin, "0x86");
+	unlockSol(hackerKp, solLock, admin, "0x83");
+});
+
+test("User4 withdraws SOL as it vests", () => {
+	ll("\n------== User4 withdraws SOL as it vests");
+	warpTime(60 * day);
+	const half = as9zBn(1);
+	withdrawSol(user4Kp, vault4, half + 1n, "0x86");
+
+	const balcBf = svm.getBalance(user4)!;
+	withdrawSol(user4Kp, vault4, half);
+	expect(svm.getBalance(user4)! - balcBf).toBeLessThanOrEqual(half);
+	//the rest is still locked
+	unlockSol(user4Kp, solLock, admin, "0x86");
+
+	warpTime(60 * day);
+	const adminBf = svm.getBalance(admin)!;
+	unlockSol(user4Kp, solLock, admin);
+	//the lock closes and returns its rent to the funder
+	acctIsNull(solLock);
+	expect(svm.getBalance(admin)! > adminBf).toBe(true);
+	withdrawSol(user4Kp, vault4, half - 1n);
+});
+
+test("Admin locks tokens in User3 vault until an unlock time", () => {
+	ll("\n------== Admin locks tokens in User3 vault");
+	setLgcMint(usdcMint);
+	setAtaCheck(usdcMint, admin, as6zBn(1000), "Admin USDC");
+	const adminAta = getAta(usdcMint, admin);
+	const vaultAta = getAta(usdcMint, vault3);
+	const user3Ata = getAta(usdcMint, user3);
+	const amount = as6zBn(100);
+	const unlockAt = now() + BigInt(10 * day);
+	const free = ataBalc(vaultAta, "Vault3");
+
+	lockTok(
+		adminKp,
+		adminAta,
+		vaultAta,
+		vault3,
+		tokLock,
+		user3Kp,
+		usdcMint,
+		6,
+		amount,
+		unlockAt,
+		unlockAt,
+	);
+	ataBalCk(vaultAta, free + amount, "Vault3");
+
+	const withdraw = (amt: bigint, expectedError = "") =>
+		lgcWithdraw(
+			user3Kp,
+			vaultAta,
+			user3Ata,
+			vault3,
+			usdcMint,
+			configPDA,
+			6,
+			amt,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+		);
+	withdraw(free + 1n, "0x86");
+	unlockTok(user3Kp, tokLock, admin, usdcMint, "0x86");
+
+	warpTime(10 * day);
+	const user3Bf = ataBalc(user3Ata, "User3");
+	withdraw(amount);
+	ataBalCk(user3Ata, user3Bf + amount, "User3");
+	unlockTok(user3Kp, tokLock, admin, usdcMint);
+	acctIsNull(tokLock);
+});
@@ -0,0 +1,119 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { decodeConfigDev, Status } from "./decoder";
+import {
+	ataBalCk,
+	closeConfig,
+	configPDA,
+	configResize,
+	findPdaV1,
+	findProposal,
+	getAta,
+	initConfig,
+	lgcRedeem,
+	multisigInit,
+	ProposalAction,
+	proposalApprove,
+	proposalExecute,
+	proposalMake,
+	readAcct,
+	setAta,
+	setLgcMint,
+	updateConfig,
+	vaultO,
+	vaultProgAddr,
+} from "./litesvm-utils";
+import { ll } from "./utils";
+import {
+	admin,
+	adminKp,
+	hackerKp,
+	owner,
+	ownerKp,
+	pyusdMint,
+	usdcMint,
+	usdgMint,
+	usdtMint,
+	user1,
+	user1Kp,
+	user2,
+	user2Kp,
+	user3,
+} from "./web3jsSetup";
+
+const multisig = findPdaV1(configPDA, "Multisig", "multisig").pda;
+const members = [admin, user1, user2];
+const proposal1 = findProposal(multisig, 1n).pda;
+
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	setLgcMint(usdcMint);
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		ownerKp,
+		[usdcMint, usdtMint, pyusdMint, usdgMint],
+		owner,
+		admin,
+		true,
+		Status.Active,
+		111000000n,
+		"multisig",
+	);
+});
+
+test("Owner hands prog_owner to a 2-of-3 Multisig", () => {
+	ll("\n------== Owner hands prog_owner to a Multisig");
+	multisigInit(hackerKp, configPDA, multisig, members, 2, "0x2");
+	multisigInit(ownerKp, configPDA, multisig, [admin, admin], 1, "0x8a");
+	multisigInit(ownerKp, configPDA, multisig, members, 4, "0x8a");
+	multisigInit(ownerKp, configPDA, multisig, members, 2);
+	const decoded = decodeConfigDev(readAcct(configPDA, vaultProgAddr));
+	expect(decoded.progOwner).toEqual(multisig);
+});
+
+test("Vault tokens stay redeemable after the handover", () => {
+	ll("\n------== Redeem from the vault after the handover");
+	//the vault is seeded by the config creator, not the current prog_owner
+	const vaultAta = getAta(usdcMint, vaultO);
+	const userAta = getAta(usdcMint, user1);
+	setAta(usdcMint, vaultO, 1000n);
+	lgcRedeem(user1Kp, vaultAta, userAta, vaultO, configPDA, usdcMint, 6, 400n);
+	ataBalCk(vaultAta, 600n, "vaultO");
+	ataBalCk(userAta, 400n, "user1");
+});
+
+test("Admin cannot bypass the Multisig", () => {
+	ll("\n------== Admin cannot update, resize or close");
+	const mints = [usdcMint, usdtMint, pyusdMint, usdgMint];
+	updateConfig(adminKp, admin, [1, 1, 0, 0], 0, 123000000n, "0xbc");
+	configResize(adminKp, configPDA, 1024n, "0xbc");
+	closeConfig(adminKp, configPDA, multisig, mints, false, "0xbc");
+});
+
+test("Proposal executes once approvals meet the threshold", () => {
+	ll("\n------== Proposal to set a new admin");
+	const setAdmin = ProposalAction.SetAdmin;
+	proposalMake(
+		hackerKp,
+		multisig,
+		proposal1,
+		1n,
+		setAdmin,
+		0,
+		0n,
+		user3,
+		"0x8b",
+	);
+	proposalMake(user1Kp, multisig, proposal1, 1n, setAdmin, 0, 0n, user3);
+
+	proposalExecute(user1Kp, multisig, proposal1, configPDA, "0x8e");
+	proposalApprove(hackerKp, multisig, proposal1, "0x8b");
+	proposalApprove(user2Kp, multisig, proposal1);
+	proposalExecute(adminKp, multisig, proposal1, configPDA);
+
+	const decoded = decodeConfigDev(readAcct(configPDA, vaultProgAddr));
+	expect(decoded.admin).toEqual(user3);
+	proposalExecute(adminKp, multisig, proposal1, configPDA, "0x8d");
+});
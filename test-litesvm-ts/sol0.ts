@@ -29,7 +29,7 @@ let amount: bigint;
 let amtDeposit: bigint;
 let amtWithdraw: bigint;
 let balcAf: bigint | null;
-const vaultRent = 1002240n; //from Rust
+const vaultRent = 1259760n; //from Rust
 //const initUsdcBalc = bigintAmt(1000, 6);
 
 const balcBf = svm.getBalance(admin);
@@ -0,0 +1,197 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { PublicKey } from "@solana/web3.js";
+import { decodeConfigDev, Status } from "./decoder";
+import {
+	acctIsNull,
+	ataBalCk,
+	closeConfig,
+	configPDA,
+	day,
+	findPdaV1,
+	findQueuedAction,
+	getAta,
+	initConfig,
+	lgcRedeem,
+	ProposalAction,
+	readAcct,
+	setAta,
+	setLgcMint,
+	timelockCancel,
+	timelockExecute,
+	timelockInit,
+	timelockQueue,
+	updateConfig,
+	vaultMintAtas,
+	vaultO,
+	vaultProgAddr,
+	warpTime,
+} from "./litesvm-utils";
+import { ll } from "./utils";
+import {
+	admin,
+	adminKp,
+	hackerKp,
+	owner,
+	ownerKp,
+	pyusdMint,
+	usdcMint,
+	usdgMint,
+	usdtMint,
+	user1,
+	user1Kp,
+} from "./web3jsSetup";
+
+const timelock = findPdaV1(configPDA, "Timelock", "timelock").pda;
+const delay = 2 * day;
+const queued1 = findQueuedAction(timelock, 1n).pda;
+const queued2 = findQueuedAction(timelock, 2n).pda;
+const queued3 = findQueuedAction(timelock, 3n).pda;
+const queued4 = findQueuedAction(timelock, 4n).pda;
+const newDelay = day;
+const noAcct = PublicKey.default;
+const mints = [usdcMint, usdtMint, pyusdMint, usdgMint];
+
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	setLgcMint(usdcMint);
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		ownerKp,
+		mints,
+		owner,
+		admin,
+		true,
+		Status.Active,
+		111000000n,
+		"timelock",
+	);
+});
+
+test("Owner hands prog_owner to a Timelock", () => {
+	ll("\n------== Owner hands prog_owner to a Timelock");
+	timelockInit(hackerKp, configPDA, timelock, delay, "0x2");
+	timelockInit(ownerKp, configPDA, timelock, delay);
+	const decoded = decodeConfigDev(readAcct(configPDA, vaultProgAddr));
+	expect(decoded.progOwner).toEqual(timelock);
+	expect(decoded.timelockDelay).toEqual(delay);
+
+	//direct changes by the admin are refused
+	const funcSelector = 1;
+	updateConfig(adminKp, admin, [funcSelector, 1, 0, 0], 0, 5n, "0x92");
+	closeConfig(adminKp, configPDA, admin, mints, false, "0x92");
+});
+
+test("Queued fee change runs only after its eta", () => {
+	ll("\n------== Queued fee change");
+	const newFee = 222000000n;
+	const setFee = ProposalAction.SetFee;
+	timelockQueue(
+		hackerKp,
+		timelock,
+		configPDA,
+		queued1,
+		1n,
+		setFee,
+		0,
+		newFee,
+		noAcct,
+		"0x2",
+	);
+	timelockQueue(
+		ownerKp,
+		timelock,
+		configPDA,
+		queued1,
+		1n,
+		setFee,
+		0,
+		newFee,
+		noAcct,
+	);
+
+	timelockExecute(hackerKp, timelock, queued1, configPDA, owner, "0x91");
+	warpTime(delay);
+	timelockExecute(hackerKp, timelock, queued1, configPDA, owner);
+	const decoded = decodeConfigDev(readAcct(configPDA, vaultProgAddr));
+	expect(decoded.fee).toEqual(newFee);
+	acctIsNull(queued1);
+});
+
+test("Owner cancels a queued admin change", () => {
+	ll("\n------== Owner cancels a queued admin change");
+	const setAdmin = ProposalAction.SetAdmin;
+	timelockQueue(
+		ownerKp,
+		timelock,
+		configPDA,
+		queued2,
+		2n,
+		setAdmin,
+		0,
+		0n,
+		user1,
+	);
+	timelockCancel(hackerKp, timelock, queued2, "0x2");
+	timelockCancel(ownerKp, timelock, queued2);
+	acctIsNull(queued2);
+});
+
+test("Vault tokens stay redeemable after the handover", () => {
+	ll("\n------== Redeem from the vault after the handover");
+	const vaultAta = getAta(usdcMint, vaultO);
+	const userAta = getAta(usdcMint, user1);
+	setAta(usdcMint, vaultO, 1000n);
+	lgcRedeem(user1Kp, vaultAta, userAta, vaultO, configPDA, usdcMint, 6, 1000n);
+	ataBalCk(vaultAta, 0n, "vaultO");
+	ataBalCk(userAta, 1000n, "user1");
+});
+
+test("Queued SetDelay changes the Config delay", () => {
+	ll("\n------== Queued SetDelay");
+	const setDelay = ProposalAction.SetDelay;
+	timelockQueue(
+		ownerKp,
+		timelock,
+		configPDA,
+		queued4,
+		4n,
+		setDelay,
+		0,
+		BigInt(newDelay),
+		noAcct,
+	);
+	warpTime(delay);
+	timelockExecute(hackerKp, timelock, queued4, configPDA, owner);
+	const decoded = decodeConfigDev(readAcct(configPDA, vaultProgAddr));
+	expect(decoded.timelockDelay).toEqual(newDelay);
+});
+
+test("Queued CloseConfig closes the config after its eta", () => {
+	ll("\n------== Queued CloseConfig");
+	const closeCfg = ProposalAction.CloseConfig;
+	timelockQueue(
+		ownerKp,
+		timelock,
+		configPDA,
+		queued3,
+		3n,
+		closeCfg,
+		0,
+		0n,
+		noAcct,
+	);
+	warpTime(newDelay);
+	timelockExecute(
+		ownerKp,
+		timelock,
+		queued3,
+		configPDA,
+		owner,
+		"",
+		vaultMintAtas(mints),
+	);
+	acctIsNull(configPDA);
+});
@@ -0,0 +1,120 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+	acctExists,
+	acctIsNull,
+	readAcct,
+	Tok22ExtFlag,
+	tok22InitMint,
+	tok22UpdateMetadata,
+} from "./litesvm-utils";
+import { ll } from "./utils";
+import { adminKp, dgcAuthorityKp } from "./web3jsSetup";
+
+const mintKp = new Keypair();
+const mint = mintKp.publicKey;
+const decimals = 6;
+
+const hasStr = (data: Uint8Array | undefined, str: string) =>
+	Buffer.from(data!).includes(Buffer.from(str));
+//extension types from the TLV entries after the AccountType byte
+const extTypes = (data: Uint8Array | undefined) => {
+	const buf = Buffer.from(data!);
+	const types: number[] = [];
+	let offset = 166;
+	while (offset + 4 <= buf.length) {
+		const extType = buf.readUInt16LE(offset);
+		if (extType === 0) break;
+		types.push(extType);
+		offset += 4 + buf.readUInt16LE(offset + 2);
+	}
+	return types;
+};
+
+test("Token2022 Mint with Metadata", () => {
+	ll("\n------== Token2022 Mint with Metadata");
+	acctIsNull(mint);
+	tok22InitMint(
+		adminKp,
+		mintKp,
+		dgcAuthorityKp,
+		dgcAuthorityKp.publicKey,
+		decimals,
+		"DragonCoin",
+		"DGC",
+		"https://dragoncoin.io/dgc.json",
+	);
+	acctExists(mint);
+	const data = readAcct(mint, TOKEN_2022_PROGRAM_ID);
+	expect(data!.length).toBeGreaterThan(166);
+	expect(data![165]).toBe(1); //AccountType Mint
+	expect(hasStr(data, "DragonCoin")).toBe(true);
+	expect(hasStr(data, "DGC")).toBe(true);
+	expect(hasStr(data, "https://dragoncoin.io/dgc.json")).toBe(true);
+});
+
+test("Token2022 Update Metadata", () => {
+	ll("\n------== Token2022 Update Metadata");
+	tok22UpdateMetadata(dgcAuthorityKp, mint, 2, "", "https://dragoncoin.io/v2.json");
+	let data = readAcct(mint, TOKEN_2022_PROGRAM_ID);
+	expect(hasStr(data, "https://dragoncoin.io/v2.json")).toBe(true);
+
+	tok22UpdateMetadata(dgcAuthorityKp, mint, 3, "website", "dragoncoin.io");
+	data = readAcct(mint, TOKEN_2022_PROGRAM_ID);
+	expect(hasStr(data, "website")).toBe(true);
+});
+
+test("Token2022 Mint with optional extensions", () => {
+	ll("\n------== Token2022 Mint with optional extensions");
+	const mintKp2 = new Keypair();
+	tok22InitMint(
+		adminKp,
+		mintKp2,
+		dgcAuthorityKp,
+		dgcAuthorityKp.publicKey,
+		decimals,
+		"FeeCoin",
+		"FEE",
+		"https://feecoin.io/fee.json",
+		{
+			flags:
+				Tok22ExtFlag.TransferFee |
+				Tok22ExtFlag.InterestBearing |
+				Tok22ExtFlag.MintCloseAuthority,
+			transferFeeBps: 50,
+			maximumFee: 5_000_000n,
+			interestRate: 300,
+			defaultState: 0,
+			permanentDelegate: PublicKey.default,
+		},
+	);
+	const data = readAcct(mintKp2.publicKey, TOKEN_2022_PROGRAM_ID);
+	//TransferFeeConfig 1, MintCloseAuthority 3, InterestBearingConfig 10, MetadataPointer 18, TokenMetadata 19
+	expect(extTypes(data).sort((a, b) => a - b)).toStrictEqual([1, 3, 10, 18, 19]);
+});
+
+test("Token2022 Mint rejects a transfer fee on a NonTransferable mint", () => {
+	ll("\n------== Token2022 Mint rejects TransferFee with NonTransferable");
+	tok22InitMint(
+		adminKp,
+		new Keypair(),
+		dgcAuthorityKp,
+		dgcAuthorityKp.publicKey,
+		decimals,
+		"StuckCoin",
+		"STK",
+		"https://stuckcoin.io/stk.json",
+		{
+			flags: Tok22ExtFlag.TransferFee | Tok22ExtFlag.NonTransferable,
+			transferFeeBps: 50,
+			maximumFee: 5_000_000n,
+			interestRate: 0,
+			defaultState: 0,
+			permanentDelegate: PublicKey.default,
+		},
+		undefined,
+		"0xbf",
+	);
+});
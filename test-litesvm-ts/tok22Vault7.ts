@@ -0,0 +1,235 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { decodeConfigDev, Status } from "./decoder";
+import {
+	ataBalCk,
+	configPDA,
+	findEscrow,
+	findMakerIndex,
+	findPdaV1,
+	getAta,
+	initConfig,
+	lgcDeposit,
+	lgcPay,
+	lgcRedeem,
+	lgcWithdraw,
+	makeTokEscrow,
+	setLgcMint,
+	svm,
+	takeTokEscrow,
+	Tok22ExtFlag,
+	tok22InitMint,
+	tok22MintToken,
+	vaultO,
+} from "./litesvm-utils";
+import { as6zBn, ll } from "./utils";
+import {
+	adminKp,
+	dgcAuthority,
+	dgcAuthorityKp,
+	owner,
+	pyusdMint,
+	usdgMint,
+	user1,
+	user1Kp,
+	user2,
+	user2Kp,
+	usdtMint,
+} from "./web3jsSetup";
+
+const tokenProg = TOKEN_2022_PROGRAM_ID;
+const mintKp = new Keypair();
+const mint = mintKp.publicKey;
+const decimals = 6;
+//50 bps transfer fee, withheld in the receiving token account
+const feeBps = 50n;
+const fee = (amt: bigint) => (amt * feeBps) / 10000n;
+
+const userAta = getAta(mint, user1, true, tokenProg);
+const vaultOut1 = findPdaV1(user1, "signerVault");
+const vaultAta1 = getAta(mint, vaultOut1.pda, true, tokenProg);
+const vaultAtaO = getAta(mint, vaultO, true, tokenProg);
+
+test("Token2022 Mint with TransferFee", () => {
+	ll("\n------== Token2022 Mint with TransferFee");
+	tok22InitMint(
+		adminKp,
+		mintKp,
+		dgcAuthorityKp,
+		dgcAuthority,
+		decimals,
+		"FeeCoin",
+		"FEE",
+		"https://feecoin.io/fee.json",
+		{
+			flags: Tok22ExtFlag.TransferFee,
+			transferFeeBps: Number(feeBps),
+			maximumFee: as6zBn(1000),
+			interestRate: 0,
+			defaultState: 0,
+			permanentDelegate: PublicKey.default,
+		},
+	);
+	tok22MintToken(dgcAuthorityKp, user1, mint, userAta, decimals, as6zBn(2000));
+	ataBalCk(userAta, as6zBn(2000), "user1");
+});
+
+test("InitConfig with a Token2022 mint", () => {
+	ll("\n------== InitConfig with a Token2022 mint");
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		user1Kp,
+		[mint, usdtMint, pyusdMint, usdgMint],
+		owner,
+		dgcAuthority,
+		true,
+		Status.Active,
+		111000000n,
+		"Token2022 vault",
+	);
+});
+
+test("Deposit/Withdraw Token2022 Tokens", () => {
+	ll("\n------== Deposit/Withdraw Token2022 Tokens");
+	const amtDeposit = as6zBn(1000);
+	lgcDeposit(
+		user1Kp,
+		userAta,
+		vaultAta1,
+		vaultOut1.pda,
+		mint,
+		configPDA,
+		decimals,
+		amtDeposit,
+		tokenProg,
+	);
+	ataBalCk(userAta, as6zBn(1000), "user1");
+	ataBalCk(vaultAta1, amtDeposit - fee(amtDeposit), "vault1");
+
+	const amtWithdraw = as6zBn(100);
+	lgcWithdraw(
+		user1Kp,
+		vaultAta1,
+		userAta,
+		vaultOut1.pda,
+		mint,
+		configPDA,
+		decimals,
+		amtWithdraw,
+		tokenProg,
+	);
+	ataBalCk(vaultAta1, amtDeposit - fee(amtDeposit) - amtWithdraw, "vault1");
+	ataBalCk(userAta, as6zBn(1000) + amtWithdraw - fee(amtWithdraw), "user1");
+});
+
+test("Pay/Redeem Token2022 Tokens", () => {
+	ll("\n------== Pay/Redeem Token2022 Tokens");
+	const userBalc = as6zBn(1100) - fee(as6zBn(100));
+	const amtPay = as6zBn(200);
+	lgcPay(
+		user1Kp,
+		userAta,
+		vaultAtaO,
+		vaultO,
+		mint,
+		configPDA,
+		decimals,
+		amtPay,
+		tokenProg,
+	);
+	ataBalCk(vaultAtaO, amtPay - fee(amtPay), "vaultO");
+	ataBalCk(userAta, userBalc - amtPay, "user1");
+
+	//net received amount is recorded
+	const decoded = decodeConfigDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.tokenBalance).toStrictEqual(amtPay - fee(amtPay));
+
+	const amtRedeem = as6zBn(50);
+	lgcRedeem(
+		user1Kp,
+		vaultAtaO,
+		userAta,
+		vaultO,
+		configPDA,
+		mint,
+		decimals,
+		amtRedeem,
+		tokenProg,
+	);
+	ataBalCk(vaultAtaO, amtPay - fee(amtPay) - amtRedeem, "vaultO");
+	ataBalCk(
+		userAta,
+		userBalc - amtPay + amtRedeem - fee(amtRedeem),
+		"user1",
+	);
+});
+
+test("Escrow with Token2022 mints", () => {
+	ll("\n------== Escrow with Token2022 mints");
+	const mintKpY = new Keypair();
+	const mintY = mintKpY.publicKey;
+	tok22InitMint(
+		adminKp,
+		mintKpY,
+		dgcAuthorityKp,
+		dgcAuthority,
+		decimals,
+		"PlainCoin",
+		"PLN",
+		"https://plaincoin.io/pln.json",
+	);
+	const takerAtaY = getAta(mintY, user2, true, tokenProg);
+	tok22MintToken(dgcAuthorityKp, user2, mintY, takerAtaY, decimals, as6zBn(500));
+
+	const id = 1n;
+	const escrowPDA = findEscrow(user1, id).pda;
+	const escrowAtaX = getAta(mint, escrowPDA, true, tokenProg);
+	const escrowAtaY = getAta(mintY, escrowPDA, true, tokenProg);
+	const takerAtaX = getAta(mint, user2, true, tokenProg);
+	const amountX = as6zBn(100);
+	const amountY = as6zBn(50);
+	makeTokEscrow(
+		user1Kp,
+		userAta,
+		escrowAtaX,
+		mint,
+		mintY,
+		escrowPDA,
+		configPDA,
+		findMakerIndex(user1).pda,
+		decimals,
+		amountX,
+		decimals,
+		amountY,
+		id,
+		tokenProg,
+	);
+	//the escrow records what it received after the transfer fee
+	const receivedX = amountX - fee(amountX);
+	ataBalCk(escrowAtaX, receivedX, "escrow X");
+
+	takeTokEscrow(
+		user2Kp,
+		takerAtaX,
+		takerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mint,
+		mintY,
+		escrowPDA,
+		configPDA,
+		decimals,
+		receivedX,
+		decimals,
+		amountY,
+		0,
+		tokenProg,
+	);
+	ataBalCk(takerAtaX, receivedX - fee(receivedX), "taker X");
+	ataBalCk(escrowAtaY, amountY, "escrow Y");
+	ataBalCk(takerAtaY, as6zBn(450), "taker Y");
+});
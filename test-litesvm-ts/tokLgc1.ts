@@ -2,17 +2,36 @@
 import { expect, test } from "bun:test";
 //Tutorial: <https://litesvm.github.io/litesvm/tutorial.html>
 import { Connection, type Keypair, type PublicKey } from "@solana/web3.js";
-import { Status } from "./decoder";
+import {
+	decodeConfigDev,
+	decodeDistributor,
+	decodeInvoice,
+	decodeStream,
+	decodeSubscription,
+	Status,
+} from "./decoder";
 import {
 	acctExists,
 	acctIsNull,
 	ataBalCk,
 	configPDA,
+	day,
 	depositSol,
+	claimLeaf,
+	distributorClaim,
+	distributorClawback,
+	distributorMake,
+	eventsInLogs,
+	findDistributor,
+	findInvoice,
 	findPdaV1,
+	findStream,
+	findSubscription,
 	getAta,
 	initConfig,
 	initSolBalc,
+	invoiceMake,
+	invoicePay,
 	lgcDeposit,
 	lgcInitAta,
 	lgcInitMint,
@@ -20,13 +39,24 @@ import {
 	lgcPay,
 	lgcRedeem,
 	lgcWithdraw,
+	merkleLevels,
+	merkleProof,
+	merkleRoot,
 	type PdaOut,
+	readAcct,
 	setAtaCheck,
 	setLgcMint,
+	streamCancel,
+	streamMake,
+	streamWithdraw,
+	subscriptionCancel,
+	subscriptionMake,
+	subscriptionPull,
 	svm,
 	vault1,
 	vaultAta1,
 	vaultO,
+	warpTime,
 } from "./litesvm-utils";
 import { as6zBn, as9zBn, bigintAmt, ll } from "./utils";
 import {
@@ -35,6 +65,7 @@ import {
 	dgcAuthorityKp,
 	dragonCoinKp,
 	hacker,
+	hackerKp,
 	owner,
 	ownerKp,
 	pyusdMint,
@@ -44,7 +75,9 @@ import {
 	user1,
 	user1Kp,
 	user2,
+	user2Kp,
 	user3,
+	user3Kp,
 } from "./web3jsSetup";
 
 let signerKp: Keypair;
@@ -65,7 +98,19 @@ let balcAf: bigint | null;
 const decDgc = 9;
 const initDgcBalc = bigintAmt(9000, decDgc);
 const initUsdcBalc = bigintAmt(1000, 6);
-//const vaultRent = 1002240n; //from Rust
+//Config ledger of USDC, mint0, held in the config vault and user vaults
+const usdcLedgerCk = (expected: bigint) => {
+	const decoded = decodeConfigDev(readAcct(configPDA));
+	expect(decoded.tokenBalances[0]).toEqual(expected);
+};
+const usdcCommittedCk = (expected: bigint) => {
+	const decoded = decodeConfigDev(readAcct(configPDA));
+	expect(decoded.committed[0]).toEqual(expected);
+};
+//a Config and vault of the hacker's own, to be passed in place of the real ones
+const hackerConfig = findPdaV1(hacker, "HackerConfig", "config").pda;
+const hackerVault = findPdaV1(hacker, "HackerVault").pda;
+//const vaultRent = 1259760n; //from Rust
 
 balcBf = svm.getBalance(admin);
 ll("admin SOL:", balcBf);
@@ -153,6 +198,16 @@ test("InitConfig", () => {
 		fee,
 		str,
 	);
+	initConfig(
+		hackerKp,
+		mints,
+		hacker,
+		hacker,
+		isAuthorized,
+		status,
+		fee,
+		"Hacker Config",
+	);
 });
 
 test("Deposit Legacy Tokens", () => {
@@ -179,6 +234,7 @@ test("Deposit Legacy Tokens", () => {
 	);
 	ataBalCk(toAta, as6zBn(370), "vault1");
 	ataBalCk(fromAta, as6zBn(630), "user1 ");
+	usdcLedgerCk(as6zBn(370));
 });
 test("Withdraw Legacy Tokens", () => {
 	ll("\n------== Withdraw Legacy Tokens");
@@ -192,9 +248,19 @@ test("Withdraw Legacy Tokens", () => {
 	vaultOut = findPdaV1(signer, "signerVault");
 	fromAta = getAta(mint, vaultOut.pda);
 
-	lgcWithdraw(signerKp, fromAta, toAta, vaultOut.pda, mint, decimals, amt);
+	lgcWithdraw(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		decimals,
+		amt,
+	);
 	ataBalCk(fromAta, as6zBn(250), "vault1");
 	ataBalCk(toAta, as6zBn(750), "user1 ");
+	usdcLedgerCk(as6zBn(250));
 });
 
 test("Owner Deposits SOL to VaultPDA", () => {
@@ -210,7 +276,7 @@ test("Owner Deposits SOL to VaultPDA", () => {
 	//sendSol(...) makes accounts, not PDA controlled by the
 	balcAf = svm.getBalance(vaultO);
 	ll("vaultO SOL:", balcAf);
-	const vaultRent = 1002240n; //from Rust
+	const vaultRent = 1259760n; //from Rust
 	expect(balcAf).toStrictEqual(vaultRent + amtDeposit);
 });
 
@@ -229,6 +295,7 @@ test("Pay Legacy Tokens", () => {
 	lgcPay(signerKp, fromAta, toAta, vaultO, mint, configPDA, decimals, amt);
 	ataBalCk(toAta, amt, "vaultO");
 	ataBalCk(fromAta, as6zBn(424), "user1 ");
+	usdcLedgerCk(as6zBn(576));
 });
 test("Redeem Legacy Tokens", () => {
 	ll("\n------== Redeem Legacy Tokens");
@@ -254,6 +321,362 @@ test("Redeem Legacy Tokens", () => {
 	);
 	ataBalCk(fromAta, as6zBn(289), "vaultO");
 	ataBalCk(toAta, as6zBn(461), "user1 ");
+	usdcLedgerCk(as6zBn(539));
+});
+
+test("Subscription Pulls Legacy Tokens", () => {
+	ll("\n------== Subscription Pulls Legacy Tokens");
+	signerKp = user1Kp;
+	mint = usdcMint;
+	decimals = 6;
+	amt = as6zBn(10);
+
+	signer = signerKp.publicKey;
+	fromAta = getAta(mint, signer);
+	toAta = getAta(mint, vaultO);
+	const subscription = findSubscription(signer, mint).pda;
+	subscriptionMake(
+		signerKp,
+		mint,
+		subscription,
+		configPDA,
+		decimals,
+		amt,
+		day,
+		2,
+	);
+	const decoded = decodeSubscription(readAcct(subscription));
+	expect(decoded.amount).toEqual(amt);
+	expect(decoded.period).toEqual(day);
+	expect(decoded.maxPeriods).toEqual(2);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	const paidThrough = decoded.paidThrough;
+
+	//anyone can pull a due payment, once per period, but only into the subscribed vault
+	const pull = (expectedError = "", vault = vaultO, config = configPDA) =>
+		subscriptionPull(
+			adminKp,
+			signer,
+			vault,
+			mint,
+			subscription,
+			config,
+			expectedError,
+		);
+	pull("0x34", hackerVault, hackerConfig);
+	pull();
+	ataBalCk(toAta, as6zBn(299), "vaultO");
+	ataBalCk(fromAta, as6zBn(451), "user1 ");
+	pull("0xaf");
+	warpTime(day);
+	pull();
+	ataBalCk(toAta, as6zBn(309), "vaultO");
+	const paid = decodeSubscription(readAcct(subscription));
+	expect(paid.paidPeriods).toEqual(2);
+	expect(paid.paidThrough).toEqual(paidThrough + BigInt(2 * day));
+	warpTime(day);
+	pull("0xb0");
+
+	subscriptionCancel(hackerKp, mint, subscription, "0x4");
+	subscriptionCancel(signerKp, mint, subscription);
+	acctIsNull(subscription);
+	pull("0xae");
+});
+
+test("Stream Legacy Tokens from the Vault", () => {
+	ll("\n------== Stream Legacy Tokens from the Vault");
+	mint = usdcMint;
+	decimals = 6;
+	const rate = as6zBn(1); //per second
+	toAta = getAta(mint, user2);
+	fromAta = getAta(mint, vaultO);
+	const stream = findStream(user2, mint).pda;
+	const now = Number(svm.getClock().unixTimestamp);
+	const make = (
+		funderKp: Keypair,
+		startAt: number,
+		endAt: number,
+		expectedError = "",
+	) =>
+		streamMake(
+			funderKp,
+			user2,
+			vaultO,
+			mint,
+			stream,
+			configPDA,
+			decimals,
+			rate,
+			startAt,
+			endAt,
+			expectedError,
+		);
+	make(hackerKp, now, now + 100, "0x3");
+	make(ownerKp, now + 100, now + 100, "0xb2");
+	make(ownerKp, now, now + 100);
+	const decoded = decodeStream(readAcct(stream));
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	expect(decoded.rate).toEqual(rate);
+	expect(decoded.endAt).toEqual(now + 100);
+	usdcCommittedCk(as6zBn(100));
+
+	//the vault holds 309, so only 209 is not committed to the stream
+	const stream1 = findStream(user1, mint).pda;
+	streamMake(
+		ownerKp,
+		user1,
+		vaultO,
+		mint,
+		stream1,
+		configPDA,
+		decimals,
+		as6zBn(3),
+		now,
+		now + 70,
+		"0xbd",
+	);
+	const redeem = (amount: bigint, expectedError = "") =>
+		lgcRedeem(
+			user1Kp,
+			fromAta,
+			getAta(mint, user1),
+			vaultO,
+			configPDA,
+			mint,
+			decimals,
+			amount,
+			undefined,
+			undefined,
+			[],
+			expectedError,
+		);
+	redeem(as6zBn(210), "0xbd");
+
+	const withdraw = (recipientKp: Keypair, expectedError = "") =>
+		streamWithdraw(
+			recipientKp,
+			vaultO,
+			mint,
+			stream,
+			configPDA,
+			expectedError,
+		);
+	warpTime(40);
+	withdraw(hackerKp, "0x4");
+	//a Stream made on the hacker's own Config cannot be paid from vaultO
+	const hackerStream = findStream(hacker, mint, hackerConfig).pda;
+	setAtaCheck(mint, hackerVault, as6zBn(100), "HackerVault USDC");
+	streamMake(
+		hackerKp,
+		hacker,
+		hackerVault,
+		mint,
+		hackerStream,
+		hackerConfig,
+		decimals,
+		rate,
+		now,
+		now + 100,
+	);
+	streamWithdraw(hackerKp, vaultO, mint, hackerStream, configPDA, "0x34");
+	withdraw(user2Kp);
+	ataBalCk(toAta, as6zBn(1040), "user2 ");
+	ataBalCk(fromAta, as6zBn(269), "vaultO");
+	usdcCommittedCk(as6zBn(60));
+	withdraw(user2Kp, "0xb3");
+
+	//the accrued 20 goes to user2, the unvested 40 stays in the vault
+	warpTime(20);
+	streamCancel(hackerKp, user2, vaultO, mint, stream, configPDA, "0x3");
+	streamCancel(ownerKp, user2, vaultO, mint, stream, configPDA);
+	ataBalCk(toAta, as6zBn(1060), "user2 ");
+	ataBalCk(fromAta, as6zBn(249), "vaultO");
+	usdcCommittedCk(0n);
+	acctIsNull(stream);
+	withdraw(user2Kp, "0xb1");
+});
+
+test("Merkle Airdrop of Legacy Tokens from the Vault", () => {
+	ll("\n------== Merkle Airdrop of Legacy Tokens from the Vault");
+	signerKp = user1Kp; //config admin
+	signer = signerKp.publicKey;
+	mint = usdcMint;
+	decimals = 6;
+	const id = 1n;
+	const claims: [PublicKey, bigint][] = [
+		[user2, as6zBn(5)],
+		[user3, as6zBn(7)],
+		[signer, as6zBn(3)],
+	];
+	const levels = merkleLevels(
+		claims.map(([claimant, amount], index) =>
+			claimLeaf(index, claimant, amount),
+		),
+	);
+	const root = merkleRoot(levels);
+	const distributor = findDistributor(signer, id).pda;
+	const deadline = svm.getClock().unixTimestamp + BigInt(day);
+	const make = (funderKp: Keypair, expectedError = "") =>
+		distributorMake(
+			funderKp,
+			vaultO,
+			mint,
+			findDistributor(funderKp.publicKey, id).pda,
+			configPDA,
+			decimals,
+			id,
+			as6zBn(15),
+			deadline,
+			claims.length,
+			root,
+			expectedError,
+		);
+	make(hackerKp, "0x3");
+	make(signerKp);
+	fromAta = getAta(mint, vaultO);
+	ataBalCk(fromAta, as6zBn(264), "vaultO");
+	ataBalCk(getAta(mint, signer), as6zBn(436), "user1 ");
+	const decoded = decodeDistributor(readAcct(distributor));
+	expect(decoded.total).toEqual(as6zBn(15));
+	expect(decoded.count).toEqual(3);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	usdcCommittedCk(as6zBn(15));
+	//a Distributor made on the hacker's own Config cannot be paid from vaultO
+	const hackerDistributor = findDistributor(hacker, id).pda;
+	distributorMake(
+		hackerKp,
+		hackerVault,
+		mint,
+		hackerDistributor,
+		hackerConfig,
+		decimals,
+		id,
+		as6zBn(15),
+		deadline,
+		claims.length,
+		root,
+	);
+	distributorClaim(
+		user2Kp,
+		vaultO,
+		mint,
+		hackerDistributor,
+		configPDA,
+		0,
+		as6zBn(5),
+		merkleProof(levels, 0),
+		"0x34",
+	);
+
+	const claim = (
+		claimantKp: Keypair,
+		index: number,
+		amount: bigint,
+		expectedError = "",
+	) =>
+		distributorClaim(
+			claimantKp,
+			vaultO,
+			mint,
+			distributor,
+			configPDA,
+			index,
+			amount,
+			merkleProof(levels, index),
+			expectedError,
+		);
+	claim(user2Kp, 0, as6zBn(5));
+	ataBalCk(getAta(mint, user2), as6zBn(1065), "user2 ");
+	claim(user2Kp, 0, as6zBn(5), "0xb6");
+	claim(hackerKp, 1, as6zBn(7), "0xb5");
+	claim(user3Kp, 1, as6zBn(8), "0xb5");
+	distributorClawback(signerKp, vaultO, mint, distributor, configPDA, "0xb7");
+	claim(user3Kp, 1, as6zBn(7));
+	ataBalCk(getAta(mint, user3), as6zBn(1007), "user3 ");
+	ataBalCk(fromAta, as6zBn(252), "vaultO");
+	usdcCommittedCk(as6zBn(3));
+
+	//user1 missed the deadline, so its 3 are clawed back
+	warpTime(day);
+	claim(signerKp, 2, as6zBn(3), "0xb7");
+	distributorClawback(hackerKp, vaultO, mint, distributor, configPDA, "0x3");
+	distributorClawback(signerKp, vaultO, mint, distributor, configPDA);
+	ataBalCk(getAta(mint, signer), as6zBn(439), "user1 ");
+	ataBalCk(fromAta, as6zBn(249), "vaultO");
+	usdcCommittedCk(0n);
+	acctIsNull(distributor);
+});
+
+test("Invoice Paid in Legacy Tokens", () => {
+	ll("\n------== Invoice Paid in Legacy Tokens");
+	signerKp = user1Kp; //config admin
+	signer = signerKp.publicKey;
+	mint = usdcMint;
+	decimals = 6;
+	amt = as6zBn(20);
+	toAta = getAta(mint, vaultO);
+	fromAta = getAta(mint, user2);
+	const dueAt = svm.getClock().unixTimestamp + BigInt(day);
+	const invoice1 = findInvoice(signer, 1n).pda;
+	const make = (
+		issuerKp: Keypair,
+		id: bigint,
+		payer?: PublicKey,
+		expectedError = "",
+	) =>
+		invoiceMake(
+			issuerKp,
+			mint,
+			findInvoice(issuerKp.publicKey, id).pda,
+			configPDA,
+			decimals,
+			id,
+			amt,
+			dueAt,
+			payer,
+			expectedError,
+		);
+	make(hackerKp, 1n, user2, "0x3");
+	make(signerKp, 1n, user2);
+	const pay = (
+		payerKp: Keypair,
+		invoice: PublicKey,
+		expectedError = "",
+		vault = vaultO,
+		config = configPDA,
+	) => invoicePay(payerKp, vault, mint, invoice, config, expectedError);
+	pay(hackerKp, invoice1, "0xba");
+	//invoice1 cannot be paid into the hacker's own vault
+	pay(user2Kp, invoice1, "0x34", hackerVault, hackerConfig);
+	const sendRes = pay(user2Kp, invoice1);
+	ataBalCk(fromAta, as6zBn(1045), "user2 ");
+	ataBalCk(toAta, as6zBn(269), "vaultO");
+	const decoded = decodeInvoice(readAcct(invoice1));
+	expect(decoded.status).toEqual(1);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	expect(decoded.paidBy).toEqual(user2.toBase58());
+	expect(decoded.received).toEqual(amt);
+
+	//InvoicePaid event: name, issuer, id, payer, mint, amount, received, paid_at
+	const [event] = eventsInLogs(sendRes);
+	expect(event?.[0]?.toString()).toEqual("InvoicePaid");
+	expect(event?.[1]).toEqual(signer.toBuffer());
+	expect(event?.[2]?.readBigUInt64LE()).toEqual(1n);
+	expect(event?.[3]).toEqual(user2.toBuffer());
+	expect(event?.[5]?.readBigUInt64LE()).toEqual(amt);
+	pay(user2Kp, invoice1, "0xb9");
+
+	//anyone can pay invoice 2, but only until it is due
+	make(signerKp, 2n);
+	const invoice2 = findInvoice(signer, 2n).pda;
+	//a program account of the Invoice length at another address is refused
+	const copied = findInvoice(signer, 3n).pda;
+	const invoice2Acct = svm.getAccount(invoice2);
+	if (!invoice2Acct) throw new Error("invoice2 not found");
+	svm.setAccount(copied, invoice2Acct);
+	pay(user3Kp, copied, "0xb8");
+	warpTime(day + 1);
+	pay(user3Kp, invoice2, "0xbb");
 });
 
 test.skip("copy accounts from devnet", async () => {
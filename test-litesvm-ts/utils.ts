@@ -8,6 +8,7 @@ import {
   getProgramDerivedAddress,
   getU8Encoder,
   getU16Decoder,
+  getU16Encoder,
   getU32Encoder,
   getU64Decoder,
   getU64Encoder,
@@ -128,6 +129,8 @@ export const numToBytes = (input: bigint | number, bit = 64) => {
     lamportsEncoder = getLamportsEncoder(getU64Encoder());
   } else if (bit === 32) {
     lamportsEncoder = getLamportsEncoder(getU32Encoder());
+  } else if (bit === 16) {
+    lamportsEncoder = getLamportsEncoder(getU16Encoder());
   } else if (bit === 8) {
     lamportsEncoder = getLamportsEncoder(getU8Encoder());
   } else {
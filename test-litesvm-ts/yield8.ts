@@ -0,0 +1,117 @@
+/** biome-ignore-all lint/style/noNonNullAssertion: <> */
+import { expect, test } from "bun:test";
+import { decodeConfig, decodeVault, Status } from "./decoder";
+import {
+	claimYield,
+	configPDA,
+	day,
+	depositSol,
+	findPdaV1,
+	initConfig,
+	setLgcMint,
+	svm,
+	vault1,
+	warpTime,
+	withdrawSol,
+	yieldFund,
+} from "./litesvm-utils";
+import { as9zBn, ll } from "./utils";
+import {
+	adminKp,
+	admin,
+	hackerKp,
+	owner,
+	pyusdMint,
+	usdcMint,
+	usdgMint,
+	user1,
+	user1Kp,
+	usdtMint,
+} from "./web3jsSetup";
+
+const yieldPool = findPdaV1(configPDA, "YieldPool", "yield").pda;
+const rateBps = 1000; //10% per year
+const poolFund = as9zBn(2);
+const principal = as9zBn(5);
+
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	setLgcMint(usdcMint);
+	setLgcMint(usdtMint);
+	setLgcMint(pyusdMint);
+	setLgcMint(usdgMint);
+	initConfig(
+		user1Kp,
+		[usdcMint, usdtMint, pyusdMint, usdgMint],
+		owner,
+		admin,
+		true,
+		Status.Active,
+		111000000n,
+		"SOL vault yield",
+	);
+});
+
+test("Admin funds the YieldPool", () => {
+	ll("\n------== Admin funds the YieldPool");
+	yieldFund(hackerKp, configPDA, yieldPool, rateBps, poolFund, "0x3");
+	yieldFund(adminKp, configPDA, yieldPool, 10001, poolFund, "0x81");
+	yieldFund(adminKp, configPDA, yieldPool, rateBps, poolFund);
+	expect(svm.getBalance(yieldPool)! > poolFund).toBe(true);
+	const config = decodeConfig(svm.getAccount(configPDA)?.data);
+	expect(config.yieldRateBps).toStrictEqual(rateBps);
+});
+
+test("User1 deposits SOL and binds the vault to the YieldPool", () => {
+	ll("\n------== User1 deposits SOL");
+	depositSol(user1Kp, vault1, principal, yieldPool);
+	const vault = decodeVault(svm.getAccount(vault1)?.data);
+	expect(vault.principal).toStrictEqual(principal);
+	expect(vault.accrued).toStrictEqual(0n);
+	expect(vault.pool.toString()).toStrictEqual(yieldPool.toBase58());
+});
+
+test("Yield accrues over a year and is claimed", () => {
+	ll("\n------== Yield accrues and is claimed");
+	warpTime(365 * day);
+	//once bound, the vault syncs only with its YieldPool
+	withdrawSol(user1Kp, vault1, as9zBn(1), "0x80");
+
+	const expected = as9zBn(0.5); //10% of 5 SOL
+	const poolBf = svm.getBalance(yieldPool)!;
+	const userBf = svm.getBalance(user1)!;
+	claimYield(user1Kp, vault1, yieldPool);
+	expect(poolBf - svm.getBalance(yieldPool)!).toStrictEqual(expected);
+	expect(svm.getBalance(user1)! - userBf).toBeLessThanOrEqual(expected);
+
+	const vault = decodeVault(svm.getAccount(vault1)?.data);
+	expect(vault.accrued).toStrictEqual(0n);
+	expect(vault.principal).toStrictEqual(principal);
+
+	//nothing more to claim
+	claimYield(user1Kp, vault1, yieldPool, "0x15");
+});
+
+test("Withdraw reduces the principal", () => {
+	ll("\n------== Withdraw reduces the principal");
+	const amtWithdraw = as9zBn(3);
+	withdrawSol(user1Kp, vault1, amtWithdraw, "", yieldPool);
+	const vault = decodeVault(svm.getAccount(vault1)?.data);
+	expect(vault.principal).toStrictEqual(principal - amtWithdraw);
+});
+
+test("A rate change only applies from now on", () => {
+	ll("\n------== A rate change only applies from now on");
+	const balance = principal - as9zBn(3);
+	warpTime(365 * day);
+	//doubling the rate must not reach back into the past year
+	yieldFund(adminKp, configPDA, yieldPool, rateBps * 2, 0n);
+	const config = decodeConfig(svm.getAccount(configPDA)?.data);
+	expect(config.yieldRateBps).toStrictEqual(rateBps * 2);
+	warpTime(365 * day);
+
+	const expected = (balance * 3n) / 10n; //10% then 20% of 2 SOL
+	const poolBf = svm.getBalance(yieldPool)!;
+	claimYield(user1Kp, vault1, yieldPool);
+	expect(poolBf - svm.getBalance(yieldPool)!).toStrictEqual(expected);
+});